@@ -54,6 +54,20 @@ pub fn create_cap_claim(cap_claim_entry: CapClaimEntry) -> ExternResult<HeaderHa
 /// recipient chain when a [ `CapGrant` ] is committed and shared. The tags are not checked or compared
 /// in any security sensitive contexts.
 ///
+/// An empty tag, as used by [ `ZomeCallCapGrant::unrestricted` ], means the grant is untagged, and any
+/// number of untagged grants can be created. A tagged grant is rejected with a [ `WasmError::Guest` ]
+/// if an active grant already has the same tag and functions, because the tag could no longer tell
+/// them apart. Use [ `update_cap_grant` ] to replace the existing grant instead, e.g. with a new secret.
+/// Assigned grants are also told apart by their assignees, so one tag can assign the same functions
+/// to many agents, one grant each, but not to the same agent twice.
+/// Grants created earlier in the same zome call are not checked, as per [ `get_cap_grants` ].
+///
+/// ```ignore
+/// create_cap_grant(
+///     CapGrantEntry::unrestricted(vec![(zome_info()?.zome_name, "foo".into())]).with_tag("foo"),
+/// )?;
+/// ```
+///
 /// Provided the grant author agent is reachable on the network:
 ///
 /// - [ `CapAccess::Unrestricted` ] access means any external agent can call the extern
@@ -103,7 +117,7 @@ pub fn create_cap_claim(cap_claim_entry: CapClaimEntry) -> ExternResult<HeaderHa
 /// extern at one time, or may apply in addition to a stricter grant. In this case, revoking a
 /// stricter grant, or failing to revoke all Unrestricted grants will leave the function open.
 ///
-/// @todo administration functions to query active grants
+/// The grants that are currently active can be listed with [ `get_cap_grants` ].
 ///
 /// There is an apparent "chicken or the egg" situation where [ `CapGrant` ] are required for remote
 /// agents to call externs, so how does an agent request a grant in the first place?
@@ -118,13 +132,54 @@ pub fn create_cap_claim(cap_claim_entry: CapClaimEntry) -> ExternResult<HeaderHa
 /// and the secret Bob received from her, then she commits a new CapClaim including the secret that
 /// Bob generated. Now Alice can call `foo` on Bob's machine any time he is online, and because all
 /// the secrets are [ `CapAccess::Assigned` ] Bob can track and update exactly who has access to his externs.
+///
+/// `CapGrantEntry::unrestricted` builds the common `init` grant for several functions at once:
+///
+/// ```ignore
+/// create_cap_grant(CapGrantEntry::unrestricted(vec![
+///     (zome_info()?.zome_name, "accept_foo_grant".into()),
+/// ]))?;
+/// ```
 pub fn create_cap_grant(cap_grant_entry: CapGrantEntry) -> ExternResult<HeaderHash> {
+    if !cap_grant_entry.tag.is_empty()
+        && get_cap_grants()?
+            .iter()
+            .any(|grant| grant.collides_with(&cap_grant_entry))
+    {
+        return Err(WasmError::Guest(format!(
+            "an active cap grant is already tagged {:?} for the same functions and assignees",
+            cap_grant_entry.tag
+        )));
+    }
     create(EntryWithDefId::new(
         EntryDefId::CapGrant,
         Entry::CapGrant(cap_grant_entry),
     ))
 }
 
+/// List the capability grants on the local source chain that are currently active.
+///
+/// Grants that have been deleted, or replaced by an update, are revoked and are not listed.
+/// Only the new grant from an [ `update_cap_grant` ] is listed.
+///
+/// This is useful to keep `init` and other grant administration idempotent, by checking for
+/// an existing grant before creating a duplicate:
+///
+/// ```ignore
+/// let grant = CapGrantEntry::unrestricted(vec![(zome_info()?.zome_name, "accept_foo_grant".into())]);
+/// if !get_cap_grants()?.contains(&grant) {
+///     create_cap_grant(grant)?;
+/// }
+/// ```
+///
+/// Grants are only listed once they have been committed, so grants created earlier in the same
+/// zome call are not included.
+///
+/// The implicit grant the authoring agent has to its own externs is never listed.
+pub fn get_cap_grants() -> ExternResult<Vec<CapGrantEntry>> {
+    HDK.with(|h| h.borrow().capability_grants(()))
+}
+
 /// Delete a capability grant.
 ///
 /// Wraps the [ `delete` ] HDK function with system type parameters set.
@@ -139,6 +194,25 @@ pub fn create_cap_grant(cap_grant_entry: CapGrantEntry) -> ExternResult<HeaderHa
 ///
 /// The input to [ `delete_cap_grant` ] is the [ `HeaderHash` ] of the [ `CapGrant` ] element to delete.
 /// Deletes can reference both [ `CapGrant` ] creates and updates.
+///
+/// To revoke a grant that was opened earlier, e.g. in `init`, find its element with [ `query` ]
+/// filtered to [ `EntryType::CapGrant` ] and check it is still listed by [ `get_cap_grants` ]:
+///
+/// ```ignore
+/// let grant = CapGrantEntry::unrestricted(vec![(zome_info()?.zome_name, "foo".into())]);
+/// if get_cap_grants()?.contains(&grant) {
+///     let filter = ChainQueryFilter::new()
+///         .entry_type(EntryType::CapGrant)
+///         .include_entries(true);
+///     if let Some(element) = query(filter)?
+///         .into_iter()
+///         .filter(|element| element.entry().to_grant_option().as_ref() == Some(&grant))
+///         .last()
+///     {
+///         delete_cap_grant(element.header_address().clone())?;
+///     }
+/// }
+/// ```
 pub fn delete_cap_grant(hash: HeaderHash) -> ExternResult<HeaderHash> {
     delete(hash)
 }
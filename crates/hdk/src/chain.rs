@@ -17,6 +17,34 @@ pub fn get_agent_activity(
     })
 }
 
+/// Page through the headers of the local source chain, oldest first.
+///
+/// Returns up to `limit` `(chain index, header hash)` pairs, starting at `start_index`,
+/// including anything committed earlier in the current zome call.
+/// An empty page means `start_index` is past the chain head.
+///
+/// Unlike [ `get_agent_activity` ] this only ever reads the calling cell's own chain,
+/// so it is cheap, consistent and doesn't touch the network.
+///
+/// ```ignore
+/// // everything this agent has done, 100 headers at a time
+/// let mut start_index = 0;
+/// loop {
+///     let page = chain_activity(start_index, 100)?;
+///     if page.is_empty() {
+///         break;
+///     }
+///     start_index += page.len() as u32;
+///     // ...
+/// }
+/// ```
+pub fn chain_activity(start_index: u32, limit: u32) -> ExternResult<Vec<(u32, HeaderHash)>> {
+    HDK.with(|h| {
+        h.borrow()
+            .chain_activity(ChainActivityInput::new(start_index, limit))
+    })
+}
+
 /// Walks the source chain in reverse (latest to oldest) filtering by header and/or entry type
 ///
 /// Given a header and entry type, returns an [ `Vec<Element>` ]
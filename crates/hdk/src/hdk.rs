@@ -23,6 +23,11 @@ pub trait HdkT: Send + Sync {
         get_agent_activity_input: GetAgentActivityInput,
     ) -> ExternResult<AgentActivity>;
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Element>>;
+    fn chain_activity(
+        &self,
+        chain_activity_input: ChainActivityInput,
+    ) -> ExternResult<Vec<(u32, HeaderHash)>>;
+    fn capability_grants(&self, capability_grants_input: ()) -> ExternResult<Vec<CapGrantEntry>>;
     // Ed25519
     fn sign(&self, sign: Sign) -> ExternResult<Signature>;
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
@@ -48,6 +53,10 @@ pub trait HdkT: Send + Sync {
     // P2P
     fn call(&self, call: Call) -> ExternResult<ZomeCallResponse>;
     fn call_remote(&self, call_remote: CallRemote) -> ExternResult<ZomeCallResponse>;
+    fn call_remote_multi(
+        &self,
+        call_remote_multi: CallRemoteMulti,
+    ) -> ExternResult<Vec<(AgentPubKey, ZomeCallResponse)>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
     // Random
@@ -94,6 +103,12 @@ impl HdkT for ErrHdk {
     fn query(&self, _: ChainQueryFilter) -> ExternResult<Vec<Element>> {
         Self::err()
     }
+    fn chain_activity(&self, _: ChainActivityInput) -> ExternResult<Vec<(u32, HeaderHash)>> {
+        Self::err()
+    }
+    fn capability_grants(&self, _: ()) -> ExternResult<Vec<CapGrantEntry>> {
+        Self::err()
+    }
     fn sign(&self, _: Sign) -> ExternResult<Signature> {
         Self::err()
     }
@@ -156,6 +171,12 @@ impl HdkT for ErrHdk {
     fn call_remote(&self, _: CallRemote) -> ExternResult<ZomeCallResponse> {
         Self::err()
     }
+    fn call_remote_multi(
+        &self,
+        _: CallRemoteMulti,
+    ) -> ExternResult<Vec<(AgentPubKey, ZomeCallResponse)>> {
+        Self::err()
+    }
     fn emit_signal(&self, _: AppSignal) -> ExternResult<()> {
         Self::err()
     }
@@ -227,6 +248,18 @@ impl HdkT for HostHdk {
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Element>> {
         host_call::<ChainQueryFilter, Vec<Element>>(__query, filter)
     }
+    fn chain_activity(
+        &self,
+        chain_activity_input: ChainActivityInput,
+    ) -> ExternResult<Vec<(u32, HeaderHash)>> {
+        host_call::<ChainActivityInput, Vec<(u32, HeaderHash)>>(
+            __chain_activity,
+            chain_activity_input,
+        )
+    }
+    fn capability_grants(&self, _: ()) -> ExternResult<Vec<CapGrantEntry>> {
+        host_call::<(), Vec<CapGrantEntry>>(__capability_grants, ())
+    }
 
     fn sign(&self, sign: Sign) -> ExternResult<Signature> {
         host_call::<Sign, Signature>(__sign, sign)
@@ -292,6 +325,15 @@ impl HdkT for HostHdk {
     fn call_remote(&self, call_remote: CallRemote) -> ExternResult<ZomeCallResponse> {
         host_call::<CallRemote, ZomeCallResponse>(__call_remote, call_remote)
     }
+    fn call_remote_multi(
+        &self,
+        call_remote_multi: CallRemoteMulti,
+    ) -> ExternResult<Vec<(AgentPubKey, ZomeCallResponse)>> {
+        host_call::<CallRemoteMulti, Vec<(AgentPubKey, ZomeCallResponse)>>(
+            __call_remote_multi,
+            call_remote_multi,
+        )
+    }
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()> {
         host_call::<AppSignal, ()>(__emit_signal, app_signal)
     }
@@ -365,4 +407,5 @@ where
     HDK.with(|h| {
         *h.borrow_mut() = Box::new(hdk);
     });
+    crate::info::clear_zome_info_cache();
 }
@@ -1,14 +1,16 @@
 use crate::prelude::*;
+use core::cell::RefCell;
 
 /// Trivial wrapper for `__agent_info` host function.
 /// Agent info input struct is `()` so the function call simply looks like this:
 ///
 /// ```ignore
 /// let agent_info = agent_info()?;
+/// let queried_at: Timestamp = agent_info.queried_at;
 /// ```
 ///
 /// the [ `AgentInfo` ] is the current agent's original pubkey/address that they joined the network with
-/// and their most recent pubkey/address.
+/// and their most recent pubkey/address, along with the time the host gathered this info.
 pub fn agent_info() -> ExternResult<AgentInfo> {
     HDK.with(|h| h.borrow().agent_info(()))
 }
@@ -23,6 +25,8 @@ pub fn dna_info() -> ExternResult<DnaInfo> {
     HDK.with(|h| h.borrow().dna_info(()))
 }
 
+thread_local!(static ZOME_INFO: RefCell<Option<ZomeInfo>> = RefCell::new(None));
+
 /// Get the zome information.
 /// There are no inputs to [ `zome_info` ].
 ///
@@ -30,8 +34,23 @@ pub fn dna_info() -> ExternResult<DnaInfo> {
 ///
 /// In general any holochain compatible wasm can be compiled and run in any zome so the zome info
 /// needs to be looked up at runtime to e.g. know where to send/receive `call_remote` rpc calls to.
+///
+/// The zome info can't change for the life of a wasm instance, so only the first call crosses the
+/// host boundary and later calls return a cached copy. Every zome call runs in a fresh instance,
+/// so e.g. an extern that looks up the zome name to build both a grant and a call makes one host
+/// call rather than two. The cache is also cleared by [ `set_hdk` ].
 pub fn zome_info() -> ExternResult<ZomeInfo> {
-    HDK.with(|h| h.borrow().zome_info(()))
+    if let Some(zome_info) = ZOME_INFO.with(|z| z.borrow().clone()) {
+        return Ok(zome_info);
+    }
+    let zome_info = HDK.with(|h| h.borrow().zome_info(()))?;
+    ZOME_INFO.with(|z| *z.borrow_mut() = Some(zome_info.clone()));
+    Ok(zome_info)
+}
+
+/// Forget the cached zome info, e.g. when a different HDK is set.
+pub(crate) fn clear_zome_info_cache() {
+    ZOME_INFO.with(|z| *z.borrow_mut() = None);
 }
 
 /// @todo Not implemented
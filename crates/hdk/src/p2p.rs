@@ -11,6 +11,12 @@ use crate::prelude::*;
 /// - fn_name: The name of the function in the zome you are calling.
 /// - cap_secret: The capability secret if required.
 /// - payload: The arguments to the function you are calling.
+///
+/// ## Response
+/// [ `ZomeCallResponse::Ok` ] holds the [ `ExternIO` ] returned by the function.
+/// [ `ZomeCallResponse::Unauthorized` ] if the cap secret does not grant access.
+/// [ `ZomeCallResponse::ZomeNotFound` ] if the cell has no zome with this name.
+/// [ `ZomeCallResponse::FunctionNotFound` ] if the zome has no function with this name.
 pub fn call<I>(
     to_cell: Option<CellId>,
     zome_name: ZomeName,
@@ -35,6 +41,54 @@ where
     })
 }
 
+/// Names the externs of the current zome as an enum, so that calls and cap grants can refer to
+/// them without stringly typed function names.
+///
+/// Each variant is the CamelCase of an extern defined in the same module, and converts into the
+/// [ `FunctionName` ] of that extern. Naming a function that doesn't exist is a compile error, so
+/// a typo can't turn into a [ `ZomeCallResponse::FunctionNotFound` ] at runtime.
+///
+/// ```ignore
+/// zome_fns! {
+///     enum FooFn { foo, foo_open }
+/// }
+///
+/// #[hdk_extern]
+/// fn call_foo(cell_id: CellId) -> ExternResult<ZomeCallResponse> {
+///     call(Some(cell_id), zome_info()?.zome_name, FooFn::Foo.into(), None, &())
+/// }
+/// ```
+#[macro_export]
+macro_rules! zome_fns {
+    ( $(#[$meta:meta])* $vis:vis enum $name:ident { $( $f:ident ),* $(,)? } ) => {
+        $crate::paste::paste! {
+            $(#[$meta])*
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            $vis enum $name {
+                $( [< $f:camel >], )*
+            }
+
+            impl $name {
+                /// The name the extern is called by.
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $( Self::[< $f:camel >] => stringify!($f), )*
+                    }
+                }
+            }
+
+            impl From<$name> for $crate::prelude::FunctionName {
+                fn from(f: $name) -> Self {
+                    f.as_str().into()
+                }
+            }
+
+            // Fails to compile unless every extern is defined.
+            const _: () = { $( let _ = $f; )* };
+        }
+    };
+}
+
 /// Wrapper for __call_remote host function.
 ///
 /// There are several positional arguments:
@@ -44,10 +98,15 @@ where
 /// - fn_name: The name of the function in the zome to call.
 /// - cap_secret: Optional cap claim secret to allow access to the remote call.
 /// - payload: The payload to send to the remote function; receiver needs to deserialize cleanly.
+/// - timeout_ms: Optional time to wait for the remote agent to respond, in milliseconds.
+///   If None the network default is used.
 ///
 /// Response is [ `ExternResult` ] which returns [ `ZomeCallResponse` ] of the function call.
 /// [ `ZomeCallResponse::NetworkError` ] if there was a network error.
+/// [ `ZomeCallResponse::NetworkTimeout` ] if the remote agent did not respond in time.
 /// [ `ZomeCallResponse::Unauthorized` ] if the provided cap grant is invalid.
+/// [ `ZomeCallResponse::ZomeNotFound` ] if the remote cell has no zome with this name.
+/// [ `ZomeCallResponse::FunctionNotFound` ] if the remote zome has no function with this name.
 /// The unauthorized case should always be handled gracefully because gap grants can be revoked at
 /// any time and the claim holder has no way of knowing until they provide a secret for a call.
 ///
@@ -55,7 +114,7 @@ where
 ///
 /// ```ignore
 /// ...
-/// let foo: Foo = call_remote(bob, "foo_zome", "do_it", secret, serializable_payload)?;
+/// let foo: Foo = call_remote(bob, "foo_zome", "do_it", secret, serializable_payload, Some(30_000))?;
 /// ...
 /// ```
 pub fn call_remote<I>(
@@ -64,6 +123,7 @@ pub fn call_remote<I>(
     fn_name: FunctionName,
     cap_secret: Option<CapSecret>,
     payload: I,
+    timeout_ms: Option<u64>,
 ) -> ExternResult<ZomeCallResponse>
 where
     I: serde::Serialize + std::fmt::Debug,
@@ -75,6 +135,234 @@ where
             fn_name,
             cap_secret,
             ExternIO::encode(payload)?,
+            timeout_ms,
+        ))
+    })
+}
+
+/// Typed version of [ `call` ].
+///
+/// Encodes the `I` payload and decodes the function's output as `O`, so the types at both ends of
+/// the call are explicit. Any response other than [ `ZomeCallResponse::Ok` ], and any output that
+/// doesn't decode as `O`, is returned as a [ `WasmError::Guest` ] naming the zome function called.
+///
+/// Use [ `call` ] instead if the caller needs to branch on why a call failed.
+///
+/// ```ignore
+/// ...
+/// let agent_info = call_typed::<_, AgentInfo>(Some(cell_id), zome_name, "whoami".into(), None, &())?;
+/// ...
+/// ```
+pub fn call_typed<I, O>(
+    to_cell: Option<CellId>,
+    zome_name: ZomeName,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+) -> ExternResult<O>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let response = call(
+        to_cell,
+        zome_name.clone(),
+        fn_name.clone(),
+        cap_secret,
+        payload,
+    )?;
+    decode_zome_call_response(&zome_name, &fn_name, response)
+}
+
+/// Typed version of [ `call` ] that reports exactly why a call failed.
+///
+/// Returns `Ok(Err(failure))` if the call didn't produce output, e.g. to tell
+/// [ `ZomeCallFailure::Unauthorized` ], where the target cell hasn't granted access to the
+/// function, apart from [ `ZomeCallFailure::FunctionNotFound` ]. Output that doesn't decode as
+/// `O` is a [ `WasmError::Guest` ] as in [ `call_typed` ].
+///
+/// ```ignore
+/// ...
+/// match try_call::<_, AgentInfo>(Some(cell_id), zome_name, "whoami".into(), None, &())? {
+///     Ok(agent_info) => ...,
+///     Err(ZomeCallFailure::Unauthorized(..)) => ...,
+///     Err(failure) => ...,
+/// }
+/// ...
+/// ```
+pub fn try_call<I, O>(
+    to_cell: Option<CellId>,
+    zome_name: ZomeName,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+) -> ExternResult<Result<O, ZomeCallFailure>>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    match call(
+        to_cell,
+        zome_name.clone(),
+        fn_name.clone(),
+        cap_secret,
+        payload,
+    )?
+    .into_result()
+    {
+        Ok(extern_io) => {
+            decode_zome_call_response(&zome_name, &fn_name, ZomeCallResponse::Ok(extern_io)).map(Ok)
+        }
+        Err(failure) => Ok(Err(failure)),
+    }
+}
+
+/// Typed version of [ `call_remote` ].
+///
+/// Errors are handled the same way as [ `call_typed` ], including network errors and timeouts.
+pub fn call_remote_typed<I, O>(
+    agent: AgentPubKey,
+    zome: ZomeName,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+    timeout_ms: Option<u64>,
+) -> ExternResult<O>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let response = call_remote(
+        agent,
+        zome.clone(),
+        fn_name.clone(),
+        cap_secret,
+        payload,
+        timeout_ms,
+    )?;
+    decode_zome_call_response(&zome, &fn_name, response)
+}
+
+/// Typed call to a capability restricted function on a remote agent.
+///
+/// The remote agent must have granted the caller an assigned or transferable capability for the
+/// function with this secret, e.g. by committing a [ `CapGrantEntry::assigned` ] and sharing the
+/// secret with the caller.
+///
+/// If the secret doesn't match a live grant, the error is a [ `WasmError::Guest` ] starting with
+/// "capability denied", so callers can tell it apart from network errors and bad outputs.
+/// Otherwise errors are handled the same way as [ `call_remote_typed` ].
+///
+/// ```ignore
+/// ...
+/// let agent_info = call_remote_with_secret::<_, AgentInfo>(bob, zome_name, "whoami".into(), secret, &(), None)?;
+/// ...
+/// ```
+pub fn call_remote_with_secret<I, O>(
+    agent: AgentPubKey,
+    zome: ZomeName,
+    fn_name: FunctionName,
+    cap_secret: CapSecret,
+    payload: I,
+    timeout_ms: Option<u64>,
+) -> ExternResult<O>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    match call_remote(
+        agent.clone(),
+        zome.clone(),
+        fn_name.clone(),
+        Some(cap_secret),
+        payload,
+        timeout_ms,
+    )? {
+        ZomeCallResponse::Unauthorized(..) => Err(WasmError::Guest(format!(
+            "capability denied: {} has no grant for {}::{} matching the secret",
+            agent, zome, fn_name
+        ))),
+        response => decode_zome_call_response(&zome, &fn_name, response),
+    }
+}
+
+fn decode_zome_call_response<O>(
+    zome_name: &ZomeName,
+    fn_name: &FunctionName,
+    response: ZomeCallResponse,
+) -> ExternResult<O>
+where
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    match response {
+        ZomeCallResponse::Ok(extern_io) => extern_io.decode().map_err(|e| {
+            WasmError::Guest(format!(
+                "output of {}::{} is not a {}: {}",
+                zome_name,
+                fn_name,
+                std::any::type_name::<O>(),
+                e
+            ))
+        }),
+        other => Err(WasmError::Guest(format!(
+            "call to {}::{} failed: {:?}",
+            zome_name, fn_name, other
+        ))),
+    }
+}
+
+/// Wrapper for __call_remote_multi host function.
+///
+/// Calls the same function on several agents near a basis hash, e.g. to ask
+/// what the agents responsible for some data think about it.
+///
+/// There are several positional arguments:
+///
+/// - basis: The hash whose neighborhood should be called.
+/// - zome: The zome to call the remote function in. Use zome_info() to get the current zome info.
+/// - fn_name: The name of the function in the zome to call.
+/// - cap_secret: Optional cap claim secret to allow access to the remote call.
+/// - payload: The payload to send to the remote function; receivers need to deserialize cleanly.
+/// - remote_agent_count: How many agents to call. If None the network default is used.
+/// - timeout_ms: Optional time to wait for the agents to respond, in milliseconds.
+///   If None the network default is used.
+///
+/// Response is [ `ExternResult` ] which returns the [ `ZomeCallResponse` ] of each agent that
+/// responded, paired with that agent's key. Partial failures are represented as follows:
+///
+/// - Agents that error at the network level or don't respond before the timeout are left out,
+///   so the result can be shorter than `remote_agent_count`, or even empty.
+/// - Agents that do respond can still refuse the call, e.g. with
+///   [ `ZomeCallResponse::Unauthorized` ], and each response must be checked on its own.
+/// - A response that can't be decoded is a [ `ZomeCallResponse::NetworkError` ] for that agent.
+///
+/// ```ignore
+/// ...
+/// let responses = call_remote_multi(basis, "foo_zome", "do_it", None, payload, Some(5), None)?;
+/// for (agent, response) in responses { ... }
+/// ...
+/// ```
+pub fn call_remote_multi<I>(
+    basis: AnyDhtHash,
+    zome: ZomeName,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+    remote_agent_count: Option<u8>,
+    timeout_ms: Option<u64>,
+) -> ExternResult<Vec<(AgentPubKey, ZomeCallResponse)>>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    HDK.with(|h| {
+        h.borrow().call_remote_multi(CallRemoteMulti::new(
+            basis,
+            zome,
+            fn_name,
+            cap_secret,
+            ExternIO::encode(payload)?,
+            remote_agent_count,
+            timeout_ms,
         ))
     })
 }
@@ -110,6 +398,14 @@ where
 /// A log will be produced at `[remote_signal]=info` if the calls
 /// fail though (this may be removed in the future).
 ///
+/// ### Delivery
+/// Unlike [ `call_remote` ] no response is awaited from the agents.
+/// Delivery is best-effort and at-most-once: a signal is never
+/// retried, so an agent that is offline or unreachable misses it,
+/// but no agent receives the same signal twice.
+/// Each agent is signalled independently, so failing to reach
+/// one agent doesn't affect delivery to the others.
+///
 /// ### Usage
 /// Currently this requires the function `recv_remote_signal` be
 /// exposed by this zome with a signature like:
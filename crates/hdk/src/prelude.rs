@@ -3,7 +3,9 @@ pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
 pub use crate::capability::generate_cap_secret;
+pub use crate::capability::get_cap_grants;
 pub use crate::capability::update_cap_grant;
+pub use crate::chain::chain_activity;
 pub use crate::chain::get_agent_activity;
 pub use crate::chain::query;
 pub use crate::ed25519::sign;
@@ -44,8 +46,13 @@ pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
 pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
+pub use crate::p2p::call_remote_multi;
+pub use crate::p2p::call_remote_typed;
+pub use crate::p2p::call_remote_with_secret;
+pub use crate::p2p::call_typed;
 pub use crate::p2p::emit_signal;
 pub use crate::p2p::remote_signal;
+pub use crate::p2p::try_call;
 pub use crate::random::*;
 pub use crate::register_entry;
 pub use crate::time::schedule;
@@ -57,6 +64,7 @@ pub use crate::x_salsa20_poly1305::x_25519_x_salsa20_poly1305_decrypt;
 pub use crate::x_salsa20_poly1305::x_25519_x_salsa20_poly1305_encrypt;
 pub use crate::x_salsa20_poly1305::x_salsa20_poly1305_decrypt;
 pub use crate::x_salsa20_poly1305::x_salsa20_poly1305_encrypt;
+pub use crate::zome_fns;
 pub use hdk_derive::hdk_entry;
 pub use hdk_derive::hdk_extern;
 pub use holo_hash;
@@ -108,7 +116,9 @@ macro_rules! holochain_externs {
             __get_link_details,
             __get_agent_activity,
             __query,
+            __chain_activity,
             __call_remote,
+            __call_remote_multi,
             __call,
             __create,
             __emit_signal,
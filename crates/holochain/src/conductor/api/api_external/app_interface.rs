@@ -90,10 +90,25 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                             call.cap, call.fn_name, call.zome_name
                         )),
                     )),
+                    Ok(ZomeCallResponse::ZomeNotFound(_, zome_name)) => Ok(AppResponse::Error(
+                        ExternalApiWireError::RibosomeError(format!(
+                            "Referenced a zome that doesn't exist: Zome: {}",
+                            zome_name
+                        )),
+                    )),
+                    Ok(ZomeCallResponse::FunctionNotFound(_, zome_name, fn_name)) => {
+                        Ok(AppResponse::Error(ExternalApiWireError::RibosomeError(format!(
+                            "Attempted to call a zome function that doesn't exist: Zome: {} Fn {}",
+                            zome_name, fn_name
+                        ))))
+                    }
                     Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
                         "Interface zome calls should never be routed to the network. This is a bug. Got {}",
                         e
                     ),
+                    Ok(ZomeCallResponse::NetworkTimeout) => unreachable!(
+                        "Interface zome calls should never be routed to the network. This is a bug."
+                    ),
                     Err(e) => Ok(AppResponse::Error(e.into())),
                 }
             }
@@ -15,6 +15,7 @@ use crate::conductor::handle::ConductorHandle;
 use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueTriggers;
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
@@ -677,10 +678,13 @@ impl Cell {
             provenance: from_agent,
             fn_name,
         };
-        // double ? because
-        // - ConductorApiResult
-        // - ZomeCallResult
-        Ok(self.call_zome(invocation, None).await??.try_into()?)
+        // zome and fn lookup failures are sent back to the caller as a
+        // response so they can be told apart from other failures
+        let response = match self.call_zome(invocation, None).await? {
+            Ok(response) => response,
+            Err(e) => e.into_zome_call_response(self.id.clone())?,
+        };
+        Ok(response.try_into()?)
     }
 
     /// Function called by the Conductor
@@ -706,7 +710,15 @@ impl Cell {
         let conductor_api = self.conductor_api.clone();
         let signal_tx = self.signal_broadcaster().await;
         let ribosome = self.get_ribosome().await?;
-        let invocation = ZomeCallInvocation::from_interface_call(conductor_api.clone(), call).await;
+        let zome_name = call.zome_name.clone();
+        let invocation =
+            match ZomeCallInvocation::from_interface_call(conductor_api.clone(), call).await {
+                Ok(invocation) => invocation,
+                Err(ConductorApiError::DnaError(DnaError::ZomeNotFound(_))) => {
+                    return Ok(Err(RibosomeError::ZomeNotExists(zome_name)))
+                }
+                Err(e) => return Err(Box::new(e).into()),
+            };
 
         let args = CallZomeWorkflowArgs {
             ribosome,
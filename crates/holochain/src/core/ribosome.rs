@@ -12,6 +12,7 @@ pub mod guest_callback;
 pub mod host_fn;
 pub mod real_ribosome;
 
+use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::api::ZomeCall;
@@ -323,7 +324,10 @@ impl Invocation for ZomeCallInvocation {
 }
 
 impl ZomeCallInvocation {
-    pub async fn from_interface_call(conductor_api: CellConductorApi, call: ZomeCall) -> Self {
+    pub async fn from_interface_call(
+        conductor_api: CellConductorApi,
+        call: ZomeCall,
+    ) -> ConductorApiResult<Self> {
         use crate::conductor::api::CellConductorApiT;
         let ZomeCall {
             cell_id,
@@ -335,16 +339,15 @@ impl ZomeCallInvocation {
         } = call;
         let zome = conductor_api
             .get_zome(cell_id.dna_hash(), &zome_name)
-            .await
-            .expect("TODO");
-        Self {
+            .await?;
+        Ok(Self {
             cell_id,
             zome,
             fn_name,
             cap,
             payload,
             provenance,
-        }
+        })
     }
 }
 
@@ -591,7 +594,12 @@ pub mod wasm_test {
                     crate::core::ribosome::ZomeCallResponse::Unauthorized(_, _, _, _) => {
                         unreachable!()
                     }
+                    crate::core::ribosome::ZomeCallResponse::ZomeNotFound(_, _) => unreachable!(),
+                    crate::core::ribosome::ZomeCallResponse::FunctionNotFound(_, _, _) => {
+                        unreachable!()
+                    }
                     crate::core::ribosome::ZomeCallResponse::NetworkError(_) => unreachable!(),
+                    crate::core::ribosome::ZomeCallResponse::NetworkTimeout => unreachable!(),
                 };
                 output
             })
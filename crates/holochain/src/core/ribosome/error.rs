@@ -100,6 +100,23 @@ pub enum RibosomeError {
     SecurePrimitive(#[from] holochain_zome_types::SecurePrimitiveError),
 }
 
+impl RibosomeError {
+    /// Zome and function lookup failures are reported back to the caller of
+    /// a zome call as a [ZomeCallResponse] so that guest code can branch on
+    /// them. Any other error is passed through unchanged.
+    pub fn into_zome_call_response(self, cell_id: CellId) -> RibosomeResult<ZomeCallResponse> {
+        match self {
+            Self::ZomeNotExists(zome_name) => {
+                Ok(ZomeCallResponse::ZomeNotFound(cell_id, zome_name))
+            }
+            Self::ZomeFnNotExists(zome_name, fn_name) => Ok(ZomeCallResponse::FunctionNotFound(
+                cell_id, zome_name, fn_name,
+            )),
+            e => Err(e),
+        }
+    }
+}
+
 impl From<xsalsa20poly1305::aead::Error> for RibosomeError {
     fn from(error: xsalsa20poly1305::aead::Error) -> Self {
         Self::Aead(error.to_string())
@@ -64,11 +64,14 @@ host_fn_api_impls! {
     // Header hash of the DeleteLink element.
     fn call_remote (zt::call_remote::CallRemote) -> zt::ZomeCallResponse;
 
+    // Call the same zome fn on several agents near a basis.
+    fn call_remote_multi (zt::call_remote::CallRemoteMulti) -> Vec<(holo_hash::AgentPubKey, zt::ZomeCallResponse)>;
+
     // @todo List all the local capability claims.
     fn capability_claims (()) -> ();
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List all the local capability grants that have not been revoked.
+    fn capability_grants (()) -> Vec<zt::entry::CapGrantEntry>;
 
     // @todo Get the capability for the current zome call.
     fn capability_info (()) -> ();
@@ -117,6 +120,9 @@ host_fn_api_impls! {
 
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // Page through the local source chain, as (chain index, header hash) pairs.
+    fn chain_activity (zt::agent_activity::ChainActivityInput) -> Vec<(u32, holo_hash::HeaderHash)>;
+
     fn get_details (zt::entry::GetInput) -> Option<zt::metadata::Details>;
 
     // Get links by entry hash from the cascade.
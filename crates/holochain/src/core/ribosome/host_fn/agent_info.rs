@@ -18,6 +18,7 @@ pub fn agent_info<'a>(
     Ok(AgentInfo {
         agent_initial_pubkey: agent_pubkey.clone(),
         agent_latest_pubkey: agent_pubkey,
+        queried_at: Timestamp::now(),
     })
 }
 
@@ -47,9 +48,13 @@ pub mod test {
         let mut host_access = fixt!(ZomeCallHostAccess);
         host_access.workspace = workspace_lock;
 
+        let before = Timestamp::now();
         let agent_info: AgentInfo =
             crate::call_test_ribosome!(host_access, TestWasm::AgentInfo, "agent_info", ());
         assert_eq!(agent_info.agent_initial_pubkey, fake_agent_pubkey_1(),);
         assert_eq!(agent_info.agent_latest_pubkey, fake_agent_pubkey_1(),);
+        // the host stamps the info as it is gathered
+        assert!(before <= agent_info.queried_at);
+        assert!(agent_info.queried_at <= Timestamp::now());
     }
 }
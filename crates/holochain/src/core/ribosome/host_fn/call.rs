@@ -24,7 +24,7 @@ pub fn call(
 
     // Create the invocation for this call
     let invocation = ZomeCall {
-        cell_id,
+        cell_id: cell_id.clone(),
         zome_name,
         cap: call.cap,
         fn_name: call.fn_name,
@@ -33,14 +33,22 @@ pub fn call(
     };
 
     // Make the call using this workspace
-    Ok(tokio_helper::block_forever_on(async move {
+    let result = tokio_helper::block_forever_on(async move {
         conductor_handle
             .call_zome(invocation, workspace)
             .await
             .map_err(Box::new)
     })
-    .map_err(|conductor_api_error| WasmError::Host(conductor_api_error.to_string()))?
-    .map_err(|ribosome_error| WasmError::Host(ribosome_error.to_string()))?)
+    .map_err(|conductor_api_error| WasmError::Host(conductor_api_error.to_string()))?;
+
+    // Missing zomes and fns are returned to the guest as a response
+    // so it can decide what to do, e.g. try a different cell.
+    match result {
+        Ok(response) => Ok(response),
+        Err(ribosome_error) => ribosome_error
+            .into_zome_call_response(cell_id)
+            .map_err(|ribosome_error| WasmError::Host(ribosome_error.to_string())),
+    }
 }
 
 #[cfg(test)]
@@ -56,11 +64,16 @@ pub mod wasm_test {
     use holochain_types::dna::DnaFile;
     use holochain_wasm_test_utils::TestWasm;
     use holochain_zome_types::test_utils::fake_agent_pubkey_2;
+    use holochain_zome_types::timestamp::Timestamp;
     use holochain_zome_types::ExternIO;
+    use holochain_zome_types::FunctionName;
+    use holochain_zome_types::ZomeCallFailure;
     use holochain_zome_types::ZomeCallResponse;
+    use holochain_zome_types::ZomeName;
     use matches::assert_matches;
 
     use crate::conductor::{api::ZomeCall, ConductorHandle};
+    use crate::core::ribosome::error::RibosomeError;
     use crate::test_utils::conductor_setup::ConductorTestData;
     use crate::test_utils::install_app;
     use crate::test_utils::new_zome_call;
@@ -95,6 +108,8 @@ pub mod wasm_test {
 
         // ALICE DOING A CALL
 
+        let before = Timestamp::now();
+
         let output = handle
             .call_zome(ZomeCall {
                 cell_id: alice_cell_id.clone(),
@@ -110,20 +125,75 @@ pub mod wasm_test {
 
         match output {
             ZomeCallResponse::Ok(guest_output) => {
-                let agent_info: AgentInfo = guest_output.decode().unwrap();
+                let agent_info: Result<AgentInfo, ZomeCallFailure> = guest_output.decode().unwrap();
+                let agent_info = agent_info.unwrap();
                 assert_eq!(
                     agent_info,
                     AgentInfo {
                         agent_initial_pubkey: bob_agent_id.clone(),
                         agent_latest_pubkey: bob_agent_id.clone(),
+                        queried_at: agent_info.queried_at,
                     },
                 );
+                // bob's cell gathered the info during the call
+                assert!(before <= agent_info.queried_at);
+                assert!(agent_info.queried_at <= Timestamp::now());
             }
             _ => unreachable!(),
         }
         conductor_test.shutdown_conductor().await;
     }
 
+    /// Without set_access bob's whoami is restricted, so alice's call reports
+    /// exactly that, while the whoami_open granted in init can be called.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_restricted_and_open() {
+        observability::test_run().ok();
+
+        let zomes = vec![TestWasm::WhoAmI];
+        let mut conductor_test = ConductorTestData::two_agents(zomes, true).await;
+        let handle = conductor_test.handle();
+        let bob_cell_id = conductor_test.bob_call_data().unwrap().cell_id.clone();
+        let alice_cell_id = conductor_test.alice_call_data().cell_id.clone();
+        let alice_agent_id = alice_cell_id.agent_pubkey().clone();
+        let bob_agent_id = bob_cell_id.agent_pubkey().clone();
+
+        let call_bob = |fn_name: &str| {
+            new_zome_call(
+                &alice_cell_id,
+                fn_name,
+                bob_cell_id.clone(),
+                TestWasm::WhoAmI,
+            )
+            .unwrap()
+        };
+
+        let result = handle.call_zome(call_bob("who_are_they_local")).await;
+        let output: Result<AgentInfo, ZomeCallFailure> =
+            unwrap_to::unwrap_to!(result.unwrap().unwrap() => ZomeCallResponse::Ok)
+                .decode()
+                .unwrap();
+        assert_eq!(
+            output,
+            Err(ZomeCallFailure::Unauthorized(
+                bob_cell_id.clone(),
+                TestWasm::WhoAmI.into(),
+                "whoami".into(),
+                alice_agent_id,
+            ))
+        );
+
+        let result = handle.call_zome(call_bob("who_are_they_local_open")).await;
+        let output: Result<AgentInfo, ZomeCallFailure> =
+            unwrap_to::unwrap_to!(result.unwrap().unwrap() => ZomeCallResponse::Ok)
+                .decode()
+                .unwrap();
+        let agent_info = output.unwrap();
+        assert_eq!(agent_info.agent_latest_pubkey, bob_agent_id);
+
+        conductor_test.shutdown_conductor().await;
+    }
+
     /// When calling the same cell we need to make sure
     /// the "as at" doesn't cause the original zome call to fail
     /// when they are both writing (moving the source chain forward)
@@ -199,6 +269,60 @@ pub mod wasm_test {
         conductor_test.shutdown_conductor().await;
     }
 
+    /// Calling a zome or fn that doesn't exist gives the guest
+    /// a response it can branch on rather than an opaque error.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_missing_zome_or_fn() {
+        observability::test_run().ok();
+
+        let zomes = vec![TestWasm::WhoAmI];
+        let mut conductor_test = ConductorTestData::two_agents(zomes, false).await;
+        let handle = conductor_test.handle();
+        let alice_cell_id = conductor_test.alice_call_data().cell_id.clone();
+
+        let call_zome_fn = |zome_name: &str, fn_name: &str| {
+            let payload = (
+                alice_cell_id.clone(),
+                ZomeName::from(zome_name),
+                FunctionName::from(fn_name),
+            );
+            new_zome_call(&alice_cell_id, "call_zome_fn", payload, TestWasm::WhoAmI).unwrap()
+        };
+
+        let result = handle
+            .call_zome(call_zome_fn("create_entry", "create_entry"))
+            .await;
+        let response: ZomeCallResponse =
+            unwrap_to::unwrap_to!(result.unwrap().unwrap() => ZomeCallResponse::Ok)
+                .decode()
+                .unwrap();
+        assert_eq!(
+            response,
+            ZomeCallResponse::ZomeNotFound(alice_cell_id.clone(), "create_entry".into())
+        );
+
+        let result = handle.call_zome(call_zome_fn("whoami", "not_a_fn")).await;
+        let response: ZomeCallResponse =
+            unwrap_to::unwrap_to!(result.unwrap().unwrap() => ZomeCallResponse::Ok)
+                .decode()
+                .unwrap();
+        assert_eq!(
+            response,
+            ZomeCallResponse::FunctionNotFound(
+                alice_cell_id.clone(),
+                "whoami".into(),
+                "not_a_fn".into()
+            )
+        );
+
+        // The same zome lookup failure from the interface is an error, not a panic.
+        let invocation = new_zome_call(&alice_cell_id, "create_entry", (), "create_entry").unwrap();
+        let result = handle.call_zome(invocation).await;
+        assert_matches!(result, Ok(Err(RibosomeError::ZomeNotExists(_))));
+
+        conductor_test.shutdown_conductor().await;
+    }
+
     async fn install_new_app(
         dna_name: &str,
         zomes: Vec<TestWasm>,
@@ -20,11 +20,13 @@ pub fn call_remote(
                 input.fn_name_as_ref().to_owned(),
                 input.cap_as_ref().to_owned(),
                 input.payload_as_ref().to_owned(),
+                input.timeout_ms(),
             )
             .await
     });
     let result = match result {
         Ok(r) => ZomeCallResponse::try_from(r)?,
+        Err(e) if e.is_timeout() => ZomeCallResponse::NetworkTimeout,
         Err(e) => ZomeCallResponse::NetworkError(e.to_string()),
     };
 
@@ -36,6 +38,7 @@ pub fn call_remote(
 pub mod wasm_test {
     use crate::conductor::api::ZomeCall;
     use crate::conductor::interface::websocket::test_utils::setup_app;
+    use crate::core::ribosome::error::RibosomeError;
     use crate::core::ribosome::ZomeCallResponse;
     use hdk::prelude::*;
     use holochain_types::prelude::*;
@@ -133,6 +136,8 @@ pub mod wasm_test {
 
         // ALICE DOING A CALL
 
+        let before = Timestamp::now();
+
         let output = handle
             .call_zome(ZomeCall {
                 cell_id: alice_cell_id,
@@ -154,8 +159,108 @@ pub mod wasm_test {
                     AgentInfo {
                         agent_initial_pubkey: bob_agent_id.clone(),
                         agent_latest_pubkey: bob_agent_id,
+                        queried_at: agent_info.queried_at,
                     },
                 );
+                // bob gathered the info while handling the remote call
+                assert!(before <= agent_info.queried_at);
+                assert!(agent_info.queried_at <= Timestamp::now());
+            }
+            _ => unreachable!(),
+        }
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// we can call a capability restricted fn on a remote with a secret
+    async fn call_remote_with_secret_test() {
+        let dna_def = DnaDef {
+            name: "call_remote_with_secret_test".to_string(),
+            uid: "4a2f7c0e-8d35-4b8e-9a51-2c6f0e9d3b17".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            zomes: vec![TestWasm::WhoAmI.into()].into(),
+        };
+        let dna_file = DnaFile::new(dna_def, vec![TestWasm::WhoAmI.into()])
+            .await
+            .unwrap();
+
+        let alice_agent_id = fake_agent_pubkey_1();
+        let alice_cell_id = CellId::new(dna_file.dna_hash().to_owned(), alice_agent_id.clone());
+        let alice_installed_cell = InstalledCell::new(alice_cell_id.clone(), "alice_handle".into());
+
+        let bob_agent_id = fake_agent_pubkey_2();
+        let bob_cell_id = CellId::new(dna_file.dna_hash().to_owned(), bob_agent_id.clone());
+        let bob_installed_cell = InstalledCell::new(bob_cell_id.clone(), "bob_handle".into());
+
+        let mut dna_store = MockDnaStore::new();
+
+        dna_store.expect_get().return_const(Some(dna_file.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(2)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(2)
+            .return_const(());
+
+        let (_tmpdir, _app_api, handle) = setup_app(
+            vec![(alice_installed_cell, None), (bob_installed_cell, None)],
+            dna_store,
+        )
+        .await;
+
+        // BOB GRANTS ALICE ACCESS TO WHOAMI WITH A SECRET
+
+        let secret: CapSecret = match handle
+            .call_zome(ZomeCall {
+                cell_id: bob_cell_id,
+                zome_name: TestWasm::WhoAmI.into(),
+                cap: None,
+                fn_name: "set_access_for".into(),
+                payload: ExternIO::encode(&alice_agent_id).unwrap(),
+                provenance: bob_agent_id.clone(),
+            })
+            .await
+            .unwrap()
+            .unwrap()
+        {
+            ZomeCallResponse::Ok(guest_output) => guest_output.decode().unwrap(),
+            _ => unreachable!(),
+        };
+
+        let call_with_secret = |secret: CapSecret| {
+            handle.call_zome(ZomeCall {
+                cell_id: alice_cell_id.clone(),
+                zome_name: TestWasm::WhoAmI.into(),
+                cap: None,
+                fn_name: "whoarethey_with_secret".into(),
+                payload: ExternIO::encode((bob_agent_id.clone(), secret)).unwrap(),
+                provenance: alice_agent_id.clone(),
+            })
+        };
+
+        // ALICE CALLS WITH THE SECRET
+
+        match call_with_secret(secret).await.unwrap().unwrap() {
+            ZomeCallResponse::Ok(guest_output) => {
+                let agent_info: AgentInfo = guest_output.decode().unwrap();
+                assert_eq!(agent_info.agent_latest_pubkey, bob_agent_id);
+            }
+            _ => unreachable!(),
+        }
+
+        // ALICE CALLS WITH A SECRET BOB NEVER GRANTED
+
+        match call_with_secret([0; CAP_SECRET_BYTES].into())
+            .await
+            .unwrap()
+        {
+            Err(RibosomeError::WasmError(WasmError::Guest(e))) => {
+                assert!(e.starts_with("capability denied"), "{}", e)
             }
             _ => unreachable!(),
         }
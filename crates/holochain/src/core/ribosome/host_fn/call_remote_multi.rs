@@ -0,0 +1,110 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_p2p::HolochainP2pCellT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+/// Agents that fail or don't respond within the timeout are left out of the
+/// result, so it can be shorter than the requested `remote_agent_count`.
+/// A response that can't be decoded is a `NetworkError` for that agent only.
+pub fn call_remote_multi(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: CallRemoteMulti,
+) -> Result<Vec<(AgentPubKey, ZomeCallResponse)>, WasmError> {
+    let results = tokio_helper::block_forever_on(async move {
+        let mut network = call_context.host_access().network().clone();
+        network
+            .call_remote_multi(
+                input.basis_as_ref().to_owned(),
+                input.zome_name_as_ref().to_owned(),
+                input.fn_name_as_ref().to_owned(),
+                input.cap_as_ref().to_owned(),
+                input.payload_as_ref().to_owned(),
+                input.remote_agent_count(),
+                input.timeout_ms(),
+            )
+            .await
+    })
+    .map_err(|e| WasmError::Host(e.to_string()))?;
+
+    Ok(results
+        .into_iter()
+        .map(|(agent, response)| {
+            let response = ZomeCallResponse::try_from(response)
+                .unwrap_or_else(|e| ZomeCallResponse::NetworkError(e.to_string()));
+            (agent, response)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::conductor::api::ZomeCall;
+    use crate::core::ribosome::ZomeCallResponse;
+    use crate::test_utils::conductor_setup::ConductorTestData;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::ExternIO;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_remote_multi_test() {
+        observability::test_run().ok();
+
+        let zomes = vec![TestWasm::WhoAmI];
+        let mut conductor_test = ConductorTestData::two_agents(zomes, true).await;
+        let handle = conductor_test.handle();
+        let alice_cell_id = conductor_test.alice_call_data().cell_id.clone();
+        let bob_cell_id = conductor_test.bob_call_data().unwrap().cell_id.clone();
+        let alice_agent_id = alice_cell_id.agent_pubkey().clone();
+        let bob_agent_id = bob_cell_id.agent_pubkey().clone();
+
+        // both agents let anyone call whoami
+        for cell_id in &[&alice_cell_id, &bob_cell_id] {
+            let _ = handle
+                .call_zome(ZomeCall {
+                    cell_id: (*cell_id).clone(),
+                    zome_name: TestWasm::WhoAmI.into(),
+                    cap: None,
+                    fn_name: "set_access".into(),
+                    payload: ExternIO::encode(()).unwrap(),
+                    provenance: cell_id.agent_pubkey().clone(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let basis: AnyDhtHash = EntryHash::from_raw_32(vec![0; 32]).into();
+        let output = handle
+            .call_zome(ZomeCall {
+                cell_id: alice_cell_id.clone(),
+                zome_name: TestWasm::WhoAmI.into(),
+                cap: None,
+                fn_name: "who_are_they_near".into(),
+                payload: ExternIO::encode(basis).unwrap(),
+                provenance: alice_agent_id.clone(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut agents: Vec<AgentPubKey> = match output {
+            ZomeCallResponse::Ok(guest_output) => {
+                let infos: Vec<AgentInfo> = guest_output.decode().unwrap();
+                infos
+                    .into_iter()
+                    .map(|info| info.agent_latest_pubkey)
+                    .collect()
+            }
+            _ => unreachable!(),
+        };
+        agents.sort();
+        let mut expected = vec![alice_agent_id, bob_agent_id];
+        expected.sort();
+        assert_eq!(agents, expected);
+
+        conductor_test.shutdown_conductor().await;
+    }
+}
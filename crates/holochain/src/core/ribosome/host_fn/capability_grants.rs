@@ -1,26 +1,40 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
 use std::sync::Arc;
 
-/// list all the grants stored locally in the chain filtered by tag
-/// this is only the current grants as per local CRUD
+/// list all the grants stored locally in the chain
+/// this is only the current grants as per local CRUD, deleted or updated grants are not listed
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+) -> Result<Vec<CapGrantEntry>, WasmError> {
+    tokio_helper::block_forever_on(async move {
+        let grants: Vec<CapGrantEntry> = call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .live_cap_grants()
+            .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))?;
+        Ok(grants)
+    })
 }
 
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod wasm_test {
+    use crate::conductor::api::error::ConductorApiResult;
+    use crate::conductor::api::ZomeCall;
     use crate::fixt::ZomeCallHostAccessFixturator;
     use crate::{conductor::ConductorBuilder, sweettest::SweetConductor};
     use crate::{core::workflow::call_zome_workflow::CallZomeWorkspace, sweettest::SweetDnaFile};
     use ::fixt::prelude::*;
     use hdk::prelude::*;
+    use holo_hash::fixt::AgentPubKeyFixturator;
     use holochain_types::fixt::CapSecretFixturator;
     use holochain_types::prelude::*;
     use holochain_types::test_utils::fake_agent_pubkey_1;
@@ -233,4 +247,286 @@ pub mod wasm_test {
 
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_grants() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Capability])
+            .await
+            .unwrap();
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .return_const(Some(dna_file.clone().into()));
+        dna_store.expect_add_dna().return_const(());
+        dna_store.expect_add_dnas::<Vec<_>>().return_const(());
+        dna_store.expect_add_entry_defs::<Vec<_>>().return_const(());
+
+        let mut conductor =
+            SweetConductor::from_builder(ConductorBuilder::with_mock_dna_store(dna_store)).await;
+
+        let apps = conductor
+            .setup_app_for_agents("app-", &[fake_agent_pubkey_1()], &[dna_file.into()])
+            .await
+            .unwrap();
+
+        let ((alice,),) = apps.into_tuples();
+        let alice = alice.zome(TestWasm::Capability);
+
+        let secret_of = |grant: &CapGrantEntry| match &grant.access {
+            CapAccess::Transferable { secret } => Some(*secret),
+            _ => None,
+        };
+
+        // the unrestricted grant made in init
+        let grants: Vec<CapGrantEntry> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(1, grants.len());
+        assert_eq!(CapAccess::Unrestricted, grants[0].access);
+
+        let original_secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+        let original_grant_hash: HeaderHash = conductor
+            .call(&alice, "transferable_cap_grant", original_secret)
+            .await;
+
+        let grants: Vec<CapGrantEntry> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(2, grants.len());
+        assert!(grants
+            .iter()
+            .any(|grant| secret_of(grant) == Some(original_secret)));
+
+        // an update revokes the original grant
+        let new_grant_hash: HeaderHash = conductor
+            .call(&alice, "roll_cap_grant", original_grant_hash)
+            .await;
+
+        let grants: Vec<CapGrantEntry> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(2, grants.len());
+        assert!(!grants
+            .iter()
+            .any(|grant| secret_of(grant) == Some(original_secret)));
+
+        // a delete revokes the new grant
+        let _: HeaderHash = conductor
+            .call(&alice, "delete_cap_grant", new_grant_hash)
+            .await;
+
+        let grants: Vec<CapGrantEntry> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(1, grants.len());
+        assert_eq!(CapAccess::Unrestricted, grants[0].access);
+
+        conductor.shutdown().await;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_duplicate_tagged_cap_grant() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Capability])
+            .await
+            .unwrap();
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .return_const(Some(dna_file.clone().into()));
+        dna_store.expect_add_dna().return_const(());
+        dna_store.expect_add_dnas::<Vec<_>>().return_const(());
+        dna_store.expect_add_entry_defs::<Vec<_>>().return_const(());
+
+        let mut conductor =
+            SweetConductor::from_builder(ConductorBuilder::with_mock_dna_store(dna_store)).await;
+
+        let apps = conductor
+            .setup_app_for_agents("app-", &[fake_agent_pubkey_1()], &[dna_file.into()])
+            .await
+            .unwrap();
+
+        let ((alice,),) = apps.into_tuples();
+        let alice = alice.zome(TestWasm::Capability);
+
+        let mut secrets = CapSecretFixturator::new(Unpredictable);
+
+        // untagged grants for the same functions can be created freely
+        let _: HeaderHash = conductor
+            .call(&alice, "transferable_cap_grant", secrets.next().unwrap())
+            .await;
+        let _: HeaderHash = conductor
+            .call(&alice, "transferable_cap_grant", secrets.next().unwrap())
+            .await;
+
+        let tagged_grant_hash: HeaderHash = conductor
+            .call(&alice, "tagged_cap_grant", secrets.next().unwrap())
+            .await;
+
+        // the tag is taken, even with a new secret
+        let result: ConductorApiResult<HeaderHash> = conductor
+            .call_fallible(&alice, "tagged_cap_grant", secrets.next().unwrap())
+            .await;
+        assert!(result.is_err());
+
+        let grants: Vec<CapGrantEntry> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(
+            1,
+            grants.iter().filter(|grant| grant.tag == "tagged").count()
+        );
+
+        // the tag is free again once the grant is revoked
+        let _: HeaderHash = conductor
+            .call(&alice, "delete_cap_grant", tagged_grant_hash)
+            .await;
+        let _: HeaderHash = conductor
+            .call(&alice, "tagged_cap_grant", secrets.next().unwrap())
+            .await;
+
+        conductor.shutdown().await;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_revoked_access() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::WhoAmI])
+            .await
+            .unwrap();
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .return_const(Some(dna_file.clone().into()));
+        dna_store.expect_add_dna().return_const(());
+        dna_store.expect_add_dnas::<Vec<_>>().return_const(());
+        dna_store.expect_add_entry_defs::<Vec<_>>().return_const(());
+
+        let mut conductor =
+            SweetConductor::from_builder(ConductorBuilder::with_mock_dna_store(dna_store)).await;
+
+        let apps = conductor
+            .setup_app_for_agents("app-", &[fake_agent_pubkey_1()], &[dna_file.into()])
+            .await
+            .unwrap();
+
+        let ((bobbo,),) = apps.into_tuples();
+        let bobbo = bobbo.zome(TestWasm::WhoAmI);
+
+        // someone other than bob calling bob's whoami
+        let handle = conductor.inner_handle();
+        let call_whoami = || {
+            handle.call_zome(ZomeCall {
+                cell_id: bobbo.cell_id().clone(),
+                zome_name: bobbo.name().clone(),
+                fn_name: "whoami".into(),
+                cap: None,
+                provenance: fake_agent_pubkey_2(),
+                payload: ExternIO::encode(()).unwrap(),
+            })
+        };
+
+        let output = call_whoami().await.unwrap().unwrap();
+        assert_matches!(output, ZomeCallResponse::Unauthorized(_, _, _, _));
+
+        // BOB OPENS ACCESS
+
+        let _: () = conductor.call(&bobbo, "set_access", ()).await;
+        let output = call_whoami().await.unwrap().unwrap();
+        assert_matches!(output, ZomeCallResponse::Ok(_));
+
+        // BOB REVOKES ACCESS SO THE CALL IS DENIED AGAIN
+
+        let _: () = conductor.call(&bobbo, "revoke_access", ()).await;
+        let output = call_whoami().await.unwrap().unwrap();
+        assert_matches!(output, ZomeCallResponse::Unauthorized(_, _, _, _));
+
+        // revoking again is a no-op, and access can be reopened
+
+        let _: () = conductor.call(&bobbo, "revoke_access", ()).await;
+        let _: () = conductor.call(&bobbo, "set_access", ()).await;
+        let output = call_whoami().await.unwrap().unwrap();
+        assert_matches!(output, ZomeCallResponse::Ok(_));
+
+        conductor.shutdown().await;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_assigned_call() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Capability])
+            .await
+            .unwrap();
+
+        let alice_agent_id = fake_agent_pubkey_1();
+        let bob_agent_id = fake_agent_pubkey_2();
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .return_const(Some(dna_file.clone().into()));
+        dna_store.expect_add_dna().return_const(());
+        dna_store.expect_add_dnas::<Vec<_>>().return_const(());
+        dna_store.expect_add_entry_defs::<Vec<_>>().return_const(());
+
+        let mut conductor =
+            SweetConductor::from_builder(ConductorBuilder::with_mock_dna_store(dna_store)).await;
+
+        let apps = conductor
+            .setup_app_for_agents(
+                "app-",
+                &[alice_agent_id.clone(), bob_agent_id.clone()],
+                &[dna_file.into()],
+            )
+            .await
+            .unwrap();
+
+        let ((alice,), (bobbo,)) = apps.into_tuples();
+        let alice = alice.zome(TestWasm::Capability);
+        let bobbo = bobbo.zome(TestWasm::Capability);
+
+        #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+        pub struct CapFor(CapSecret, AgentPubKey);
+
+        let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+
+        // BOB ASSIGNS THE SECRET TO SOMEONE ELSE SO ALICE IS DENIED EVEN WITH THE SECRET
+
+        let someone_else = AgentPubKeyFixturator::new(Unpredictable).next().unwrap();
+        let _: HeaderHash = conductor
+            .call(&bobbo, "assigned_cap_grant", CapFor(secret, someone_else))
+            .await;
+
+        let output: ZomeCallResponse = conductor
+            .call(
+                &alice,
+                "try_cap_claim",
+                CapFor(secret, bob_agent_id.clone()),
+            )
+            .await;
+        assert_matches!(output, ZomeCallResponse::Unauthorized(_, _, _, _));
+
+        // BOB ASSIGNS THE SECRET TO ALICE SO NOW SHE CAN CALL
+
+        let _: HeaderHash = conductor
+            .call(
+                &bobbo,
+                "assigned_cap_grant",
+                CapFor(secret, alice_agent_id.clone()),
+            )
+            .await;
+
+        let output: ZomeCallResponse = conductor
+            .call(
+                &alice,
+                "try_cap_claim",
+                CapFor(secret, bob_agent_id.clone()),
+            )
+            .await;
+        assert_eq!(output, ZomeCallResponse::Ok(ExternIO::encode(()).unwrap()));
+
+        conductor.shutdown().await;
+
+        Ok(())
+    }
 }
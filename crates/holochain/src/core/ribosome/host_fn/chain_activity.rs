@@ -0,0 +1,91 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+/// page through the headers of the local source chain, in chain order
+/// unlike get_agent_activity this never goes to the network
+pub fn chain_activity(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: ChainActivityInput,
+) -> Result<Vec<(u32, HeaderHash)>, WasmError> {
+    let ChainActivityInput { start_index, limit } = input;
+    tokio_helper::block_forever_on(async move {
+        let activity: Vec<(u32, HeaderHash)> = call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .chain_activity(start_index, limit)
+            .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))?;
+        Ok(activity)
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_activity_pages_the_chain() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+
+        let _hash_a: EntryHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Query, "add_path", "a".to_string());
+        let _hash_b: EntryHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Query, "add_path", "b".to_string());
+
+        let all: Vec<(u32, HeaderHash)> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Query,
+            "chain_activity",
+            ChainActivityInput::new(0, u32::MAX)
+        );
+        // the same headers as a query, oldest first and numbered from 0
+        let elements: Vec<Element> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Query,
+            "query",
+            ChainQueryFilter::default()
+        );
+        assert_eq!(elements.len(), all.len());
+        for (i, (element, (index, header_hash))) in
+            elements.iter().rev().zip(all.iter()).enumerate()
+        {
+            assert_eq!(i as u32, *index);
+            assert_eq!(element.header_address(), header_hash);
+        }
+
+        let page: Vec<(u32, HeaderHash)> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Query,
+            "chain_activity",
+            ChainActivityInput::new(1, 2)
+        );
+        assert_eq!(all[1..3].to_vec(), page);
+
+        let past_the_head: Vec<(u32, HeaderHash)> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Query,
+            "chain_activity",
+            ChainActivityInput::new(all.len() as u32, 10)
+        );
+        assert!(past_the_head.is_empty());
+    }
+}
@@ -17,35 +17,23 @@ pub fn remote_signal(
     const FN_NAME: &str = "recv_remote_signal";
     // Timeouts and errors are ignored,
     // this is a send and forget operation.
-    let network = call_context.host_access().network().clone();
+    let mut network = call_context.host_access().network().clone();
     let RemoteSignal { agents, signal } = input;
     let zome_name: ZomeName = call_context.zome().into();
     let fn_name: FunctionName = FN_NAME.into();
-    for agent in agents {
-        tokio::task::spawn(
-            {
-                let mut network = network.clone();
-                let zome_name = zome_name.clone();
-                let fn_name = fn_name.clone();
-                let payload = signal.clone();
-                async move {
-                    tracing::debug!("sending to {:?}", agent);
-                    let result = network
-                        .call_remote(agent.clone(), zome_name, fn_name, None, payload)
-                        .await;
-                    tracing::debug!("sent to {:?}", agent);
-                    if let Err(e) = result {
-                        tracing::info!(
-                            "Failed to send remote signal to {:?} because of {:?}",
-                            agent,
-                            e
-                        );
-                    }
-                }
+    tokio::task::spawn(
+        async move {
+            tracing::debug!("sending to {:?}", agents);
+            // failures to individual agents are logged by the network
+            let result = network
+                .remote_signal(agents, zome_name, fn_name, None, signal, None)
+                .await;
+            if let Err(e) = result {
+                tracing::info!("Failed to send remote signal because of {:?}", e);
             }
-            .in_current_span(),
-        );
-    }
+        }
+        .in_current_span(),
+    );
     Ok(())
 }
 
@@ -83,17 +71,10 @@ mod tests {
                 api.emit_signal(AppSignal::new(signal)).map_err(Into::into)
             })
             .callback("init", move |api, ()| {
-                let mut functions: GrantedFunctions = BTreeSet::new();
-                functions.insert((
+                let cap_grant_entry = CapGrantEntry::unrestricted(vec![(
                     api.zome_info(()).unwrap().zome_name,
                     "recv_remote_signal".into(),
-                ));
-                let cap_grant_entry = CapGrantEntry {
-                    tag: "".into(),
-                    // empty access converts to unrestricted
-                    access: ().into(),
-                    functions,
-                };
+                )]);
                 api.create(EntryWithDefId::new(
                     EntryDefId::CapGrant,
                     Entry::CapGrant(cap_grant_entry),
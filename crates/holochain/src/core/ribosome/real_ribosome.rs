@@ -34,9 +34,11 @@ use crate::core::ribosome::host_fn::app_info::app_info;
 use crate::core::ribosome::host_fn::call::call;
 use crate::core::ribosome::host_fn::call_info::call_info;
 use crate::core::ribosome::host_fn::call_remote::call_remote;
+use crate::core::ribosome::host_fn::call_remote_multi::call_remote_multi;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
+use crate::core::ribosome::host_fn::chain_activity::chain_activity;
 use crate::core::ribosome::host_fn::create::create;
 use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
@@ -328,6 +330,10 @@ impl RealRibosome {
                 func!(invoke_host_function!(get_agent_activity)),
             );
             ns.insert("__query", func!(invoke_host_function!(query)));
+            ns.insert(
+                "__chain_activity",
+                func!(invoke_host_function!(chain_activity)),
+            );
         } else {
             ns.insert("__get", func!(invoke_host_function!(unreachable)));
             ns.insert("__get_details", func!(invoke_host_function!(unreachable)));
@@ -341,6 +347,10 @@ impl RealRibosome {
                 func!(invoke_host_function!(unreachable)),
             );
             ns.insert("__query", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__chain_activity",
+                func!(invoke_host_function!(unreachable)),
+            );
         }
 
         if let HostFnAccess {
@@ -349,12 +359,20 @@ impl RealRibosome {
         } = host_fn_access
         {
             ns.insert("__call_remote", func!(invoke_host_function!(call_remote)));
+            ns.insert(
+                "__call_remote_multi",
+                func!(invoke_host_function!(call_remote_multi)),
+            );
             ns.insert(
                 "__remote_signal",
                 func!(invoke_host_function!(remote_signal)),
             );
         } else {
             ns.insert("__call_remote", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__call_remote_multi",
+                func!(invoke_host_function!(unreachable)),
+            );
             ns.insert("__remote_signal", func!(invoke_host_function!(unreachable)));
         }
 
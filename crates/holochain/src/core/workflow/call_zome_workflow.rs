@@ -78,6 +78,7 @@ pub async fn call_zome_workflow<
         let mut guard = workspace_lock.write().await;
         let workspace = &mut guard;
         writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+        workspace.source_chain.announce_head()?;
     }
 
     trigger_produce_dht_ops.trigger();
@@ -40,6 +40,7 @@ pub async fn initialize_zomes_workflow<'env, Ribosome: RibosomeT>(
         let workspace: &mut CallZomeWorkspace = &mut guard;
         // commit the workspace
         writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+        workspace.source_chain.announce_head()?;
     }
     Ok(result)
 }
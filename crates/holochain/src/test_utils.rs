@@ -193,8 +193,12 @@ async fn test_network_inner<F>(
 where
     F: Fn(&HolochainP2pEvent) -> bool + Send + 'static,
 {
+    // these networks are local to the test, so there are no remote
+    // identities worth verifying
+    let mut config = holochain_p2p::kitsune_p2p::KitsuneP2pConfig::default();
+    config.transport_security = holochain_p2p::kitsune_p2p::TransportSecurityConfig::insecure();
     let (network, mut recv) = spawn_holochain_p2p(
-        holochain_p2p::kitsune_p2p::KitsuneP2pConfig::default(),
+        config,
         holochain_p2p::kitsune_p2p::dependencies::kitsune_p2p_proxy::TlsConfig::new_ephemeral()
             .await
             .unwrap(),
@@ -70,6 +70,11 @@ pub enum DbName {
     ValidationReceipts,
     /// Single store for all known agents on the network
     Agent,
+    /// Additional int KV stores laid out like [DbName::ChainSequence], so tests
+    /// can keep several independent source chains in one environment
+    #[cfg(feature = "test_chain_sequences")]
+    #[display(fmt = "TestChainSequence{}", _0)]
+    TestChainSequence(u8),
 }
 
 impl DbName {
@@ -100,6 +105,8 @@ impl DbName {
             ValidationLimbo => Single,
             ValidationReceipts => Multi,
             Agent => Single,
+            #[cfg(feature = "test_chain_sequences")]
+            TestChainSequence(_) => SingleInt,
         }
     }
 }
@@ -172,6 +179,10 @@ lazy_static! {
     pub static ref VALIDATION_RECEIPTS: DbKey<MultiStore> = DbKey::new(DbName::ValidationReceipts);
     /// The key to access the Agent database
     pub static ref AGENT: DbKey<SingleStore> = DbKey::new(DbName::Agent);
+    /// The keys to access the TestChainSequence databases of a Cell environment
+    #[cfg(feature = "test_chain_sequences")]
+    pub static ref TEST_CHAIN_SEQUENCES: Vec<DbKey<IntegerStore>> =
+    (0..4).map(|i| DbKey::new(DbName::TestChainSequence(i))).collect();
 }
 
 lazy_static! {
@@ -235,6 +246,10 @@ fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> Data
             register_db(env, um, &*INTEGRATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_RECEIPTS)?;
+            #[cfg(feature = "test_chain_sequences")]
+            for key in TEST_CHAIN_SEQUENCES.iter() {
+                register_db(env, um, key)?;
+            }
         }
         EnvironmentKind::Conductor => {
             register_db(env, um, &*CONDUCTOR_STATE)?;
@@ -43,6 +43,7 @@ pub trait HolochainP2pCellT {
     async fn leave(&mut self) -> actor::HolochainP2pResult<()>;
 
     /// Invoke a zome function on a remote node (if you have been granted the capability).
+    /// If `timeout_ms` is None the network default timeout is used.
     async fn call_remote(
         &mut self,
         to_agent: AgentPubKey,
@@ -50,8 +51,36 @@ pub trait HolochainP2pCellT {
         fn_name: FunctionName,
         cap: Option<CapSecret>,
         payload: ExternIO,
+        timeout_ms: Option<u64>,
     ) -> actor::HolochainP2pResult<SerializedBytes>;
 
+    /// Invoke a zome function on the agents near a basis hash.
+    /// Agents that fail or don't respond within the timeout are left out of the result.
+    #[allow(clippy::too_many_arguments)]
+    async fn call_remote_multi(
+        &mut self,
+        basis: holo_hash::AnyDhtHash,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        remote_agent_count: Option<u8>,
+        timeout_ms: Option<u64>,
+    ) -> actor::HolochainP2pResult<Vec<(AgentPubKey, SerializedBytes)>>;
+
+    /// Invoke a zome function on each of the given agents without awaiting a response.
+    /// Failures to individual agents are logged and otherwise dropped.
+    #[allow(clippy::too_many_arguments)]
+    async fn remote_signal(
+        &mut self,
+        to_agent_list: Vec<AgentPubKey>,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        timeout_ms: Option<u64>,
+    ) -> actor::HolochainP2pResult<()>;
+
     /// Publish data to the correct neighborhood.
     #[allow(clippy::ptr_arg)]
     async fn publish(
@@ -149,6 +178,7 @@ impl HolochainP2pCellT for HolochainP2pCell {
         fn_name: FunctionName,
         cap: Option<CapSecret>,
         payload: ExternIO,
+        timeout_ms: Option<u64>,
     ) -> actor::HolochainP2pResult<SerializedBytes> {
         self.sender
             .call_remote(
@@ -159,6 +189,57 @@ impl HolochainP2pCellT for HolochainP2pCell {
                 fn_name,
                 cap,
                 payload,
+                timeout_ms,
+            )
+            .await
+    }
+
+    /// Invoke a zome function on the agents near a basis hash.
+    async fn call_remote_multi(
+        &mut self,
+        basis: holo_hash::AnyDhtHash,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        remote_agent_count: Option<u8>,
+        timeout_ms: Option<u64>,
+    ) -> actor::HolochainP2pResult<Vec<(AgentPubKey, SerializedBytes)>> {
+        self.sender
+            .call_remote_multi(
+                (*self.dna_hash).clone(),
+                (*self.from_agent).clone(),
+                basis,
+                zome_name,
+                fn_name,
+                cap,
+                payload,
+                remote_agent_count,
+                timeout_ms,
+            )
+            .await
+    }
+
+    /// Invoke a zome function on each of the given agents without awaiting a response.
+    async fn remote_signal(
+        &mut self,
+        to_agent_list: Vec<AgentPubKey>,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        timeout_ms: Option<u64>,
+    ) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .remote_signal(
+                (*self.dna_hash).clone(),
+                (*self.from_agent).clone(),
+                to_agent_list,
+                zome_name,
+                fn_name,
+                cap,
+                payload,
+                timeout_ms,
             )
             .await
     }
@@ -71,6 +71,39 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming signal from a remote node
+    #[allow(clippy::too_many_arguments)]
+    fn handle_incoming_remote_signal(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        from_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        data: Vec<u8>,
+    ) -> kitsune_p2p::event::KitsuneP2pEventHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            // a signal is handled like a call, but nobody is waiting for the response
+            evt_sender
+                .call_remote(
+                    dna_hash,
+                    to_agent,
+                    from_agent,
+                    zome_name,
+                    fn_name,
+                    cap,
+                    ExternIO::from(data),
+                )
+                .await
+                .map_err(kitsune_p2p::KitsuneP2pError::from)?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming get request from a remote node
     #[tracing::instrument(skip(self, dna_hash, to_agent, dht_hash, options), level = "trace")]
     fn handle_incoming_get(
@@ -330,14 +363,20 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 query,
                 options,
             } => self.handle_incoming_get_agent_activity(space, to_agent, agent, query, options),
-            // holochain_p2p never publishes via request
-            // these only occur on broadcasts
+            // holochain_p2p never publishes or signals via request
+            // these only occur on broadcasts and notifies
             crate::wire::WireMessage::Publish { .. } => {
                 Err(HolochainP2pError::invalid_p2p_message(
                     "invalid: publish is a broadcast type, not a request".to_string(),
                 )
                 .into())
             }
+            crate::wire::WireMessage::RemoteSignal { .. } => {
+                Err(HolochainP2pError::invalid_p2p_message(
+                    "invalid: remote signal is a notify type, not a request".to_string(),
+                )
+                .into())
+            }
             crate::wire::WireMessage::ValidationReceipt { receipt } => {
                 self.handle_incoming_validation_receipt(space, to_agent, receipt)
             }
@@ -388,6 +427,14 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 dht_hash,
                 ops,
             ),
+            crate::wire::WireMessage::RemoteSignal {
+                zome_name,
+                fn_name,
+                cap,
+                data,
+            } => self.handle_incoming_remote_signal(
+                space, to_agent, from_agent, zome_name, fn_name, cap, data,
+            ),
         }
     }
 
@@ -524,9 +571,12 @@ impl HolochainP2pHandler for HolochainP2pActor {
         let agent = agent_pub_key.into_kitsune();
 
         let kitsune_p2p = self.kitsune_p2p.clone();
-        Ok(async move { Ok(kitsune_p2p.join(space, agent).await?) }
-            .boxed()
-            .into())
+        Ok(async move {
+            kitsune_p2p.join(space, agent, None).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
     }
 
     #[tracing::instrument(skip(self), level = "trace")]
@@ -539,9 +589,11 @@ impl HolochainP2pHandler for HolochainP2pActor {
         let agent = agent_pub_key.into_kitsune();
 
         let kitsune_p2p = self.kitsune_p2p.clone();
-        Ok(async move { Ok(kitsune_p2p.leave(space, agent).await?) }
-            .boxed()
-            .into())
+        Ok(
+            async move { Ok(kitsune_p2p.leave(space, agent, false).await?) }
+                .boxed()
+                .into(),
+        )
     }
 
     #[tracing::instrument(skip(self), level = "trace")]
@@ -554,6 +606,7 @@ impl HolochainP2pHandler for HolochainP2pActor {
         fn_name: FunctionName,
         cap: Option<CapSecret>,
         payload: ExternIO,
+        timeout_ms: Option<u64>,
     ) -> HolochainP2pHandlerResult<SerializedBytes> {
         let space = dna_hash.into_kitsune();
         let to_agent = to_agent.into_kitsune();
@@ -565,7 +618,7 @@ impl HolochainP2pHandler for HolochainP2pActor {
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
             let result: Vec<u8> = kitsune_p2p
-                .rpc_single(space, to_agent, from_agent, req, None)
+                .rpc_single(space, to_agent, from_agent, req, timeout_ms)
                 .await?;
             Ok(UnsafeBytes::from(result).into())
         }
@@ -573,6 +626,108 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self, payload), level = "trace")]
+    fn handle_call_remote_multi(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        basis: holo_hash::AnyDhtHash,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        remote_agent_count: Option<u8>,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<Vec<(AgentPubKey, SerializedBytes)>> {
+        let space = dna_hash.into_kitsune();
+        let from_agent = from_agent.into_kitsune();
+        let basis = basis.to_kitsune();
+
+        let payload =
+            crate::wire::WireMessage::call_remote(zome_name, fn_name, cap, payload).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            let result = kitsune_p2p
+                .rpc_multi(kitsune_p2p::actor::RpcMulti {
+                    space,
+                    from_agent,
+                    basis,
+                    remote_agent_count,
+                    timeout_ms,
+                    // wait for every agent rather than the first to answer
+                    as_race: false,
+                    race_timeout_ms: None,
+                    priority: kitsune_p2p::actor::MessagePriority::Normal,
+                    selection: kitsune_p2p::actor::AgentSelection::Any,
+                    payload,
+                })
+                .await?;
+
+            Ok(result
+                .into_iter()
+                .map(
+                    |kitsune_p2p::actor::RpcMultiResponse {
+                         agent, response, ..
+                     }| {
+                        (
+                            AgentPubKey::from_kitsune(&agent),
+                            UnsafeBytes::from(response).into(),
+                        )
+                    },
+                )
+                .collect())
+        }
+        .boxed()
+        .into())
+    }
+
+    #[tracing::instrument(skip(self, payload), level = "trace")]
+    fn handle_remote_signal(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        to_agent_list: Vec<AgentPubKey>,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let from_agent = from_agent.into_kitsune();
+
+        let payload =
+            crate::wire::WireMessage::remote_signal(zome_name, fn_name, cap, payload).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            let sends = to_agent_list.into_iter().map(|agent| {
+                let kitsune_p2p = kitsune_p2p.clone();
+                let space = space.clone();
+                let from_agent = from_agent.clone();
+                let payload = payload.clone();
+                async move {
+                    let to_agent = agent.clone().into_kitsune();
+                    if let Err(e) = kitsune_p2p
+                        .notify_single(space, to_agent, from_agent, payload, timeout_ms)
+                        .await
+                    {
+                        tracing::info!(
+                            "Failed to send remote signal to {:?} because of {:?}",
+                            agent,
+                            e
+                        );
+                    }
+                }
+            });
+            futures::future::join_all(sends).await;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_publish(
         &mut self,
@@ -599,7 +754,10 @@ impl HolochainP2pHandler for HolochainP2pActor {
                     basis,
                     remote_agent_count: None, // default best-effort
                     timeout_ms,
+                    max_nodes: 0, // network default
+                    priority: kitsune_p2p::actor::MessagePriority::Normal,
                     payload,
+                    msg_id: None,
                 })
                 .await?;
             Ok(())
@@ -657,6 +815,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
                     timeout_ms: options.timeout_ms,
                     as_race: options.as_race,
                     race_timeout_ms: options.race_timeout_ms,
+                    priority: kitsune_p2p::actor::MessagePriority::Normal,
+                    selection: kitsune_p2p::actor::AgentSelection::Any,
                     payload,
                 })
                 .instrument(tracing::debug_span!("rpc_multi"))
@@ -700,6 +860,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
                     timeout_ms: options.timeout_ms,
                     as_race: options.as_race,
                     race_timeout_ms: options.race_timeout_ms,
+                    priority: kitsune_p2p::actor::MessagePriority::Normal,
+                    selection: kitsune_p2p::actor::AgentSelection::Any,
                     payload,
                 })
                 .await?;
@@ -745,6 +907,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
                     timeout_ms: options.timeout_ms,
                     as_race: false,
                     race_timeout_ms: options.timeout_ms,
+                    priority: kitsune_p2p::actor::MessagePriority::Normal,
+                    selection: kitsune_p2p::actor::AgentSelection::Any,
                     payload,
                 })
                 .await?;
@@ -795,6 +959,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
                     timeout_ms: options.timeout_ms,
                     as_race: false,
                     race_timeout_ms: options.timeout_ms,
+                    priority: kitsune_p2p::actor::MessagePriority::Normal,
+                    selection: kitsune_p2p::actor::AgentSelection::Any,
                     payload,
                 })
                 .await?;
@@ -37,9 +37,37 @@ impl HolochainP2pHandler for StubNetwork {
         fn_name: FunctionName,
         cap: Option<CapSecret>,
         payload: ExternIO,
+        timeout_ms: Option<u64>,
     ) -> HolochainP2pHandlerResult<SerializedBytes> {
         Err("stub".into())
     }
+    fn handle_call_remote_multi(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        basis: holo_hash::AnyDhtHash,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        remote_agent_count: Option<u8>,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<Vec<(AgentPubKey, SerializedBytes)>> {
+        Err("stub".into())
+    }
+    fn handle_remote_signal(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        to_agent_list: Vec<AgentPubKey>,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
     fn handle_publish(
         &mut self,
         dna_hash: DnaHash,
@@ -174,6 +202,22 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_error_is_timeout() {
+        let timed_out: HolochainP2pError = kitsune_p2p::KitsuneP2pError::from(
+            kitsune_p2p_types::KitsuneError::from(kitsune_p2p_types::KitsuneErrorKind::TimedOut),
+        )
+        .into();
+        assert!(timed_out.is_timeout());
+
+        let closed: HolochainP2pError = kitsune_p2p::KitsuneP2pError::from(
+            kitsune_p2p_types::KitsuneError::from(kitsune_p2p_types::KitsuneErrorKind::Closed),
+        )
+        .into();
+        assert!(!closed.is_timeout());
+        assert!(!HolochainP2pError::from("timeout".to_string()).is_timeout());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_call_remote_workflow() {
         let (dna, a1, a2, _) = test_setup();
@@ -220,6 +264,7 @@ mod tests {
                 "".into(),
                 None,
                 ExternIO::encode(b"yippo").unwrap(),
+                None,
             )
             .await
             .unwrap();
@@ -231,6 +276,76 @@ mod tests {
         r_task.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remote_signal_workflow() {
+        let (dna, a1, a2, a3) = test_setup();
+
+        let (p2p, mut evt) = spawn_holochain_p2p(
+            KitsuneP2pConfig::default(),
+            TlsConfig::new_ephemeral().await.unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let (sig_send, mut sig_recv) = tokio::sync::mpsc::unbounded_channel();
+        let r_task = tokio::task::spawn(async move {
+            use tokio_stream::StreamExt;
+            while let Some(evt) = evt.next().await {
+                use crate::types::event::HolochainP2pEvent::*;
+                match evt {
+                    CallRemote {
+                        respond,
+                        to_agent,
+                        fn_name,
+                        payload,
+                        ..
+                    } => {
+                        sig_send.send((to_agent, fn_name, payload)).unwrap();
+                        respond.r(Ok(async move {
+                            Ok(UnsafeBytes::from(b"ignored".to_vec()).into())
+                        }
+                        .boxed()
+                        .into()));
+                    }
+                    SignNetworkData { respond, .. } => {
+                        respond.r(Ok(async move { Ok([0; 64].into()) }.boxed().into()));
+                    }
+                    PutAgentInfoSigned { respond, .. } => {
+                        respond.r(Ok(async move { Ok(()) }.boxed().into()));
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        p2p.join(dna.clone(), a1.clone()).await.unwrap();
+        p2p.join(dna.clone(), a2.clone()).await.unwrap();
+
+        // a3 never joined, so can't be reached, but that doesn't fail the signal
+        p2p.remote_signal(
+            dna,
+            a1,
+            vec![a2.clone(), a3],
+            "".into(),
+            "recv_remote_signal".into(),
+            None,
+            ExternIO::encode(b"yippo").unwrap(),
+            Some(1000),
+        )
+        .await
+        .unwrap();
+
+        let (to_agent, fn_name, payload) = sig_recv.recv().await.unwrap();
+        assert_eq!(a2, to_agent);
+        assert_eq!(FunctionName::from("recv_remote_signal"), fn_name);
+        assert_eq!(ExternIO::encode(b"yippo").unwrap(), payload);
+
+        p2p.ghost_actor_shutdown().await.unwrap();
+        r_task.await.unwrap();
+        // a2 got the signal once, and a3 not at all
+        assert!(sig_recv.recv().await.is_none());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_send_validation_receipt_workflow() {
         let (dna, a1, a2, _) = test_setup();
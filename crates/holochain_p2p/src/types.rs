@@ -41,6 +41,11 @@ impl HolochainP2pError {
     pub fn invalid_p2p_message(s: String) -> Self {
         Self::InvalidP2pMessage(s)
     }
+
+    /// did the network give up waiting for a response?
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::OtherKitsuneP2pError(e) if e.is_timeout())
+    }
 }
 
 // do some manual type translation so we get better error displays
@@ -198,6 +198,7 @@ ghost_actor::ghost_chan! {
         fn leave(dna_hash: DnaHash, agent_pub_key: AgentPubKey) -> ();
 
         /// Invoke a zome function on a remote node (if you have been granted the capability).
+        /// If `timeout_ms` is None the network default timeout is used.
         fn call_remote(
             dna_hash: DnaHash,
             from_agent: AgentPubKey,
@@ -206,8 +207,38 @@ ghost_actor::ghost_chan! {
             fn_name: FunctionName,
             cap: Option<CapSecret>,
             payload: ExternIO,
+            timeout_ms: Option<u64>,
         ) -> SerializedBytes;
 
+        /// Invoke a zome function on the agents near a basis hash.
+        /// Agents that fail or don't respond within the timeout are left out of the result.
+        fn call_remote_multi(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            basis: holo_hash::AnyDhtHash,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+            cap: Option<CapSecret>,
+            payload: ExternIO,
+            remote_agent_count: Option<u8>,
+            timeout_ms: Option<u64>,
+        ) -> Vec<(AgentPubKey, SerializedBytes)>;
+
+        /// Invoke a zome function on each of the given agents without awaiting a response,
+        /// e.g. to push a notification. Delivery is best-effort and at-most-once.
+        /// Resolves once every agent has received the signal or failed to, and
+        /// failures to individual agents are logged and otherwise dropped.
+        fn remote_signal(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            to_agent_list: Vec<AgentPubKey>,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+            cap: Option<CapSecret>,
+            payload: ExternIO,
+            timeout_ms: Option<u64>,
+        ) -> ();
+
         /// Publish data to the correct neighborhood.
         fn publish(
             dna_hash: DnaHash,
@@ -29,6 +29,13 @@ pub(crate) enum WireMessage {
         #[serde(with = "serde_bytes")]
         data: Vec<u8>,
     },
+    RemoteSignal {
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+    },
     Publish {
         request_validation_receipt: bool,
         dht_hash: holo_hash::AnyDhtHash,
@@ -83,6 +90,20 @@ impl WireMessage {
         }
     }
 
+    pub fn remote_signal(
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+    ) -> WireMessage {
+        Self::RemoteSignal {
+            zome_name,
+            fn_name,
+            cap,
+            data: payload.into_vec(),
+        }
+    }
+
     pub fn publish(
         request_validation_receipt: bool,
         dht_hash: holo_hash::AnyDhtHash,
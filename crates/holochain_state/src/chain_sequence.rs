@@ -9,20 +9,48 @@
 /// If the chain head has moved since the db was created, committing the transaction fails with a special error type.
 use crate::source_chain::{SourceChainError, SourceChainResult};
 use fallible_iterator::DoubleEndedFallibleIterator;
+use holo_hash::encode::holo_dht_location_bytes;
 use holo_hash::HeaderHash;
+use holo_hash::HOLO_HASH_CORE_LEN;
 use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvIntBufFresh;
 use holochain_lmdb::buffer::KvIntStore;
+use holochain_lmdb::db::DbKey;
 use holochain_lmdb::db::GetDb;
 use holochain_lmdb::db::CHAIN_SEQUENCE;
 use holochain_lmdb::error::DatabaseError;
 use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::exports::IntegerStore;
 use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use tracing::*;
 
+#[cfg(any(test, feature = "test_utils"))]
+pub use mem_store::MemChainSequenceStore;
+pub use store::ChainSequenceStore;
+
+#[cfg(any(test, feature = "test_utils"))]
+mod mem_store;
+mod store;
+
+/// What [`ChainSequenceBuf::flush_to_txn_with`] should do when the chain head
+/// has moved since the buffer was created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Fail the flush with SourceChainError::HeadMoved, as flush_to_txn does.
+    Abort,
+    /// Rebase the headers added in this transaction on top of the new head,
+    /// and flush them there.  See [`ChainSequenceBuf::rebase`] for what the
+    /// caller is responsible for.
+    Rebase,
+}
+
 /// A Value in the ChainSequence database.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChainSequenceItem {
@@ -33,30 +61,93 @@ pub struct ChainSequenceItem {
 
 type Store = KvIntBufFresh<ChainSequenceItem>;
 
-/// A BufferedStore for interacting with the ChainSequence database
-pub struct ChainSequenceBuf {
-    buf: Store,
+/// The cached incomplete DHT op count of a buffer which hasn't counted them since it was
+/// created or last flushed.
+const UNCOUNTED: u64 = u64::MAX;
+
+/// A point-in-time copy of a ChainSequenceBuf's head info, including any headers
+/// added in the buffer's transaction but not yet flushed.  It holds no reader,
+/// so it can be cloned and sent to other tasks, e.g. for status reporting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainSequenceSnapshot {
+    current_head: Option<HeaderHash>,
+    next_index: u32,
+    tx_seq: u32,
+}
+
+impl ChainSequenceSnapshot {
+    /// The chain head when the snapshot was taken. None if the chain was empty.
+    pub fn chain_head(&self) -> Option<&HeaderHash> {
+        self.current_head.as_ref()
+    }
+
+    /// empty if len is 0
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The length of the chain when the snapshot was taken.
+    pub fn len(&self) -> u32 {
+        self.next_index
+    }
+
+    /// The tx_seq of the transaction the buffer was writing.
+    pub fn tx_seq(&self) -> u32 {
+        self.tx_seq
+    }
+}
+
+/// Chain growth counts from a ChainSequenceBuf, including any headers added
+/// in the buffer's transaction but not yet flushed.  Suitable for exporting
+/// as gauges, e.g. to watch chain growth and the DHT op publish backlog.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChainSequenceMetrics {
+    /// The number of headers in the chain.
+    pub len: u32,
+    /// The number of transactional bundles, i.e. distinct tx_seq values, in the chain.
+    pub bundles: u32,
+    /// The number of headers which have not yet been marked with complete_dht_op.
+    pub incomplete_dht_ops: u32,
+}
+
+/// A BufferedStore for interacting with the ChainSequence database, backed by LMDB
+/// unless another ChainSequenceStore is given, eg. a MemChainSequenceStore in tests.
+pub struct ChainSequenceBuf<S = KvIntBufFresh<ChainSequenceItem>> {
+    buf: S,
     next_index: u32,
     tx_seq: u32,
     current_head: Option<HeaderHash>,
     persisted_head: Option<HeaderHash>,
+    persisted_next_index: u32,
+    head_listener: Option<tokio::sync::mpsc::Sender<(u32, HeaderHash)>>,
+    unannounced_head: Option<(u32, HeaderHash)>,
+    head_moved_counter: Option<Arc<AtomicU64>>,
+    header_index: Option<HashMap<HeaderHash, u32>>,
+    /// The incomplete DHT op count, or UNCOUNTED; atomic so metrics can cache it through &self.
+    incomplete_dht_ops: AtomicU64,
 }
 
 impl ChainSequenceBuf {
     /// Create a new instance
     pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
-        let buf: Store = KvIntBufFresh::new(env.clone(), env.get_db(&*CHAIN_SEQUENCE)?);
-        let (next_index, tx_seq, current_head) =
-            fresh_reader!(env, |r| { Self::head_info(buf.store(), &r) })?;
-        let persisted_head = current_head.clone();
+        Self::new_named(env, &*CHAIN_SEQUENCE)
+    }
 
-        Ok(ChainSequenceBuf {
-            buf,
-            next_index,
-            tx_seq,
-            current_head,
-            persisted_head,
-        })
+    /// Create a new instance backed by the given database rather than the
+    /// environment's ChainSequence database, e.g. one of the TEST_CHAIN_SEQUENCES,
+    /// so that tests can keep several independent chains in one environment.
+    pub fn new_named(
+        env: EnvironmentRead,
+        key: &'static DbKey<IntegerStore>,
+    ) -> DatabaseResult<Self> {
+        let db = env.get_db(key)?;
+        Self::from_db(env, db)
+    }
+
+    fn from_db(env: EnvironmentRead, db: IntegerStore) -> DatabaseResult<Self> {
+        let buf: Store = KvIntBufFresh::new(env.clone(), db);
+        let head_info = fresh_reader!(env, |r| { Self::head_info(buf.store(), &r) })?;
+        Ok(Self::with_store(buf, head_info))
     }
 
     fn head_info<R: Readable>(
@@ -80,34 +171,392 @@ impl ChainSequenceBuf {
         )
     }
 
-    /// Get the chain head, AKA top chain header. None if the chain is empty.
+    /// Recover from a SourceChainError::HeadMoved by re-reading the persisted
+    /// chain and re-appending the headers added in this transaction on top of
+    /// the new head, returning a fresh buffer ready to flush.  The rebased
+    /// buffer uses the same database as this one, eg. one opened with new_named.
+    ///
+    /// Only the header addresses are carried over: the caller is responsible
+    /// for ensuring those headers are still valid on top of the new head, and
+    /// any complete_dht_op marks made in this transaction must be redone.
+    pub fn rebase(mut self) -> SourceChainResult<Self> {
+        self.rebase_in_place()?;
+        Ok(self)
+    }
+
+    /// As rebase, but replaces this buffer with the rebased one.
+    fn rebase_in_place(&mut self) -> SourceChainResult<()> {
+        let header_addresses: Vec<HeaderHash> =
+            self.iter_from(self.persisted_next_index).collect()?;
+        let mut rebased = Self::from_db(self.buf.env().clone(), self.buf.store().db())?;
+        if self.header_index.is_some() {
+            rebased = rebased.with_header_index()?;
+        }
+        rebased.head_listener = self.head_listener.take();
+        rebased.unannounced_head = self.unannounced_head.take();
+        rebased.head_moved_counter = self.head_moved_counter.take();
+        rebased.put_headers(header_addresses)?;
+        *self = rebased;
+        Ok(())
+    }
+
+    /// Flush like flush_to_txn, but if the as-at check fails, let `on_conflict`
+    /// decide whether to abort or rebase and flush again, eg. for append-only
+    /// data where the order of competing writes doesn't matter.  It is called
+    /// with the head this buffer was based on, and the head persisted by the
+    /// competing writer.  No other writer can commit while we hold the
+    /// writer, so a rebased flush can't conflict again.  On a rebase this
+    /// buffer is replaced by the rebased one, so announce_head can still be
+    /// called once the writer is committed.
+    pub fn flush_to_txn_with(
+        &mut self,
+        writer: &mut Writer,
+        on_conflict: impl FnOnce(Option<&HeaderHash>, Option<&HeaderHash>) -> ConflictResolution,
+    ) -> SourceChainResult<()> {
+        match self.flush_to_txn_ref(writer) {
+            Err(SourceChainError::HeadMoved(ours, theirs, count)) => {
+                match on_conflict(ours.as_ref(), theirs.as_ref()) {
+                    ConflictResolution::Abort => {
+                        Err(SourceChainError::HeadMoved(ours, theirs, count))
+                    }
+                    ConflictResolution::Rebase => {
+                        self.rebase_in_place()?;
+                        self.flush_to_txn_ref(writer)
+                    }
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Lazily iterate over the (index, header address) of every persisted header which has not
+    /// yet been marked with complete_dht_op.  Errors if there are unflushed changes, since only
+    /// the persisted chain is scanned.
+    pub fn get_items_with_incomplete_dht_ops<'txn, R: Readable>(
+        &self,
+        r: &'txn R,
+    ) -> SourceChainResult<
+        Box<dyn FallibleIterator<Item = (u32, HeaderHash), Error = DatabaseError> + 'txn>,
+    > {
+        if !self.buf.is_scratch_fresh() {
+            return Err(SourceChainError::ScratchNotFresh);
+        }
+        // TODO: PERF: Currently this checks every header but we could keep
+        // a list of indices for only the headers which have been transformed.
+        // There is nothing to compact in the meantime: the completion flag is
+        // stored in the same item as the header address and tx_seq, and those
+        // items are the chain index itself, so they can never be pruned.
+        Ok(Box::new(self.buf.store().iter(r)?.filter_map(|(i, c)| {
+            Ok(if !c.dht_transforms_complete {
+                Some((
+                    IntKey::from_key_bytes_or_friendly_panic(i).into(),
+                    c.header_address,
+                ))
+            } else {
+                None
+            })
+        })))
+    }
+
+    /// Check that the indices of the persisted chain run 0, 1, 2, ... with no
+    /// gaps, returning SourceChainError::ChainGap at the first mismatch.
+    /// This is a self-check against database corruption, eg. at startup.
+    pub fn validate_contiguous(&self) -> SourceChainResult<()> {
+        let env = self.buf.env().clone();
+        fresh_reader!(env, |r| {
+            let mut indices = self
+                .buf
+                .store()
+                .iter(&r)?
+                .map(|(key, _)| Ok(u32::from(IntKey::from_key_bytes_or_friendly_panic(key))));
+            let mut expected = 0;
+            while let Some(found) = indices.next()? {
+                if found != expected {
+                    return Err(SourceChainError::ChainGap { expected, found });
+                }
+                expected += 1;
+            }
+            Ok(())
+        })
+    }
+
+    /// Scan for an index claimed by two different headers, returning the first such index and the
+    /// competing header addresses, persisted first.  The database holds one header per index, so
+    /// the only way two bundles can both extend the chain from the same index is for another
+    /// writer to persist headers at indices this buffer has also added headers at.  Flushing such
+    /// a buffer fails the as-at check; this is a diagnostic aid for seeing where the chains
+    /// diverged when debugging consistency failures.
+    pub fn detect_fork(&self) -> SourceChainResult<Option<(u32, Vec<HeaderHash>)>> {
+        let mut added = Vec::new();
+        for i in self.persisted_next_index..self.next_index {
+            if let Some(item) = self.buf.get(&i.into())? {
+                added.push((i, item.header_address));
+            }
+        }
+        let env = self.buf.env().clone();
+        fresh_reader!(env, |r| {
+            for (i, ours) in added {
+                if let Some(theirs) = self.buf.store().get(&r, &i.into())? {
+                    if theirs.header_address != ours {
+                        return Ok(Some((i, vec![theirs.header_address, ours])));
+                    }
+                }
+            }
+            Ok(None)
+        })
+    }
+
+    /// Check that the persisted chain head is still `expected` (None for an empty chain), failing
+    /// fast with SourceChainError::HeadMoved otherwise.  This lets a workflow confirm the head its
+    /// work was built on before doing expensive downstream work, rather than finding out when the
+    /// as-at check fails on flush.  Reads the database, so sees headers committed by other writers
+    /// since this buffer was created, but not headers added in this buffer's scratch space.  The
+    /// number of intervening headers is only reported if `expected` is this buffer's own
+    /// persisted head.
+    pub fn expect_head(&self, expected: Option<&HeaderHash>) -> SourceChainResult<()> {
+        let env = self.buf.env().clone();
+        let (persisted_next_index, _, persisted_head) =
+            fresh_reader!(env, |r| { Self::head_info(self.buf.store(), &r) })?;
+        if persisted_head.as_ref() == expected {
+            return Ok(());
+        }
+        let intervening = if expected == self.persisted_head.as_ref() {
+            persisted_next_index.checked_sub(self.persisted_next_index)
+        } else {
+            None
+        };
+        Err(SourceChainError::HeadMoved(
+            expected.cloned(),
+            persisted_head,
+            intervening,
+        ))
+    }
+}
+
+impl<S: ChainSequenceStore> ChainSequenceBuf<S> {
+    /// Create a buffer over a store whose persisted chain has this (next index, next tx_seq, head).
+    fn with_store(
+        buf: S,
+        (next_index, tx_seq, current_head): (u32, u32, Option<HeaderHash>),
+    ) -> Self {
+        let persisted_head = current_head.clone();
+
+        ChainSequenceBuf {
+            buf,
+            next_index,
+            tx_seq,
+            current_head,
+            persisted_head,
+            persisted_next_index: next_index,
+            head_listener: None,
+            unannounced_head: None,
+            head_moved_counter: None,
+            header_index: None,
+            incomplete_dht_ops: AtomicU64::new(UNCOUNTED),
+        }
+    }
+
+    /// Register a listener to be sent the (index, address) of the new chain
+    /// head whenever this buffer moves the chain.  Nothing is sent by the
+    /// flush itself, since its transaction may yet be rolled back: the caller
+    /// must call announce_head once the writer has been committed, as the
+    /// call_zome workflow does for its source chain.
+    pub fn with_head_listener(
+        mut self,
+        head_listener: tokio::sync::mpsc::Sender<(u32, HeaderHash)>,
+    ) -> Self {
+        self.head_listener = Some(head_listener);
+        self
+    }
+
+    /// Register a counter to be incremented each time a flush fails the
+    /// as-at check with SourceChainError::HeadMoved, and on no other error.
+    /// The counter may be shared by every buffer writing to a cell, so a
+    /// steadily climbing count flags a hot cell whose writers keep colliding.
+    pub fn with_head_moved_counter(mut self, head_moved_counter: Arc<AtomicU64>) -> Self {
+        self.head_moved_counter = Some(head_moved_counter);
+        self
+    }
+
+    /// Maintain a reverse index from header address to chain index, so that index_of_header and
+    /// contains_header are O(1) lookups rather than scans of the chain.  Building the index reads
+    /// the whole chain once, and it holds a copy of every header address in the chain (around 40
+    /// bytes each, plus the map's overhead) for the life of the buffer, so it is only worthwhile
+    /// for buffers which will make many membership queries, e.g. while validating a long chain.
+    pub fn with_header_index(mut self) -> DatabaseResult<Self> {
+        let mut header_index = HashMap::with_capacity(self.next_index as usize);
+        for i in 0..self.next_index {
+            if let Some(header_address) = self.get_at_index(i)? {
+                header_index.insert(header_address, i);
+            }
+        }
+        self.header_index = Some(header_index);
+        Ok(self)
+    }
+
+    /// Get the chain head, AKA top chain header, including any headers added in this transaction
+    /// but not yet flushed. None if the chain is empty.
     pub fn chain_head(&self) -> Option<&HeaderHash> {
         self.current_head.as_ref()
     }
 
+    /// Get the chain head as of the last flush, or when the buffer was created, ie. the head
+    /// other writers and peers can see.  Unlike chain_head, this excludes headers added in this
+    /// transaction.  None if the persisted chain is empty.
+    pub fn persisted_head(&self) -> Option<&HeaderHash> {
+        self.persisted_head.as_ref()
+    }
+
     /// empty if len is 0
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
-    /// The length is just the next index
-    pub fn len(&self) -> usize {
-        self.next_index as usize
+    /// The length of the chain, including any headers added in this transaction but not yet
+    /// flushed.  This is just the next index.
+    pub fn len(&self) -> u32 {
+        self.next_index
+    }
+
+    /// Take a snapshot of the chain head, length and tx_seq, including any
+    /// headers added in this transaction but not yet flushed.
+    pub fn snapshot(&self) -> ChainSequenceSnapshot {
+        ChainSequenceSnapshot {
+            current_head: self.current_head.clone(),
+            next_index: self.next_index,
+            tx_seq: self.tx_seq,
+        }
+    }
+
+    /// Gather the chain's length, bundle count and incomplete DHT op count.
+    ///
+    /// The length is already known, and since tx_seq never decreases along the chain, bundles
+    /// are counted by binary searching for the end of each one rather than reading every
+    /// header.  Headers can be marked complete in any order though, so the first call counts
+    /// incomplete DHT ops by reading each header once.  The count is then cached and kept up to
+    /// date by put_header, complete_dht_op and truncate_to, so later calls don't read the chain
+    /// again until the buffer is flushed.  Other writers may have completed ops by then, so the
+    /// first call after a flush counts them afresh.
+    pub fn metrics(&self) -> DatabaseResult<ChainSequenceMetrics> {
+        let mut bundles = 0;
+        let mut start = 0;
+        while start < self.next_index {
+            let tx_seq = match self.buf.get_item(start)? {
+                Some(item) => item.tx_seq,
+                None => break,
+            };
+            bundles += 1;
+            // find the first index of the next bundle
+            let (mut lo, mut hi) = (start + 1, self.next_index);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                match self.buf.get_item(mid)? {
+                    Some(item) if item.tx_seq == tx_seq => lo = mid + 1,
+                    _ => hi = mid,
+                }
+            }
+            start = lo;
+        }
+
+        let incomplete_dht_ops = match self.incomplete_dht_ops.load(Ordering::Relaxed) {
+            UNCOUNTED => {
+                let mut count = 0;
+                for i in 0..self.next_index {
+                    if let Some(item) = self.buf.get_item(i)? {
+                        if !item.dht_transforms_complete {
+                            count += 1;
+                        }
+                    }
+                }
+                self.incomplete_dht_ops
+                    .store(u64::from(count), Ordering::Relaxed);
+                count
+            }
+            count => count as u32,
+        };
+
+        Ok(ChainSequenceMetrics {
+            len: self.next_index,
+            bundles,
+            incomplete_dht_ops,
+        })
     }
 
-    /// Get a header at an index
-    pub fn get(&self, i: u32) -> DatabaseResult<Option<HeaderHash>> {
+    /// Get the address of the header at a chain index, checking this transaction's scratch space
+    /// before the persisted chain.  None if there is no header at that index (yet).
+    pub fn get_at_index(&self, i: u32) -> DatabaseResult<Option<HeaderHash>> {
         self.buf
-            .get(&i.into())
+            .get_item(i)
             .map(|seq_item| seq_item.map(|si| si.header_address))
     }
 
+    /// Iterate over the header addresses in chain order, starting at (and including) the given
+    /// index.  Like chain_head, this includes headers added in this transaction but not yet
+    /// flushed.  Each header is read lazily, so a peer can request "everything after sequence N"
+    /// without loading the whole chain.
+    pub fn iter_from(
+        &self,
+        index: u32,
+    ) -> impl FallibleIterator<Item = HeaderHash, Error = DatabaseError> + '_ {
+        fallible_iterator::convert((index..self.next_index).map(move |i| self.get_at_index(i)))
+            .filter_map(Ok)
+    }
+
+    /// Iterate over the chain indices and header addresses newest first, starting at the chain
+    /// head.  Like iter_from, this includes headers added in this transaction but not yet flushed,
+    /// which come first, and each header is read lazily, so showing recent activity doesn't load
+    /// the whole chain.
+    pub fn iter_back(
+        &self,
+    ) -> impl FallibleIterator<Item = (u32, HeaderHash), Error = DatabaseError> + '_ {
+        fallible_iterator::convert(
+            (0..self.next_index)
+                .rev()
+                .map(move |i| Ok(self.get_at_index(i)?.map(|hash| (i, hash)))),
+        )
+        .filter_map(Ok)
+    }
+
+    /// Get the chain index of the header with this address.  Like chain_head, this includes
+    /// headers added in this transaction but not yet flushed, and doesn't see headers persisted
+    /// by other writers since this buffer was created.  None if the header isn't in the chain.
+    /// This scans the chain back from the head, unless the buffer was built with_header_index.
+    pub fn index_of_header(&self, header_address: &HeaderHash) -> DatabaseResult<Option<u32>> {
+        match &self.header_index {
+            Some(header_index) => Ok(header_index.get(header_address).copied()),
+            None => self
+                .iter_back()
+                .find(|(_, h)| Ok(h == header_address))
+                .map(|found| found.map(|(i, _)| i)),
+        }
+    }
+
+    /// Whether the header with this address is in the chain; see index_of_header.
+    pub fn contains_header(&self, header_address: &HeaderHash) -> DatabaseResult<bool> {
+        Ok(self.index_of_header(header_address)?.is_some())
+    }
+
+    /// Get the address of the last header in the transactional bundle with this tx_seq, ie. the
+    /// chain head just after that bundle was committed.  Like chain_head, this includes headers
+    /// added in this transaction but not yet flushed.  None if there is no such bundle.
+    pub fn head_at_tx_seq(&self, tx_seq: u32) -> DatabaseResult<Option<HeaderHash>> {
+        // tx_seq never decreases along the chain, so search back from the head
+        for i in (0..self.next_index).rev() {
+            match self.buf.get_item(i)? {
+                Some(item) if item.tx_seq == tx_seq => return Ok(Some(item.header_address)),
+                Some(item) if item.tx_seq < tx_seq => break,
+                _ => (),
+            }
+        }
+        Ok(None)
+    }
+
     /// Add a header to the chain, setting all other values automatically.
     /// This is intentionally the only way to modify this database.
     #[instrument(skip(self))]
     pub fn put_header(&mut self, header_address: HeaderHash) -> DatabaseResult<()> {
-        self.buf.put(
-            self.next_index.into(),
+        self.buf.put_item(
+            self.next_index,
             ChainSequenceItem {
                 header_address: header_address.clone(),
                 tx_seq: self.tx_seq,
@@ -115,38 +564,111 @@ impl ChainSequenceBuf {
             },
         )?;
         trace!(self.next_index);
+        if let Some(header_index) = &mut self.header_index {
+            header_index.insert(header_address.clone(), self.next_index);
+        }
+        let count = self.incomplete_dht_ops.get_mut();
+        if *count != UNCOUNTED {
+            *count += 1;
+        }
         self.next_index += 1;
         self.current_head = Some(header_address);
         Ok(())
     }
 
-    pub fn get_items_with_incomplete_dht_ops<'txn, R: Readable>(
-        &self,
-        r: &'txn R,
-    ) -> SourceChainResult<
-        Box<dyn FallibleIterator<Item = (u32, HeaderHash), Error = DatabaseError> + 'txn>,
-    > {
-        if !self.buf.is_scratch_fresh() {
-            return Err(SourceChainError::ScratchNotFresh);
+    /// As put_header, but first checks the header address is well-formed: its DHT location bytes
+    /// must be those computed from its hash, as they are for any address made by hashing a header.
+    /// An address built from arbitrary bytes is rejected here, rather than going unnoticed until
+    /// something tries to find the header it doesn't address.
+    pub fn put_header_checked(&mut self, header_address: HeaderHash) -> SourceChainResult<()> {
+        let loc_bytes = holo_dht_location_bytes(header_address.get_raw_32());
+        if header_address.get_raw_36()[HOLO_HASH_CORE_LEN..] != loc_bytes[..] {
+            return Err(SourceChainError::MalformedHeaderAddress(header_address));
         }
-        // TODO: PERF: Currently this checks every header but we could keep
-        // a list of indices for only the headers which have been transformed.
-        Ok(Box::new(self.buf.store().iter(r)?.filter_map(|(i, c)| {
-            Ok(if !c.dht_transforms_complete {
-                Some((
-                    IntKey::from_key_bytes_or_friendly_panic(i).into(),
-                    c.header_address,
-                ))
-            } else {
-                None
-            })
-        })))
+        Ok(self.put_header(header_address)?)
+    }
+
+    /// Add a bundle of headers to the chain, in order, at contiguous indices.
+    /// All headers in the bundle share the same tx_seq, marking them as
+    /// committed together in one transactional bundle.
+    pub fn put_headers(
+        &mut self,
+        header_addresses: impl IntoIterator<Item = HeaderHash>,
+    ) -> DatabaseResult<()> {
+        for header_address in header_addresses {
+            self.put_header(header_address)?;
+        }
+        Ok(())
+    }
+
+    /// Roll the chain back to the given length, removing the headers at this index and above,
+    /// whether they were added in this transaction or are already persisted.  Does nothing if
+    /// the chain is not longer than the index.  Truncating persisted headers moves the chain,
+    /// so flushing is subject to the usual as-at check.
+    pub fn truncate_to(&mut self, index: u32) -> DatabaseResult<()> {
+        if index >= self.next_index {
+            return Ok(());
+        }
+        for i in index..self.next_index {
+            let count = self.incomplete_dht_ops.get_mut();
+            if *count != UNCOUNTED {
+                if let Some(item) = self.buf.get_item(i)? {
+                    if !item.dht_transforms_complete {
+                        *count -= 1;
+                    }
+                }
+            }
+            self.buf.delete_item(i)?;
+            // Headers added in this transaction were never persisted, so just drop
+            // them from the scratch space.  Leaving a delete in place would remove
+            // whatever a competing writer put at this index, without an as-at check
+            // if this transaction no longer moves the chain.
+            if i >= self.persisted_next_index {
+                self.buf.cancel_delete_item(i)?;
+            }
+        }
+        if let Some(header_index) = &mut self.header_index {
+            header_index.retain(|_, i| *i < index);
+        }
+        self.next_index = index;
+        self.current_head = match index.checked_sub(1) {
+            Some(i) => self.get_at_index(i)?,
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Send the chain head written by the last flush to the head listener, if
+    /// any.  Call this once the flush's transaction has been committed: the
+    /// head is only announced if it is actually persisted, so a write which
+    /// was rolled back is never announced.  A full or closed listener is
+    /// logged and otherwise ignored.
+    pub fn announce_head(&mut self) -> DatabaseResult<()> {
+        if let Some((index, head)) = self.unannounced_head.take() {
+            if let Some(head_listener) = &self.head_listener {
+                if self.get_at_index(index)?.as_ref() == Some(&head) {
+                    if let Err(e) = head_listener.try_send((index, head)) {
+                        warn!(?e, "Failed to announce new chain head");
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 
+    /// Mark the header at this index as having had its DHT ops produced, so that it is no longer
+    /// returned by get_items_with_incomplete_dht_ops once flushed.  Does nothing if there is no
+    /// header at the index.
     pub fn complete_dht_op(&mut self, i: u32) -> SourceChainResult<()> {
-        if let Some(mut c) = self.buf.get(&i.into())? {
+        if let Some(mut c) = self.buf.get_item(i)? {
+            if !c.dht_transforms_complete {
+                let count = self.incomplete_dht_ops.get_mut();
+                if *count != UNCOUNTED {
+                    *count -= 1;
+                }
+            }
             c.dht_transforms_complete = true;
-            self.buf.put(i.into(), c)?;
+            self.buf.put_item(i, c)?;
         }
         Ok(())
     }
@@ -158,7 +680,44 @@ impl ChainSequenceBuf {
     /// or when produce_dht_ops updates the
     /// dht_transforms_complete.
     pub fn chain_moved_in_this_transaction(&self) -> bool {
-        self.current_head != self.persisted_head
+        self.current_head != self.persisted_head || self.next_index != self.persisted_next_index
+    }
+
+    /// Perform the as-at check against the persisted chain's (next index, next tx_seq, head),
+    /// returning a SourceChainError::HeadMoved error if it fails, or else `write` the scratch
+    /// space to the store and begin a new bundle.
+    fn flush_checked(
+        &mut self,
+        (persisted_next_index, _, persisted_head): (u32, u32, Option<HeaderHash>),
+        write: impl FnOnce(&mut S) -> SourceChainResult<()>,
+    ) -> SourceChainResult<()> {
+        let persisted_head_moved = self.persisted_head != persisted_head;
+        if persisted_head_moved && self.chain_moved_in_this_transaction() {
+            if let Some(head_moved_counter) = &self.head_moved_counter {
+                head_moved_counter.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(SourceChainError::HeadMoved(
+                self.persisted_head.to_owned(),
+                persisted_head,
+                persisted_next_index.checked_sub(self.persisted_next_index),
+            ))
+        } else {
+            write(&mut self.buf)?;
+            // Other writers may complete ops from now on, so count them again when next asked.
+            *self.incomplete_dht_ops.get_mut() = UNCOUNTED;
+            // The bundle is written, so begin a new one: this buffer may be
+            // flushed again, and later headers must get the next tx_seq.
+            if self.chain_moved_in_this_transaction() {
+                self.tx_seq += 1;
+                self.persisted_head = self.current_head.clone();
+                self.persisted_next_index = self.next_index;
+                self.unannounced_head = self
+                    .current_head
+                    .clone()
+                    .map(|head| (self.next_index - 1, head));
+            }
+            Ok(())
+        }
     }
 }
 
@@ -170,7 +729,9 @@ impl BufferedStore for ChainSequenceBuf {
     }
 
     /// Commit to the source chain, performing an as-at check and returning a
-    /// SourceChainError::HeadMoved error if the as-at check fails
+    /// SourceChainError::HeadMoved error if the as-at check fails.  The error
+    /// includes the number of headers committed by the competing writer, so
+    /// that retry logic knows how far it must rebase.
     fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> SourceChainResult<()> {
         // Nothing to write
         if self.is_clean() {
@@ -179,17 +740,35 @@ impl BufferedStore for ChainSequenceBuf {
 
         // Writing a chain move
         let env = self.buf.env().clone();
-        let db = env.get_db(&*CHAIN_SEQUENCE)?;
-        let (_, _, persisted_head) = ChainSequenceBuf::head_info(&KvIntStore::new(db), writer)?;
-        let persisted_head_moved = self.persisted_head != persisted_head;
-        if persisted_head_moved && self.chain_moved_in_this_transaction() {
-            Err(SourceChainError::HeadMoved(
-                self.persisted_head.to_owned(),
-                persisted_head,
-            ))
-        } else {
-            Ok(self.buf.flush_to_txn_ref(writer)?)
+        let db = self.buf.store().db();
+        let persisted = ChainSequenceBuf::head_info(&KvIntStore::new(db), writer)?;
+        self.flush_checked(persisted, |buf| {
+            buf.flush_to_txn_ref(writer)?;
+            *buf = KvIntBufFresh::new(env, db);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test_utils"))]
+impl ChainSequenceBuf<MemChainSequenceStore> {
+    /// Create a buffer over an in-memory store, for testing the chain logic without an LMDB
+    /// environment.
+    pub fn new_in_memory(store: MemChainSequenceStore) -> Self {
+        let head_info = store.persisted_head_info();
+        Self::with_store(store, head_info)
+    }
+
+    /// Flush to the in-memory store, with the same as-at check as flush_to_txn.
+    pub fn flush(&mut self) -> SourceChainResult<()> {
+        if self.buf.is_scratch_clean() {
+            return Ok(());
         }
+        let persisted = self.buf.persisted_head_info();
+        self.flush_checked(persisted, |buf| {
+            buf.flush_scratch();
+            Ok(())
+        })
     }
 }
 
@@ -197,9 +776,14 @@ impl BufferedStore for ChainSequenceBuf {
 pub mod tests {
     use super::BufferedStore;
     use super::ChainSequenceBuf;
+    use super::ChainSequenceItem;
+    use super::ChainSequenceMetrics;
+    use super::ConflictResolution;
+    use super::MemChainSequenceStore;
     use super::SourceChainError;
     use crate::source_chain::SourceChainResult;
     use holo_hash::HeaderHash;
+    use holochain_lmdb::db::TEST_CHAIN_SEQUENCES;
     use holochain_lmdb::env::ReadManager;
     use holochain_lmdb::env::WriteManager;
     use holochain_lmdb::error::DatabaseResult;
@@ -207,6 +791,15 @@ pub mod tests {
     use holochain_lmdb::test_utils::test_cell_env;
     use matches::assert_matches;
     use observability;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    fn header_hash(i: u8) -> HeaderHash {
+        let mut raw = vec![0; 36];
+        raw[35] = i;
+        HeaderHash::from_raw_36(raw)
+    }
 
     #[tokio::test(flavor = "multi_thread")]
     async fn chain_sequence_scratch_awareness() -> DatabaseResult<()> {
@@ -383,6 +976,32 @@ pub mod tests {
             assert_eq!(items, vec![0, 0, 0, 1, 1, 1]);
         }
 
+        // Two flushes of a single buffer are two bundles
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((6..8).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+            buf.put_header(header_hash(8))?;
+            env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+            // Flushing with nothing new doesn't start a bundle
+            env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+            buf.put_header(header_hash(9))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        let reader = env.reader()?;
+        {
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.chain_head(), Some(&header_hash(9)));
+            let items: Vec<u32> = buf
+                .buf
+                .store()
+                .iter(&reader)?
+                .map(|(_, i)| Ok(i.tx_seq))
+                .collect()?;
+            assert_eq!(items, vec![0, 0, 0, 1, 1, 1, 2, 2, 3, 4]);
+        }
+
         Ok(())
     }
 
@@ -475,7 +1094,8 @@ pub mod tests {
                 None,
                 Some(
                     hash
-                )
+                ),
+                Some(3)
             ))
             if hash == expected_hash
         );
@@ -552,4 +1172,1040 @@ pub mod tests {
 
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_iter_from() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 0..3 {
+                buf.put_header(header_hash(i))?;
+            }
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 3..5 {
+                buf.put_header(header_hash(i))?;
+            }
+
+            // Spans both persisted and scratch headers
+            let items: Vec<HeaderHash> = buf.iter_from(1).collect()?;
+            assert_eq!(items, (1..5).map(header_hash).collect::<Vec<_>>());
+
+            // Only scratch headers
+            let items: Vec<HeaderHash> = buf.iter_from(4).collect()?;
+            assert_eq!(items, vec![header_hash(4)]);
+
+            // Past the head
+            assert_eq!(buf.iter_from(5).count()?, 0);
+            assert_eq!(buf.iter_from(u32::MAX).count()?, 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_iter_back() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.iter_back().count()?, 0);
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 0..3 {
+                buf.put_header(header_hash(i))?;
+            }
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 3..5 {
+                buf.put_header(header_hash(i))?;
+            }
+
+            // Uncommitted headers come first, then the persisted chain
+            let items: Vec<(u32, HeaderHash)> = buf.iter_back().collect()?;
+            assert_eq!(
+                items,
+                (0..5)
+                    .rev()
+                    .map(|i| (i as u32, header_hash(i)))
+                    .collect::<Vec<_>>()
+            );
+
+            // Stops early without reading the rest of the chain
+            let items: Vec<(u32, HeaderHash)> = buf.iter_back().take(2).collect()?;
+            assert_eq!(items, vec![(4, header_hash(4)), (3, header_hash(3))]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_contains_header() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((0..3).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        // The same answers with and without the reverse index
+        for indexed in vec![false, true] {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            if indexed {
+                buf = buf.with_header_index()?;
+            }
+            buf.put_headers((3..5).map(header_hash))?;
+
+            // Persisted and scratch headers are present
+            for i in 0..5 {
+                assert_eq!(buf.index_of_header(&header_hash(i))?, Some(i as u32));
+                assert!(buf.contains_header(&header_hash(i))?);
+            }
+            assert_eq!(buf.index_of_header(&header_hash(5))?, None);
+            assert!(!buf.contains_header(&header_hash(5))?);
+
+            // Truncated headers are absent, whether persisted or not
+            buf.truncate_to(2)?;
+            assert!(buf.contains_header(&header_hash(1))?);
+            assert!(!buf.contains_header(&header_hash(2))?);
+            assert!(!buf.contains_header(&header_hash(4))?);
+
+            // ... until they are put back
+            buf.put_header(header_hash(4))?;
+            assert_eq!(buf.index_of_header(&header_hash(4))?, Some(2));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_get_at_index() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 0..5 {
+                buf.put_header(header_hash(i))?;
+            }
+
+            // Before commit, the header is found in the scratch space
+            assert_eq!(buf.get_at_index(2)?, Some(header_hash(2)));
+            assert_eq!(buf.get_at_index(5)?, None);
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            // After commit, the header is found in the persisted chain
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.get_at_index(2)?, Some(header_hash(2)));
+            assert_eq!(buf.get_at_index(5)?, None);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_len() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert!(buf.is_empty());
+            assert_eq!(buf.len(), 0);
+            for i in 0..3 {
+                buf.put_header(header_hash(i))?;
+            }
+
+            // Scratch additions are counted before the flush
+            assert!(!buf.is_empty());
+            assert_eq!(buf.len(), 3);
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.len(), 3);
+            buf.put_header(header_hash(3))?;
+            assert_eq!(buf.len(), 4);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_head_at_tx_seq() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.head_at_tx_seq(0)?, None);
+            buf.put_headers((0..3).map(header_hash))?;
+            // The bundle being built in scratch is included
+            assert_eq!(buf.head_at_tx_seq(0)?, Some(header_hash(2)));
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((3..6).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        // tx_seqs are [0, 0, 0, 1, 1, 1]
+        let buf = ChainSequenceBuf::new(arc.clone().into())?;
+        assert_eq!(buf.head_at_tx_seq(0)?, Some(header_hash(2)));
+        assert_eq!(buf.head_at_tx_seq(1)?, Some(header_hash(5)));
+        assert_eq!(buf.head_at_tx_seq(2)?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_truncate_to() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((0..5).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        // Truncating beyond the end does nothing
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.truncate_to(5)?;
+            assert_eq!(buf.len(), 5);
+            assert!(buf.is_clean());
+        }
+
+        // Truncating scratch headers only drops them from the scratch space
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((5..7).map(header_hash))?;
+            buf.truncate_to(5)?;
+            assert_eq!(buf.len(), 5);
+            assert_eq!(buf.chain_head(), Some(&header_hash(4)));
+            assert!(!buf.chain_moved_in_this_transaction());
+            assert!(buf.is_clean());
+        }
+
+        // Truncate across persisted and scratch headers
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_header(header_hash(5))?;
+            buf.truncate_to(3)?;
+            assert_eq!(buf.len(), 3);
+            assert_eq!(buf.chain_head(), Some(&header_hash(2)));
+            assert_eq!(buf.get_at_index(3)?, None);
+            assert_eq!(buf.get_at_index(5)?, None);
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.len(), 3);
+            assert_eq!(buf.chain_head(), Some(&header_hash(2)));
+            let headers: Vec<HeaderHash> = buf.iter_from(0).collect()?;
+            assert_eq!(headers, (0..3).map(header_hash).collect::<Vec<_>>());
+            buf.validate_contiguous()?;
+        }
+
+        // Truncating to zero empties the chain
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.truncate_to(0)?;
+            assert!(buf.is_empty());
+            assert_eq!(buf.chain_head(), None);
+        }
+
+        // A truncation still fails if another writer moved the chain first
+        let mut truncating = ChainSequenceBuf::new(arc.clone().into())?;
+        truncating.truncate_to(1)?;
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_header(header_hash(9))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+        let result = env.with_commit(|mut writer| truncating.flush_to_txn(&mut writer));
+        assert_matches!(
+            result,
+            Err(SourceChainError::HeadMoved(Some(old), Some(new), Some(1)))
+            if old == header_hash(2) && new == header_hash(9)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_detect_fork() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((0..2).map(header_hash))?;
+            assert_eq!(None, buf.detect_fork()?);
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        let mut ours = ChainSequenceBuf::new(arc.clone().into())?;
+        ours.put_headers(vec![header_hash(2), header_hash(3)])?;
+        assert_eq!(None, ours.detect_fork()?);
+
+        // another writer puts a different header at index 2
+        {
+            let mut theirs = ChainSequenceBuf::new(arc.clone().into())?;
+            theirs.put_header(header_hash(9))?;
+            env.with_commit(|mut writer| theirs.flush_to_txn(&mut writer))?;
+        }
+        assert_eq!(
+            Some((2, vec![header_hash(9), header_hash(2)])),
+            ours.detect_fork()?
+        );
+
+        // the persisted chain alone has no competing headers
+        let fresh = ChainSequenceBuf::new(arc.clone().into())?;
+        assert_eq!(None, fresh.detect_fork()?);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_expect_head() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+        buf.expect_head(None)?;
+        // headers in scratch space aren't the persisted head
+        buf.put_headers((0..2).map(header_hash))?;
+        buf.expect_head(None)?;
+        assert_matches!(
+            buf.expect_head(Some(&header_hash(1))),
+            Err(SourceChainError::HeadMoved(Some(_), None, None))
+        );
+        env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        buf.expect_head(Some(&header_hash(1)))?;
+
+        // another writer moves the chain
+        let working = ChainSequenceBuf::new(arc.clone().into())?;
+        working.expect_head(Some(&header_hash(1)))?;
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_header(header_hash(2))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+        assert_matches!(
+            working.expect_head(Some(&header_hash(1))),
+            Err(SourceChainError::HeadMoved(Some(old), Some(new), Some(1)))
+            if old == header_hash(1) && new == header_hash(2)
+        );
+        assert_matches!(
+            working.expect_head(None),
+            Err(SourceChainError::HeadMoved(None, Some(new), None))
+            if new == header_hash(2)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_snapshot() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+        let empty = buf.snapshot();
+        assert!(empty.is_empty());
+        assert_eq!(empty.chain_head(), None);
+        assert_eq!(empty.tx_seq(), 0);
+
+        // Scratch additions are included in the snapshot
+        buf.put_headers(vec![header_hash(0), header_hash(1)])?;
+        let snapshot = buf.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.chain_head(), Some(&header_hash(1)));
+        assert_eq!(snapshot.tx_seq(), 0);
+
+        // The snapshot doesn't change as the buffer moves on, and can be
+        // sent to another task
+        buf.put_header(header_hash(2))?;
+        env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        let sent = tokio::task::spawn(async move { snapshot }).await.unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent.chain_head(), Some(&header_hash(1)));
+
+        let buf = ChainSequenceBuf::new(arc.clone().into())?;
+        let snapshot = buf.snapshot();
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.chain_head(), Some(&header_hash(2)));
+        assert_eq!(snapshot.tx_seq(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_named_stores_are_independent() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        let chain_a = &TEST_CHAIN_SEQUENCES[0];
+        let chain_b = &TEST_CHAIN_SEQUENCES[1];
+        {
+            let mut buf_a = ChainSequenceBuf::new_named(arc.clone().into(), chain_a)?;
+            buf_a.put_headers((0..3).map(header_hash))?;
+            env.with_commit(|mut writer| buf_a.flush_to_txn(&mut writer))?;
+
+            let mut buf_b = ChainSequenceBuf::new_named(arc.clone().into(), chain_b)?;
+            buf_b.put_header(header_hash(10))?;
+            env.with_commit(|mut writer| buf_b.flush_to_txn(&mut writer))?;
+        }
+
+        let buf_a = ChainSequenceBuf::new_named(arc.clone().into(), chain_a)?;
+        assert_eq!(buf_a.len(), 3);
+        assert_eq!(buf_a.chain_head(), Some(&header_hash(2)));
+        let buf_b = ChainSequenceBuf::new_named(arc.clone().into(), chain_b)?;
+        assert_eq!(buf_b.len(), 1);
+        assert_eq!(buf_b.chain_head(), Some(&header_hash(10)));
+        // the default store is untouched
+        assert!(ChainSequenceBuf::new(arc.clone().into())?.is_empty());
+
+        // moving one chain doesn't trip the as-at check of another
+        let mut buf_a = ChainSequenceBuf::new_named(arc.clone().into(), chain_a)?;
+        let mut buf_b = ChainSequenceBuf::new_named(arc.clone().into(), chain_b)?;
+        buf_a.put_header(header_hash(3))?;
+        buf_b.put_header(header_hash(11))?;
+        env.with_commit(|mut writer| buf_a.flush_to_txn(&mut writer))?;
+        env.with_commit(|mut writer| buf_b.flush_to_txn(&mut writer))?;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_metrics() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        assert_eq!(
+            ChainSequenceBuf::new(arc.clone().into())?.metrics()?,
+            ChainSequenceMetrics::default()
+        );
+
+        // bundles of 3, 1 and 4 headers
+        let mut start = 0;
+        for size in &[3, 1, 4] {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((start..start + size).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+            start += size;
+        }
+
+        let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+        buf.complete_dht_op(0)?;
+        buf.complete_dht_op(5)?;
+        // a bundle of 2 in scratch
+        buf.put_headers((8..10).map(header_hash))?;
+
+        // tx_seqs are [0, 0, 0, 1, 2, 2, 2, 2, 3, 3]
+        let tx_seqs: Vec<u32> = (0..buf.len())
+            .map(|i| buf.buf.get(&i.into()).unwrap().unwrap().tx_seq)
+            .collect();
+        let mut distinct = tx_seqs.clone();
+        distinct.dedup();
+        assert_eq!(
+            buf.metrics()?,
+            ChainSequenceMetrics {
+                len: 10,
+                bundles: distinct.len() as u32,
+                incomplete_dht_ops: 8,
+            }
+        );
+        assert_eq!(distinct.len(), 4);
+
+        // the cached count follows later changes, and is counted again after a flush
+        buf.complete_dht_op(9)?;
+        buf.complete_dht_op(9)?;
+        assert_eq!(buf.metrics()?.incomplete_dht_ops, 7);
+        env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+        buf.put_header(header_hash(10))?;
+        assert_eq!(buf.metrics()?.incomplete_dht_ops, 8);
+
+        buf.truncate_to(10)?;
+        assert_eq!(
+            buf.metrics()?.incomplete_dht_ops,
+            ChainSequenceBuf::new(arc.clone().into())?
+                .metrics()?
+                .incomplete_dht_ops
+        );
+
+        // truncating into a bundle keeps it, truncating to its start removes it
+        buf.truncate_to(6)?;
+        assert_eq!(buf.metrics()?.bundles, 3);
+        buf.truncate_to(4)?;
+        assert_eq!(
+            buf.metrics()?,
+            ChainSequenceMetrics {
+                len: 4,
+                bundles: 2,
+                incomplete_dht_ops: 3,
+            }
+        );
+
+        // ops completed through another buffer are counted once this one is flushed
+        {
+            let mut other = ChainSequenceBuf::new(arc.clone().into())?;
+            other.complete_dht_op(1)?;
+            env.with_commit(|mut writer| other.flush_to_txn(&mut writer))?;
+        }
+        env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+        assert_eq!(buf.metrics()?.incomplete_dht_ops, 2);
+        buf.complete_dht_op(1)?;
+        assert_eq!(buf.metrics()?.incomplete_dht_ops, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_complete_dht_op() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 0..3 {
+                buf.put_header(header_hash(i))?;
+            }
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.complete_dht_op(1)?;
+            // A missing index is ignored
+            buf.complete_dht_op(3)?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        let reader = env.reader()?;
+        {
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_eq!(buf.len(), 3);
+            let items: Vec<bool> = buf
+                .buf
+                .store()
+                .iter(&reader)?
+                .map(|(_, i)| Ok(i.dht_transforms_complete))
+                .collect()?;
+            assert_eq!(items, vec![false, true, false]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_incomplete_dht_ops() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            for i in 0..5 {
+                buf.put_header(header_hash(i))?;
+            }
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.complete_dht_op(0)?;
+            buf.complete_dht_op(3)?;
+
+            // Unflushed changes can't be scanned
+            {
+                let reader = env.reader()?;
+                assert_matches!(
+                    buf.get_items_with_incomplete_dht_ops(&reader).err(),
+                    Some(SourceChainError::ScratchNotFresh)
+                );
+            }
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        let reader = env.reader()?;
+        {
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            let items: Vec<(u32, HeaderHash)> =
+                buf.get_items_with_incomplete_dht_ops(&reader)?.collect()?;
+            assert_eq!(
+                items,
+                vec![
+                    (1, header_hash(1)),
+                    (2, header_hash(2)),
+                    (4, header_hash(4))
+                ]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_put_headers() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((0..3).map(header_hash))?;
+            assert_eq!(buf.len(), 3);
+            assert_eq!(buf.chain_head(), Some(&header_hash(2)));
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_headers((3..5).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        let reader = env.reader()?;
+        {
+            let buf = ChainSequenceBuf::new(arc.clone().into())?;
+            let items: Vec<(u32, HeaderHash, u32)> = buf
+                .buf
+                .store()
+                .iter(&reader)?
+                .map(|(key, i)| {
+                    Ok((
+                        IntKey::from_key_bytes_or_friendly_panic(key).into(),
+                        i.header_address,
+                        i.tx_seq,
+                    ))
+                })
+                .collect()?;
+            assert_eq!(
+                items,
+                vec![
+                    (0, header_hash(0), 0),
+                    (1, header_hash(1), 0),
+                    (2, header_hash(2), 0),
+                    (3, header_hash(3), 1),
+                    (4, header_hash(4), 1),
+                ]
+            );
+        }
+
+        Ok(())
+    }
+
+    /// If the chain head has moved from under us, we can rebase our headers
+    /// on top of the new head and try again
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_rebase_after_head_moved() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_header(header_hash(0))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        // Two writers begin from the same head
+        let mut loser = ChainSequenceBuf::new(arc.clone().into())?;
+        let mut winner = ChainSequenceBuf::new(arc.clone().into())?;
+        loser.put_headers(vec![header_hash(10), header_hash(11)])?;
+        winner.put_header(header_hash(1))?;
+        env.with_commit(|mut writer| winner.flush_to_txn(&mut writer))?;
+
+        let result = env.with_commit(|mut writer| loser.flush_to_txn_ref(&mut writer));
+        assert_matches!(
+            result,
+            Err(SourceChainError::HeadMoved(Some(old), Some(new), Some(1)))
+            if old == header_hash(0) && new == header_hash(1)
+        );
+
+        let loser = loser.rebase()?;
+        assert_eq!(loser.len(), 4);
+        assert_eq!(loser.chain_head(), Some(&header_hash(11)));
+        env.with_commit(|mut writer| loser.flush_to_txn(&mut writer))?;
+
+        let buf = ChainSequenceBuf::new(arc.clone().into())?;
+        let items: Vec<HeaderHash> = buf.iter_from(0).collect()?;
+        assert_eq!(
+            items,
+            vec![
+                header_hash(0),
+                header_hash(1),
+                header_hash(10),
+                header_hash(11)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_rebase_named_store() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+        let chain = &TEST_CHAIN_SEQUENCES[2];
+
+        let mut loser = ChainSequenceBuf::new_named(arc.clone().into(), chain)?;
+        let mut winner = ChainSequenceBuf::new_named(arc.clone().into(), chain)?;
+        loser.put_header(header_hash(10))?;
+        winner.put_header(header_hash(0))?;
+        env.with_commit(|mut writer| winner.flush_to_txn(&mut writer))?;
+
+        let loser = loser.rebase()?;
+        assert_eq!(loser.len(), 2);
+        env.with_commit(|mut writer| loser.flush_to_txn(&mut writer))?;
+
+        // the same happens when flush_to_txn_with rebases
+        let mut loser = ChainSequenceBuf::new_named(arc.clone().into(), chain)?;
+        let mut winner = ChainSequenceBuf::new_named(arc.clone().into(), chain)?;
+        loser.put_header(header_hash(11))?;
+        winner.put_header(header_hash(1))?;
+        env.with_commit(|mut writer| winner.flush_to_txn(&mut writer))?;
+        env.with_commit(|mut writer| {
+            loser.flush_to_txn_with(&mut writer, |_, _| ConflictResolution::Rebase)
+        })?;
+
+        let buf = ChainSequenceBuf::new_named(arc.clone().into(), chain)?;
+        let items: Vec<HeaderHash> = buf.iter_from(0).collect()?;
+        assert_eq!(
+            items,
+            vec![
+                header_hash(0),
+                header_hash(10),
+                header_hash(1),
+                header_hash(11)
+            ]
+        );
+        // nothing was rebased onto the default chain
+        assert!(ChainSequenceBuf::new(arc.clone().into())?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_flush_with_conflict_resolution() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            buf.put_header(header_hash(0))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        // Without a conflict the callback isn't consulted
+        let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+        buf.put_header(header_hash(1))?;
+        env.with_commit(|mut writer| {
+            buf.flush_to_txn_with(&mut writer, |_, _| unreachable!("no conflict"))
+        })?;
+
+        // Aborting fails the flush just like flush_to_txn
+        let mut loser = ChainSequenceBuf::new(arc.clone().into())?;
+        let mut winner = ChainSequenceBuf::new(arc.clone().into())?;
+        loser.put_header(header_hash(10))?;
+        winner.put_header(header_hash(2))?;
+        env.with_commit(|mut writer| winner.flush_to_txn_ref(&mut writer))?;
+
+        let mut heads = None;
+        let result = env.with_commit(|mut writer| {
+            loser.flush_to_txn_with(&mut writer, |ours, theirs| {
+                heads = Some((ours.cloned(), theirs.cloned()));
+                ConflictResolution::Abort
+            })
+        });
+        assert_matches!(
+            result,
+            Err(SourceChainError::HeadMoved(Some(old), Some(new), Some(1)))
+            if old == header_hash(1) && new == header_hash(2)
+        );
+        assert_eq!(heads, Some((Some(header_hash(1)), Some(header_hash(2)))));
+
+        // Rebasing appends our headers after the competing writer's
+        let mut loser = ChainSequenceBuf::new(arc.clone().into())?;
+        loser.put_headers(vec![header_hash(10), header_hash(11)])?;
+        winner.put_header(header_hash(3))?;
+        env.with_commit(|mut writer| winner.flush_to_txn_ref(&mut writer))?;
+
+        env.with_commit(|mut writer| {
+            loser.flush_to_txn_with(&mut writer, |_, _| ConflictResolution::Rebase)
+        })?;
+
+        let buf = ChainSequenceBuf::new(arc.clone().into())?;
+        let items: Vec<HeaderHash> = buf.iter_from(0).collect()?;
+        assert_eq!(
+            items,
+            vec![
+                header_hash(0),
+                header_hash(1),
+                header_hash(2),
+                header_hash(3),
+                header_hash(10),
+                header_hash(11)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_head_moved_counter() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+        let counter = Arc::new(AtomicU64::new(0));
+
+        // Successful and clean flushes are not counted
+        let mut buf =
+            ChainSequenceBuf::new(arc.clone().into())?.with_head_moved_counter(counter.clone());
+        env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+        buf.put_header(header_hash(0))?;
+        env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+        assert_eq!(counter.load(Ordering::Relaxed), 0);
+
+        // Nor are other errors in the flush's transaction
+        buf.put_header(header_hash(1))?;
+        let result = env.with_commit(|mut writer| {
+            buf.flush_to_txn_ref(&mut writer)?;
+            Err::<(), _>(SourceChainError::ScratchNotFresh)
+        });
+        assert_matches!(result, Err(SourceChainError::ScratchNotFresh));
+        assert_eq!(counter.load(Ordering::Relaxed), 0);
+
+        // A failed as-at check is counted, by the rebased buffer too
+        let mut loser =
+            ChainSequenceBuf::new(arc.clone().into())?.with_head_moved_counter(counter.clone());
+        let mut winner = ChainSequenceBuf::new(arc.clone().into())?;
+        loser.put_header(header_hash(10))?;
+        winner.put_header(header_hash(1))?;
+        env.with_commit(|mut writer| winner.flush_to_txn_ref(&mut writer))?;
+
+        let result = env.with_commit(|mut writer| loser.flush_to_txn_ref(&mut writer));
+        assert_matches!(result, Err(SourceChainError::HeadMoved(_, _, _)));
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+        let mut loser = loser.rebase()?;
+        winner.put_header(header_hash(2))?;
+        env.with_commit(|mut writer| winner.flush_to_txn_ref(&mut writer))?;
+        let result = env.with_commit(|mut writer| loser.flush_to_txn_ref(&mut writer));
+        assert_matches!(result, Err(SourceChainError::HeadMoved(_, _, _)));
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_head_listener() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+
+        let mut buf = ChainSequenceBuf::new(arc.clone().into())?.with_head_listener(tx);
+        buf.put_headers((0..2).map(header_hash))?;
+        env.with_commit(|mut writer| buf.flush_to_txn_ref(&mut writer))?;
+        buf.announce_head()?;
+        assert_eq!(rx.recv().await, Some((1, header_hash(1))));
+
+        // A flush which is rolled back is not announced
+        buf.put_header(header_hash(2))?;
+        let result = env.with_commit(|mut writer| {
+            buf.flush_to_txn_ref(&mut writer)?;
+            Err::<(), _>(SourceChainError::ScratchNotFresh)
+        });
+        assert_matches!(result, Err(SourceChainError::ScratchNotFresh));
+        buf.announce_head()?;
+
+        drop(buf);
+        assert_eq!(rx.recv().await, None);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_head_listener_after_rebase() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+
+        let mut loser = ChainSequenceBuf::new(arc.clone().into())?.with_head_listener(tx);
+        let mut winner = ChainSequenceBuf::new(arc.clone().into())?;
+        loser.put_headers(vec![header_hash(10), header_hash(11)])?;
+        winner.put_header(header_hash(0))?;
+        env.with_commit(|mut writer| winner.flush_to_txn(&mut writer))?;
+
+        env.with_commit(|mut writer| {
+            loser.flush_to_txn_with(&mut writer, |_, _| ConflictResolution::Rebase)
+        })?;
+        loser.announce_head()?;
+        assert_eq!(rx.recv().await, Some((2, header_hash(11))));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_sequence_validate_contiguous() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_matches!(buf.validate_contiguous(), Ok(()));
+            buf.put_headers((0..3).map(header_hash))?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        {
+            let mut buf = ChainSequenceBuf::new(arc.clone().into())?;
+            assert_matches!(buf.validate_contiguous(), Ok(()));
+
+            // Skip index 3, as a corrupted database might
+            buf.buf.put(
+                4.into(),
+                ChainSequenceItem {
+                    header_address: header_hash(4),
+                    tx_seq: 1,
+                    dht_transforms_complete: false,
+                },
+            )?;
+            env.with_commit(|mut writer| buf.flush_to_txn(&mut writer))?;
+        }
+
+        let buf = ChainSequenceBuf::new(arc.clone().into())?;
+        assert_matches!(
+            buf.validate_contiguous(),
+            Err(SourceChainError::ChainGap {
+                expected: 3,
+                found: 4
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn chain_sequence_in_memory() -> SourceChainResult<()> {
+        let store = MemChainSequenceStore::new();
+        let mut buf = ChainSequenceBuf::new_in_memory(store.fresh());
+        assert_eq!(buf.chain_head(), None);
+        buf.put_headers(vec![header_hash(0), header_hash(1)])?;
+        assert_eq!(buf.chain_head(), Some(&header_hash(1)));
+        buf.flush()?;
+
+        // A new buffer reads the flushed chain back
+        let mut buf = ChainSequenceBuf::new_in_memory(store.fresh());
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.chain_head(), Some(&header_hash(1)));
+        assert_eq!(buf.snapshot().tx_seq(), 1);
+
+        // Truncating unflushed headers leaves the persisted chain alone
+        buf.put_header(header_hash(2))?;
+        buf.truncate_to(2)?;
+        assert!(!buf.chain_moved_in_this_transaction());
+
+        // The as-at check fails if a competing writer moved the head first
+        let mut theirs = ChainSequenceBuf::new_in_memory(store.fresh());
+        theirs.put_header(header_hash(3))?;
+        buf.put_header(header_hash(4))?;
+        theirs.flush()?;
+        let (old, new) = (header_hash(1), header_hash(3));
+        assert_matches!(
+            buf.flush(),
+            Err(SourceChainError::HeadMoved(Some(o), Some(n), Some(1))) if o == old && n == new
+        );
+        assert_eq!(
+            ChainSequenceBuf::new_in_memory(store.fresh()).chain_head(),
+            Some(&header_hash(3))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn chain_sequence_put_header_checked() -> SourceChainResult<()> {
+        let mut buf = ChainSequenceBuf::new_in_memory(MemChainSequenceStore::new());
+        let hashed = HeaderHash::from_raw_32(vec![1; 32]);
+        buf.put_header_checked(hashed.clone())?;
+        assert_eq!(buf.chain_head(), Some(&hashed));
+
+        // a raw address whose location bytes weren't computed from its hash
+        let mut raw = vec![1; 32];
+        raw.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        let raw = HeaderHash::from_raw_36(raw);
+        assert_matches!(
+            buf.put_header_checked(raw.clone()),
+            Err(SourceChainError::MalformedHeaderAddress(h)) if h == raw
+        );
+        assert_eq!(buf.len(), 1);
+
+        // the unchecked path still takes it, as the tests here rely on
+        buf.put_header(raw.clone())?;
+        assert_eq!(buf.chain_head(), Some(&raw));
+
+        Ok(())
+    }
+
+    #[test]
+    fn chain_sequence_persisted_head() -> SourceChainResult<()> {
+        let mut buf = ChainSequenceBuf::new_in_memory(MemChainSequenceStore::new());
+        assert_eq!(buf.persisted_head(), None);
+
+        buf.put_headers(vec![header_hash(0), header_hash(1)])?;
+        assert_eq!(buf.chain_head(), Some(&header_hash(1)));
+        assert_eq!(buf.persisted_head(), None);
+        buf.flush()?;
+        assert_eq!(buf.persisted_head(), Some(&header_hash(1)));
+
+        buf.put_header(header_hash(2))?;
+        assert_eq!(buf.chain_head(), Some(&header_hash(2)));
+        assert_eq!(buf.persisted_head(), Some(&header_hash(1)));
+        buf.flush()?;
+        assert_eq!(buf.chain_head(), buf.persisted_head());
+        assert_eq!(buf.persisted_head(), Some(&header_hash(2)));
+
+        Ok(())
+    }
 }
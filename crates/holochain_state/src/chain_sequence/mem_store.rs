@@ -0,0 +1,97 @@
+//! An in-memory store for testing a ChainSequenceBuf.
+
+use super::ChainSequenceItem;
+use super::ChainSequenceStore;
+use holo_hash::HeaderHash;
+use holochain_lmdb::error::DatabaseResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// An in-memory ChainSequenceStore, for testing ChainSequenceBuf logic without a temp dir.
+/// Stores made with [MemChainSequenceStore::fresh] share the persisted chain but not the
+/// scratch space, like two buffers on the same environment, so competing writers can be
+/// simulated too.
+#[derive(Debug, Default)]
+pub struct MemChainSequenceStore {
+    persisted: Arc<Mutex<HashMap<u32, ChainSequenceItem>>>,
+    /// None marks a delete.
+    scratch: HashMap<u32, Option<ChainSequenceItem>>,
+}
+
+impl MemChainSequenceStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store with an empty scratch space over the same persisted chain.
+    pub fn fresh(&self) -> Self {
+        Self {
+            persisted: self.persisted.clone(),
+            scratch: HashMap::new(),
+        }
+    }
+
+    /// The (next index, next tx_seq, head) of the persisted chain.
+    pub(super) fn persisted_head_info(&self) -> (u32, u32, Option<HeaderHash>) {
+        let persisted = self
+            .persisted
+            .lock()
+            .expect("chain sequence store poisoned");
+        persisted
+            .iter()
+            .max_by_key(|(i, _)| **i)
+            .map(|(i, item)| (i + 1, item.tx_seq + 1, Some(item.header_address.clone())))
+            .unwrap_or((0, 0, None))
+    }
+
+    /// Apply the scratch space to the persisted chain.
+    pub(super) fn flush_scratch(&mut self) {
+        let mut persisted = self
+            .persisted
+            .lock()
+            .expect("chain sequence store poisoned");
+        for (i, item) in self.scratch.drain() {
+            match item {
+                Some(item) => persisted.insert(i, item),
+                None => persisted.remove(&i),
+            };
+        }
+    }
+}
+
+impl ChainSequenceStore for MemChainSequenceStore {
+    fn get_item(&self, i: u32) -> DatabaseResult<Option<ChainSequenceItem>> {
+        Ok(match self.scratch.get(&i) {
+            Some(item) => item.clone(),
+            None => self
+                .persisted
+                .lock()
+                .expect("chain sequence store poisoned")
+                .get(&i)
+                .cloned(),
+        })
+    }
+
+    fn put_item(&mut self, i: u32, item: ChainSequenceItem) -> DatabaseResult<()> {
+        self.scratch.insert(i, Some(item));
+        Ok(())
+    }
+
+    fn delete_item(&mut self, i: u32) -> DatabaseResult<()> {
+        self.scratch.insert(i, None);
+        Ok(())
+    }
+
+    fn cancel_delete_item(&mut self, i: u32) -> DatabaseResult<()> {
+        if let Some(None) = self.scratch.get(&i) {
+            self.scratch.remove(&i);
+        }
+        Ok(())
+    }
+
+    fn is_scratch_clean(&self) -> bool {
+        self.scratch.is_empty()
+    }
+}
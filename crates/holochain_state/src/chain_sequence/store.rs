@@ -0,0 +1,49 @@
+//! The stores a ChainSequenceBuf can be backed by.
+
+use super::ChainSequenceItem;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvIntBufFresh;
+use holochain_lmdb::error::DatabaseResult;
+
+/// The store behind a ChainSequenceBuf: the persisted chain sequence, overlaid with a scratch
+/// space holding the changes made in the buffer's transaction.  The chain logic all lives in
+/// ChainSequenceBuf, so that it can be tested against a MemChainSequenceStore without an LMDB
+/// environment.  The LMDB store is the default.
+pub trait ChainSequenceStore {
+    /// Get the item at a chain index, checking the scratch space before the persisted chain.
+    fn get_item(&self, i: u32) -> DatabaseResult<Option<ChainSequenceItem>>;
+
+    /// Put an item at a chain index in the scratch space.
+    fn put_item(&mut self, i: u32, item: ChainSequenceItem) -> DatabaseResult<()>;
+
+    /// Delete the item at a chain index in the scratch space.
+    fn delete_item(&mut self, i: u32) -> DatabaseResult<()>;
+
+    /// Drop a pending delete of a chain index from the scratch space, if there is one.
+    fn cancel_delete_item(&mut self, i: u32) -> DatabaseResult<()>;
+
+    /// Whether the scratch space holds no changes.
+    fn is_scratch_clean(&self) -> bool;
+}
+
+impl ChainSequenceStore for KvIntBufFresh<ChainSequenceItem> {
+    fn get_item(&self, i: u32) -> DatabaseResult<Option<ChainSequenceItem>> {
+        self.get(&i.into())
+    }
+
+    fn put_item(&mut self, i: u32, item: ChainSequenceItem) -> DatabaseResult<()> {
+        self.put(i.into(), item)
+    }
+
+    fn delete_item(&mut self, i: u32) -> DatabaseResult<()> {
+        self.delete(i.into())
+    }
+
+    fn cancel_delete_item(&mut self, i: u32) -> DatabaseResult<()> {
+        self.cancel_delete(i.into())
+    }
+
+    fn is_scratch_clean(&self) -> bool {
+        self.is_clean()
+    }
+}
@@ -107,7 +107,67 @@ impl SourceChain {
         // if we are here then the caller is not the current agent so we need to search the source
         // chain to see if there is a local grant that is valid for the provided secret/agent
         // combination
-        let committed_valid_grant = fresh_reader!(self.env(), |r| {
+        let committed_valid_grant = self
+            .live_cap_grants()?
+            .into_iter()
+            .map(CapGrant::RemoteAgent)
+            // filter down to only the grants for this function
+            .filter(|grant| grant.is_valid(check_function, check_agent, check_secret))
+            // if there are still multiple grants, fold them down based on specificity
+            // authorship > assigned > transferable > unrestricted
+            .fold(None, |mut acc, grant| {
+                acc = match &grant {
+                    CapGrant::RemoteAgent(zome_call_cap_grant) => {
+                        match &zome_call_cap_grant.access {
+                            CapAccess::Assigned { .. } => match &acc {
+                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
+                                    match acc_zome_call_cap_grant.access {
+                                        // an assigned acc takes precedence
+                                        CapAccess::Assigned { .. } => acc,
+                                        // current grant takes precedence over all other accs
+                                        _ => Some(grant),
+                                    }
+                                }
+                                None => Some(grant),
+                                // authorship should be short circuit and filtered
+                                _ => unreachable!(),
+                            },
+                            CapAccess::Transferable { .. } => match &acc {
+                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
+                                    match acc_zome_call_cap_grant.access {
+                                        // an assigned acc takes precedence
+                                        CapAccess::Assigned { .. } => acc,
+                                        // transferable acc takes precedence
+                                        CapAccess::Transferable { .. } => acc,
+                                        // current grant takes preference over other accs
+                                        _ => Some(grant),
+                                    }
+                                }
+                                None => Some(grant),
+                                // authorship should be short circuited and filtered by now
+                                _ => unreachable!(),
+                            },
+                            CapAccess::Unrestricted => match acc {
+                                Some(_) => acc,
+                                None => Some(grant),
+                            },
+                        }
+                    }
+                    // ChainAuthor should have short circuited and be filtered out already
+                    _ => unreachable!(),
+                };
+                acc
+            });
+        Ok(committed_valid_grant)
+    }
+
+    /// List the CapGrants committed to this source chain which have not been
+    /// revoked, i.e. neither deleted nor replaced by an update.
+    ///
+    /// NB: [B-01676] as with `valid_cap_grant` the entries must be persisted
+    /// to be listed, so grants created earlier in the same call are not included.
+    pub fn live_cap_grants(&self) -> SourceChainResult<Vec<CapGrantEntry>> {
+        let live_grants = fresh_reader!(self.env(), |r| {
             let (references, headers): (
                 HashSet<HeaderHash>,
                 Vec<HoloHashed<holochain_zome_types::element::SignedHeader>>,
@@ -182,58 +242,13 @@ impl SourceChain {
             .filter(|entry| {
                 Ok(live_cap_grants.contains(entry.as_hash()))
             })
-            .filter_map(|entry| Ok(entry.as_cap_grant()))
-            // filter down to only the grants for this function
-            .filter(|grant| {
-                Ok(grant.is_valid(check_function, check_agent, check_secret))
-            })
-            // if there are still multiple grants, fold them down based on specificity
-            // authorship > assigned > transferable > unrestricted
-            .fold(None, |mut acc, grant| {
-                acc = match &grant {
-                    CapGrant::RemoteAgent(zome_call_cap_grant) => {
-                        match &zome_call_cap_grant.access {
-                            CapAccess::Assigned { .. } => match &acc {
-                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
-                                    match acc_zome_call_cap_grant.access {
-                                        // an assigned acc takes precedence
-                                        CapAccess::Assigned { .. } => acc,
-                                        // current grant takes precedence over all other accs
-                                        _ => Some(grant),
-                                    }
-                                }
-                                None => Some(grant),
-                                // authorship should be short circuit and filtered
-                                _ => unreachable!(),
-                            },
-                            CapAccess::Transferable { .. } => match &acc {
-                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
-                                    match acc_zome_call_cap_grant.access {
-                                        // an assigned acc takes precedence
-                                        CapAccess::Assigned { .. } => acc,
-                                        // transferable acc takes precedence
-                                        CapAccess::Transferable { .. } => acc,
-                                        // current grant takes preference over other accs
-                                        _ => Some(grant),
-                                    }
-                                }
-                                None => Some(grant),
-                                // authorship should be short circuited and filtered by now
-                                _ => unreachable!(),
-                            }
-                            CapAccess::Unrestricted => match acc {
-                                Some(_) => acc,
-                                None => Some(grant),
-                            }
-                        }
-                    },
-                    // ChainAuthor should have short circuited and be filtered out already
-                    _ => unreachable!(),
-                };
-                Ok(acc)
-            })
+            .filter_map(|entry| Ok(match entry.as_cap_grant() {
+                Some(CapGrant::RemoteAgent(grant)) => Some(grant),
+                _ => None,
+            }))
+            .collect()
         })?;
-        Ok(committed_valid_grant)
+        Ok(live_grants)
     }
 
     // @todo bring all this back when we want to administer cap claims better
@@ -298,6 +313,26 @@ impl SourceChain {
             })
             .collect()
     }
+
+    /// Page through the headers of this source chain in chain order, as
+    /// (chain index, header hash) pairs, starting at `start_index` and returning
+    /// at most `limit` of them. Like `query` this includes headers added in this
+    /// call but not yet flushed, and is intended for the `chain_activity` host
+    /// function, so a zome can walk a long chain without returning it all at once.
+    pub fn chain_activity(
+        &self,
+        start_index: u32,
+        limit: u32,
+    ) -> SourceChainResult<Vec<(u32, HeaderHash)>> {
+        Ok(self
+            .0
+            .sequence()
+            .iter_from(start_index)
+            .take(limit as usize)
+            .enumerate()
+            .map(|(i, header_hash)| Ok((start_index + i as u32, header_hash)))
+            .collect()?)
+    }
 }
 
 impl From<SourceChainBuf> for SourceChain {
@@ -321,6 +356,8 @@ pub mod tests {
     use ::fixt::prelude::*;
     use hdk::prelude::*;
     use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_types::test_utils::fake_agent_pubkey_2;
     use holochain_types::test_utils::fake_dna_hash;
     use holochain_zome_types::capability::CapAccess;
     use holochain_zome_types::capability::ZomeCallCapGrant;
@@ -483,6 +520,51 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_chain_activity() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store
+                .genesis(fake_dna_hash(1), fake_agent_pubkey_1(), None)
+                .await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let mut chain = SourceChain::new(env.clone().into())?;
+        // genesis makes 3 headers, this makes a 4th that isn't flushed yet
+        let (entry, entry_hash) =
+            EntryHashed::from_content_sync(Entry::Agent(fake_agent_pubkey_2())).into_inner();
+        let header = chain
+            .put(
+                builder::Create {
+                    entry_type: EntryType::AgentPubKey,
+                    entry_hash,
+                },
+                Some(entry),
+            )
+            .await?;
+
+        let all = chain.chain_activity(0, u32::MAX)?;
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            all.iter().map(|(i, _)| *i).collect::<Vec<_>>()
+        );
+        assert_eq!(header, all[3].1);
+        assert_eq!(chain.chain_head()?, &all[3].1);
+
+        // pages line up with the whole chain
+        assert_eq!(all[..2].to_vec(), chain.chain_activity(0, 2)?);
+        assert_eq!(all[2..].to_vec(), chain.chain_activity(2, 2)?);
+        assert_eq!(all[3..].to_vec(), chain.chain_activity(3, 10)?);
+        assert!(chain.chain_activity(4, 10)?.is_empty());
+        assert!(chain.chain_activity(0, 0)?.is_empty());
+
+        Ok(())
+    }
+
     // @todo bring all this back when we want to administer cap claims better
     // #[tokio::test(flavor = "multi_thread")]
     // async fn test_get_cap_claim() -> SourceChainResult<()> {
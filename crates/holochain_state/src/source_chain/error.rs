@@ -12,9 +12,9 @@ pub enum SourceChainError {
     ChainEmpty,
 
     #[error(
-        "Attempted to commit a bundle to the source chain, but the source chain head has moved since the bundle began. Bundle head: {0:?}, Current head: {1:?}"
+        "Attempted to commit a bundle to the source chain, but the source chain head has moved since the bundle began. Bundle head: {0:?}, Current head: {1:?}, Intervening headers: {2:?}"
     )]
-    HeadMoved(Option<HeaderHash>, Option<HeaderHash>),
+    HeadMoved(Option<HeaderHash>, Option<HeaderHash>, Option<u32>),
 
     #[error(
         "The source chain's structure is invalid. This error is not recoverable. Detail:\n{0}"
@@ -36,6 +36,11 @@ pub enum SourceChainError {
     #[error("SerdeJson Error: {0}")]
     SerdeJsonError(String),
 
+    /// A header address whose DHT location bytes don't match its hash,
+    /// so it can't have been made by hashing a header
+    #[error("The header address {0} is malformed")]
+    MalformedHeaderAddress(HeaderHash),
+
     /// Element signature doesn't validate against the header
     #[error("Element signature is invalid")]
     InvalidSignature,
@@ -59,6 +64,10 @@ pub enum SourceChainError {
     #[error("Required the scratch space to be empty but contained values")]
     ScratchNotFresh,
 
+    /// The persisted chain sequence skips or repeats an index
+    #[error("The chain sequence is not contiguous: expected index {expected} but found {found}")]
+    ChainGap { expected: u32, found: u32 },
+
     /// Element signature doesn't validate against the header
     #[error("Element associated with header {0} was not found on the source chain")]
     ElementMissing(String),
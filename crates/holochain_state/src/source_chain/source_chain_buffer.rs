@@ -69,17 +69,17 @@ impl SourceChainBuf {
     }
 
     pub fn len(&self) -> usize {
-        self.sequence.len()
+        self.sequence.len() as usize
     }
 
     // TODO: TK-01747: Make this check more robust maybe?
     // PERF: This call must be fast
     pub fn has_genesis(&self) -> bool {
-        self.sequence.len() >= POST_GENESIS_SEQ_THRESHOLD as usize
+        self.sequence.len() >= POST_GENESIS_SEQ_THRESHOLD
     }
 
     pub fn get_at_index(&self, i: u32) -> SourceChainResult<Option<Element>> {
-        if let Some(address) = self.sequence.get(i)? {
+        if let Some(address) = self.sequence.get_at_index(i)? {
             self.get_element(&address)
         } else {
             Ok(None)
@@ -131,6 +131,21 @@ impl SourceChainBuf {
         &self.sequence
     }
 
+    /// Register a listener for new chain heads; see ChainSequenceBuf::with_head_listener.
+    pub fn with_head_listener(
+        mut self,
+        head_listener: tokio::sync::mpsc::Sender<(u32, HeaderHash)>,
+    ) -> Self {
+        self.sequence = self.sequence.with_head_listener(head_listener);
+        self
+    }
+
+    /// Announce the chain head written by the last flush to the head listener, if any.
+    /// Call this once the flush's transaction has been committed.
+    pub fn announce_head(&mut self) -> DatabaseResult<()> {
+        self.sequence.announce_head()
+    }
+
     /// Add a Element to the source chain, using a fully-formed Header
     pub async fn put_raw(
         &mut self,
@@ -152,7 +167,7 @@ impl SourceChainBuf {
         }
         */
 
-        self.sequence.put_header(header_address.clone())?;
+        self.sequence.put_header_checked(header_address.clone())?;
         self.elements.put(signed_header, maybe_entry)?;
         Ok(header_address)
     }
@@ -353,7 +368,7 @@ pub mod tests {
             async {
                 let dna_header = Header::Dna(header::Dna {
                     author: agent_pubkey.clone(),
-                    timestamp: Timestamp(0, 0).into(),
+                    timestamp: Timestamp::EPOCH.into(),
                     hash: dna.dna_hash().clone(),
                 });
                 let dna_header = HeaderHashed::from_content_sync(dna_header);
@@ -450,6 +465,32 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn source_chain_buffer_announce_head() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+
+        let (_agent_pubkey, dna_header, dna_entry, agent_header, agent_entry) = fixtures();
+
+        let mut store = SourceChainBuf::new(arc.clone().into())?.with_head_listener(tx);
+        store
+            .put_raw(dna_header.as_content().clone(), dna_entry)
+            .await?;
+        store
+            .put_raw(agent_header.as_content().clone(), agent_entry)
+            .await?;
+        arc.guard()
+            .with_commit(|writer| store.flush_to_txn_ref(writer))?;
+        store.announce_head()?;
+        assert_eq!(
+            rx.recv().await,
+            Some((1, agent_header.as_hash().to_owned()))
+        );
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn source_chain_buffer_dump_entries_json() -> SourceChainResult<()> {
         let test_env = test_cell_env();
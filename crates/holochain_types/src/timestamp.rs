@@ -15,12 +15,10 @@ use std::convert::TryInto;
 /// Supports +/- std::time::Duration directly
 pub use holochain_zome_types::timestamp::*; // Timestamp, TimestampError
 
-/// Returns the current system time as a Timestamp.  We do not make this a holochain_zome_types
-/// timestamp::Timestamp impl now() method, because we need Timestamp to be WASM compatible, and
-/// chrono doesn't have a now() implementation for WASM.  So, use holochain_types timestamp::now()
-/// instead.
+/// Returns the current system time as a Timestamp.  This is a thin wrapper around the host-only
+/// holochain_zome_types Timestamp::now(), kept for existing callers.
 pub fn now() -> Timestamp {
-    Timestamp::from(chrono::offset::Utc::now())
+    Timestamp::now()
 }
 
 const SEC: usize = std::mem::size_of::<i64>();
@@ -112,6 +110,36 @@ mod tests {
         assert_eq!(TEST_TS, &t.to_string());
     }
 
+    #[test]
+    fn test_timestamp_serde_roundtrips() {
+        let t: Timestamp = TEST_TS.try_into().unwrap();
+
+        // JSON is human-readable, so uses the rfc3339 string form
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(format!("\"{}\"", TEST_TS), json);
+        assert_eq!(t, serde_json::from_str::<Timestamp>(&json).unwrap());
+
+        // ... but the tuple form is also accepted
+        assert_eq!(
+            t,
+            serde_json::from_str::<Timestamp>("[1588706164,266431045]").unwrap()
+        );
+
+        // ... and is used for Timestamps not representable as rfc3339
+        let far = Timestamp(i64::MAX, 1);
+        let json = serde_json::to_string(&far).unwrap();
+        assert_eq!("[9223372036854775807,1]", json);
+        assert_eq!(far, serde_json::from_str::<Timestamp>(&json).unwrap());
+
+        // messagepack (on the wire) always uses the compact tuple form
+        let sb = SerializedBytes::try_from(t).unwrap();
+        assert_eq!(&TEST_EN[..], sb.bytes().as_slice());
+        assert_eq!(t, Timestamp::try_from(sb).unwrap());
+
+        let sb = SerializedBytes::try_from(far).unwrap();
+        assert_eq!(far, Timestamp::try_from(sb).unwrap());
+    }
+
     #[test]
     fn test_timestamp_key_roundtrips() {
         // create test timestamps
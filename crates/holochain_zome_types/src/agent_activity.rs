@@ -21,3 +21,19 @@ impl GetAgentActivityInput {
         }
     }
 }
+
+/// A page of the calling cell's own source chain, see `chain_activity`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChainActivityInput {
+    /// The chain index of the first header to return.
+    pub start_index: u32,
+    /// The most headers to return.
+    pub limit: u32,
+}
+
+impl ChainActivityInput {
+    /// Constructor.
+    pub fn new(start_index: u32, limit: u32) -> Self {
+        Self { start_index, limit }
+    }
+}
@@ -3,6 +3,7 @@ use crate::prelude::*;
 use crate::zome::FunctionName;
 use crate::zome::ZomeName;
 use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct CallRemote {
@@ -11,6 +12,7 @@ pub struct CallRemote {
     fn_name: FunctionName,
     cap: Option<CapSecret>,
     payload: ExternIO,
+    timeout_ms: Option<u64>,
 }
 
 impl CallRemote {
@@ -20,6 +22,7 @@ impl CallRemote {
         fn_name: FunctionName,
         cap: Option<CapSecret>,
         payload: ExternIO,
+        timeout_ms: Option<u64>,
     ) -> Self {
         Self {
             target_agent,
@@ -27,6 +30,7 @@ impl CallRemote {
             fn_name,
             cap,
             payload,
+            timeout_ms,
         }
     }
 
@@ -49,4 +53,70 @@ impl CallRemote {
     pub fn payload_as_ref(&self) -> &ExternIO {
         &self.payload
     }
+
+    pub fn timeout_ms(&self) -> Option<u64> {
+        self.timeout_ms
+    }
+}
+
+/// Call the same zome fn on several agents near a basis hash.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CallRemoteMulti {
+    basis: AnyDhtHash,
+    zome_name: ZomeName,
+    fn_name: FunctionName,
+    cap: Option<CapSecret>,
+    payload: ExternIO,
+    remote_agent_count: Option<u8>,
+    timeout_ms: Option<u64>,
+}
+
+impl CallRemoteMulti {
+    pub fn new(
+        basis: AnyDhtHash,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap: Option<CapSecret>,
+        payload: ExternIO,
+        remote_agent_count: Option<u8>,
+        timeout_ms: Option<u64>,
+    ) -> Self {
+        Self {
+            basis,
+            zome_name,
+            fn_name,
+            cap,
+            payload,
+            remote_agent_count,
+            timeout_ms,
+        }
+    }
+
+    pub fn basis_as_ref(&self) -> &AnyDhtHash {
+        &self.basis
+    }
+
+    pub fn zome_name_as_ref(&self) -> &ZomeName {
+        &self.zome_name
+    }
+
+    pub fn fn_name_as_ref(&self) -> &FunctionName {
+        &self.fn_name
+    }
+
+    pub fn cap_as_ref(&self) -> &Option<CapSecret> {
+        &self.cap
+    }
+
+    pub fn payload_as_ref(&self) -> &ExternIO {
+        &self.payload
+    }
+
+    pub fn remote_agent_count(&self) -> Option<u8> {
+        self.remote_agent_count
+    }
+
+    pub fn timeout_ms(&self) -> Option<u64> {
+        self.timeout_ms
+    }
 }
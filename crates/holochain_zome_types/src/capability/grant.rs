@@ -49,7 +49,12 @@ pub struct CurryPayloads(pub BTreeMap<GrantedFunction, SerializedBytes>);
 /// network connection. This must match the strictness of the CapAccess.
 pub struct ZomeCallCapGrant {
     /// A string by which to later query for saved grants.
-    /// This does not need to be unique within a source chain.
+    ///
+    /// An empty tag means the grant is untagged. Any number of untagged grants may be
+    /// active at once, even for the same functions.
+    /// A non-empty tag may be shared by grants for different functions, or by grants for the
+    /// same functions assigned to different agents, but not otherwise by two active grants
+    /// for the same functions, see `collides_with`.
     pub tag: String,
     /// Specifies who may claim this capability, and by what means
     pub access: CapAccess,
@@ -74,6 +79,63 @@ impl ZomeCallCapGrant {
             // @todo curry_payloads,
         }
     }
+
+    /// An untagged grant that lets anyone call the given functions without a secret.
+    pub fn unrestricted(functions: impl IntoIterator<Item = GrantedFunction>) -> Self {
+        Self::new(
+            "".into(),
+            CapAccess::Unrestricted,
+            functions.into_iter().collect(),
+        )
+    }
+
+    /// A grant that only the given agents may use, and only with the secret.
+    /// The secret still has to be shared with the assignees, e.g. as a
+    /// `CapClaim` sent to them with `call_remote`.
+    pub fn assigned(
+        tag: String,
+        secret: CapSecret,
+        assignees: impl IntoIterator<Item = AgentPubKey>,
+        functions: impl IntoIterator<Item = GrantedFunction>,
+    ) -> Self {
+        Self::new(
+            tag,
+            CapAccess::Assigned {
+                secret,
+                assignees: assignees.into_iter().collect(),
+            },
+            functions.into_iter().collect(),
+        )
+    }
+
+    /// The same grant with the given tag.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// Would this grant be ambiguous alongside `other`?
+    /// Tagged grants are ambiguous if they have the same tag and functions, as there would be
+    /// no way to tell which one the tag refers to when administering them.
+    /// The exception is two assigned grants with no assignee in common, as the assignee
+    /// tells them apart, so the same tag can be used to assign the same functions to each
+    /// agent in turn.
+    /// Untagged grants never collide.
+    pub fn collides_with(&self, other: &ZomeCallCapGrant) -> bool {
+        !self.tag.is_empty()
+            && self.tag == other.tag
+            && self.functions == other.functions
+            && match (&self.access, &other.access) {
+                (
+                    CapAccess::Assigned { assignees, .. },
+                    CapAccess::Assigned {
+                        assignees: other_assignees,
+                        ..
+                    },
+                ) => !assignees.is_disjoint(other_assignees),
+                _ => true,
+            }
+    }
 }
 
 impl From<ZomeCallCapGrant> for CapGrant {
@@ -176,3 +238,51 @@ impl From<(CapSecret, AgentPubKey)> for CapAccess {
 pub type GrantedFunction = (ZomeName, FunctionName);
 /// A collection of zome/function pairs
 pub type GrantedFunctions = BTreeSet<GrantedFunction>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tagged_grants_collide() {
+        let function = |name: &str| (ZomeName::from("zome"), FunctionName::from(name));
+        let foo = ZomeCallCapGrant::unrestricted(vec![function("foo")]);
+        let bar = ZomeCallCapGrant::unrestricted(vec![function("bar")]);
+
+        // untagged grants never collide
+        assert!(!foo.collides_with(&foo));
+
+        let tagged_foo = foo.clone().with_tag("tag");
+        assert!(tagged_foo.collides_with(&tagged_foo));
+        assert!(!tagged_foo.collides_with(&foo));
+        assert!(!tagged_foo.collides_with(&foo.clone().with_tag("other")));
+        assert!(!tagged_foo.collides_with(&bar.with_tag("tag")));
+
+        // an assigned grant collides with an unassigned one
+        let secret = CapSecret::from([0; crate::capability::CAP_SECRET_BYTES]);
+        let assigned_foo = |assignees: Vec<AgentPubKey>| {
+            ZomeCallCapGrant::assigned("tag".into(), secret, assignees, vec![function("foo")])
+        };
+        let alice = AgentPubKey::from_raw_36(vec![1; 36]);
+        assert!(tagged_foo.collides_with(&assigned_foo(vec![alice.clone()])));
+        assert!(assigned_foo(vec![alice]).collides_with(&tagged_foo));
+    }
+
+    #[test]
+    fn assigned_grants_collide_by_assignee() {
+        let function = |name: &str| (ZomeName::from("zome"), FunctionName::from(name));
+        let secret = CapSecret::from([0; crate::capability::CAP_SECRET_BYTES]);
+        let assigned_foo = |assignees: Vec<AgentPubKey>| {
+            ZomeCallCapGrant::assigned("tag".into(), secret, assignees, vec![function("foo")])
+        };
+        let alice = AgentPubKey::from_raw_36(vec![1; 36]);
+        let bob = AgentPubKey::from_raw_36(vec![2; 36]);
+
+        // the same tag and functions can be assigned to each agent in turn
+        assert!(!assigned_foo(vec![alice.clone()]).collides_with(&assigned_foo(vec![bob.clone()])));
+
+        // but not to the same agent twice
+        assert!(assigned_foo(vec![alice.clone()]).collides_with(&assigned_foo(vec![alice.clone()])));
+        assert!(assigned_foo(vec![alice, bob.clone()]).collides_with(&assigned_foo(vec![bob])));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::header::ZomeId;
+use crate::timestamp::Timestamp;
 use crate::zome::ZomeName;
 use holo_hash::AgentPubKey;
 use holo_hash::DnaHash;
@@ -45,13 +46,22 @@ pub struct AgentInfo {
     /// Same as the initial pubkey if it has never been changed.
     /// The agent can revoke an old key and replace it with a new one, the latest appears here.
     pub agent_latest_pubkey: AgentPubKey,
+    /// When the host gathered this info.
+    /// Info returned by a remote agent (e.g. via `call_remote`) carries the remote's
+    /// own timestamp, so says how fresh the remote's view of itself was.
+    pub queried_at: Timestamp,
 }
 
 impl AgentInfo {
-    pub fn new(agent_initial_pubkey: AgentPubKey, agent_latest_pubkey: AgentPubKey) -> Self {
+    pub fn new(
+        agent_initial_pubkey: AgentPubKey,
+        agent_latest_pubkey: AgentPubKey,
+        queried_at: Timestamp,
+    ) -> Self {
         Self {
             agent_initial_pubkey,
             agent_latest_pubkey,
+            queried_at,
         }
     }
 }
@@ -2,6 +2,7 @@
 
 #[allow(missing_docs)]
 mod error;
+mod index;
 
 use std::{
     convert::TryFrom,
@@ -13,6 +14,7 @@ use std::{
 use crate::prelude::*;
 
 pub use error::{TimestampError, TimestampResult};
+pub use index::TimestampIndex;
 
 /// A UTC timestamp for use in Holochain's headers.  It is assumed to be untrustworthy: it may
 /// contain times offset from the UNIX epoch with the full +/- i64 range.  Most of these times are
@@ -24,19 +26,22 @@ pub use error::{TimestampError, TimestampResult};
 /// -- it is not acceptable for our core Holochain algorithms to panic when accessing DHT Header
 /// information committed by other random Holochain nodes!
 ///
-/// Timestamp implements `Serialize` and `Display` as rfc3339 time strings (if possible).
+/// Timestamp implements `Display` as rfc3339 time strings (if possible).  It implements `Serialize`
+/// as an rfc3339 time string for human-readable formats (eg. JSON), and as a compact (i64, u32)
+/// tuple for binary formats (eg. the messagepack used on the wire).
 /// - Field 0: i64 - Seconds since UNIX epoch UTC (midnight 1970-01-01).
 /// - Field 1: u32 - Nanoseconds in addition to above seconds, always in positive direction.
 ///
-/// Supports +/- chrono::Duration directly.  There is no Timestamp::now() method, since this is not
-/// supported by WASM; however, holochain_types provides a timestamp::now() method.
+/// Supports +/- chrono::Duration directly.  Timestamp::now() is only available on non-WASM targets,
+/// since WASM cannot supply a wall-clock time; zomes must obtain the time from the host.
 ///
 /// Create a new Timestamp instance from the supplied secs/nsecs.  Note that we can easily create a
 /// Timestamp that cannot be converted to a valid DateTime<Utc> (ie. by supplying 86,400-second days
 /// beyond range of +/- i32 offset from 0AD or 1970AD, nsecs beyond 1e9, etc.; see its code.)
-#[derive(
-    Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, SerializedBytes,
-)]
+///
+/// Equality, ordering and hashing all use the instant in time a Timestamp represents, so a
+/// denormalized Timestamp (nsecs >= 1e9) compares equal to its normalized form.
+#[derive(Clone, Copy, SerializedBytes)]
 pub struct Timestamp(
     pub i64, // seconds from UNIX Epoch, positive or negative
     pub u32, // nanoseconds, always a positive offset
@@ -46,19 +51,107 @@ pub struct Timestamp(
 /// as (seconds, nanoseconds) tuple (output and parsing of large +/- years is unreliable).
 impl fmt::Display for Timestamp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let ce = -62167219200_i64..=253402214400_i64;
-        if ce.contains(&self.0) {
-            if let Ok(ts) = chrono::DateTime::<chrono::Utc>::try_from(self) {
-                return write!(
+        match self.as_rfc3339() {
+            Some(ts) => write!(f, "{}", ts),
+            // Outside 0000-01-01 to 9999-12-31, or not a valid DateTime<Utc>; Display raw value
+            // tuple
+            None => write!(f, "({},{})", self.0, self.1),
+        }
+    }
+}
+
+/// Serialize as an rfc3339 string for human-readable formats, if the Timestamp is representable as
+/// one (see Display); otherwise, and for all binary formats, as a compact (i64, u32) tuple struct.
+impl serde::ser::Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use serde::ser::SerializeTupleStruct;
+        if serializer.is_human_readable() {
+            if let Some(ts) = self.as_rfc3339() {
+                return serializer.serialize_str(&ts);
+            }
+        }
+        let mut state = serializer.serialize_tuple_struct("Timestamp", 2)?;
+        state.serialize_field(&self.0)?;
+        state.serialize_field(&self.1)?;
+        state.end()
+    }
+}
+
+/// Deserialize from either of the representations produced by Serialize.  Human-readable formats
+/// may contain either an rfc3339 string or the (i64, u32) tuple, so accept both.
+impl<'de> serde::de::Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct TimestampVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for TimestampVisitor {
+            type Value = Timestamp;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
                     f,
-                    "{}",
-                    ts.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
-                );
+                    "an rfc3339 time string or a (seconds, nanoseconds) tuple"
+                )
             }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Timestamp::from_str(v).map_err(E::custom)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                use serde::de::Error;
+                let secs = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                let nsecs = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+                Ok(Timestamp(secs, nsecs))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TimestampVisitor)
+        } else {
+            deserializer.deserialize_tuple_struct("Timestamp", 2, TimestampVisitor)
         }
-        // Outside 0000-01-01 to 9999-12-31; Display raw value tuple, or not a valid DateTime<Utc>;
-        // Display raw value tuple
-        write!(f, "({},{})", self.0, self.1)
+    }
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_nanos() == other.as_nanos()
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_nanos().cmp(&other.as_nanos())
+    }
+}
+
+impl std::hash::Hash for Timestamp {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_nanos().hash(state)
     }
 }
 
@@ -89,6 +182,26 @@ impl From<u32> for Timestamp {
     }
 }
 
+/// Convert unsigned Unix seconds (eg. from an external source) into a Timestamp.  There is
+/// deliberately no infallible `From<u64>`: seconds beyond i64::MAX would wrap into the distant past
+/// if cast, so they fail with TimestampError::OutOfRange instead.
+///
+/// ```
+/// use holochain_zome_types::prelude::*;
+/// use std::convert::TryFrom;
+/// assert_eq!( Timestamp::try_from( 1_612_321_506_u64 ), Ok(Timestamp( 1_612_321_506, 0 )));
+/// assert_eq!( Timestamp::try_from( u64::MAX ), Err(TimestampError::OutOfRange));
+/// ```
+impl TryFrom<u64> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(secs: u64) -> Result<Self, Self::Error> {
+        i64::try_from(secs)
+            .map(Timestamp::from)
+            .map_err(|_| TimestampError::OutOfRange)
+    }
+}
+
 impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
     fn from(t: chrono::DateTime<chrono::Utc>) -> Self {
         std::convert::From::from(&t)
@@ -118,12 +231,48 @@ impl TryFrom<&Timestamp> for chrono::DateTime<chrono::Utc> {
     type Error = TimestampError;
 
     fn try_from(t: &Timestamp) -> Result<Self, Self::Error> {
+        // Nanoseconds >= 1e9 are interpreted by chrono as a leap second; carry them into seconds.
+        let t = Timestamp::normalize(t.0, t.1.into()).ok_or(TimestampError::Overflow)?;
         let t = chrono::naive::NaiveDateTime::from_timestamp_opt(t.0, t.1)
-            .ok_or(TimestampError::Overflow)?;
+            .ok_or(TimestampError::OutOfRange)?;
         Ok(chrono::DateTime::from_utc(t, chrono::Utc))
     }
 }
 
+/// Convert a std::time::SystemTime (eg. from filesystem metadata) into a Timestamp.  Times before
+/// the UNIX epoch produce negative seconds.
+impl TryFrom<std::time::SystemTime> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(t: std::time::SystemTime) -> Result<Self, Self::Error> {
+        match t.duration_since(std::time::UNIX_EPOCH) {
+            Ok(since) => Timestamp::EPOCH.checked_add(&since),
+            Err(before) => Timestamp::EPOCH.checked_sub(&before.duration()),
+        }
+        .ok_or(TimestampError::Overflow)
+    }
+}
+
+/// Convert a Timestamp into a std::time::SystemTime; the representable range of a SystemTime is
+/// platform dependent, so this may fail with TimestampError::OutOfRange.
+impl TryFrom<Timestamp> for std::time::SystemTime {
+    type Error = TimestampError;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        let t = Timestamp::normalize(t.0, t.1.into()).ok_or(TimestampError::Overflow)?;
+        let secs = u64::try_from(i128::from(t.0).abs()).map_err(|_| TimestampError::OutOfRange)?;
+        let secs = core::time::Duration::from_secs(secs);
+        let nanos = core::time::Duration::from_nanos(t.1.into());
+        if t.0 >= 0 {
+            std::time::UNIX_EPOCH.checked_add(secs)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(secs)
+        }
+        .and_then(|st| st.checked_add(nanos))
+        .ok_or(TimestampError::OutOfRange)
+    }
+}
+
 impl FromStr for Timestamp {
     type Err = TimestampError;
 
@@ -198,6 +347,108 @@ impl<D: Into<core::time::Duration>> Sub<D> for &Timestamp {
     }
 }
 
+/// A signed offset from a Timestamp, eg. for header fields which store a time relative to another
+/// rather than an absolute time.  Wraps a chrono::Duration, and serializes as i64 milliseconds, so
+/// any sub-millisecond part is dropped when serialized.  Timestamp +/- TimestampOffset are
+/// overflow-checked, like Timestamp +/- core::time::Duration, but the offset may be negative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimestampOffset(pub chrono::Duration);
+
+impl TimestampOffset {
+    /// An offset of the given (possibly negative) number of milliseconds.  Fails with
+    /// TimestampError::OutOfRange for i64::MIN, which a chrono::Duration cannot represent.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( TimestampOffset::from_millis(-1_500).map(|o| o.as_millis()), Ok(-1_500));
+    /// assert_eq!( TimestampOffset::from_millis(i64::MIN), Err(TimestampError::OutOfRange));
+    /// ```
+    pub fn from_millis(millis: i64) -> TimestampResult<TimestampOffset> {
+        if millis == i64::MIN {
+            return Err(TimestampError::OutOfRange);
+        }
+        Ok(TimestampOffset(chrono::Duration::milliseconds(millis)))
+    }
+
+    /// The offset in whole milliseconds, truncated towards zero.  This is what is serialized.
+    pub fn as_millis(&self) -> i64 {
+        self.0.num_milliseconds()
+    }
+}
+
+impl From<chrono::Duration> for TimestampOffset {
+    fn from(d: chrono::Duration) -> Self {
+        TimestampOffset(d)
+    }
+}
+
+impl From<TimestampOffset> for chrono::Duration {
+    fn from(o: TimestampOffset) -> Self {
+        o.0
+    }
+}
+
+impl serde::ser::Serialize for TimestampOffset {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_i64(self.as_millis())
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for TimestampOffset {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let millis = i64::deserialize(deserializer)?;
+        TimestampOffset::from_millis(millis).map_err(D::Error::custom)
+    }
+}
+
+/// Timestamp +/- TimestampOffset.
+/// ```
+/// use holochain_zome_types::prelude::*;
+///
+/// let offset = TimestampOffset::from_millis(-1_500).unwrap();
+/// assert_eq!( Timestamp( 10, 0 ) + offset, Ok(Timestamp( 8, 500_000_000 )));
+/// assert_eq!( Timestamp( 10, 0 ) - offset, Ok(Timestamp( 11, 500_000_000 )));
+/// ```
+impl Add<TimestampOffset> for Timestamp {
+    type Output = TimestampResult<Timestamp>;
+
+    fn add(self, rhs: TimestampOffset) -> Self::Output {
+        self.checked_add_signed(&rhs.0)
+    }
+}
+
+impl Add<TimestampOffset> for &Timestamp {
+    type Output = TimestampResult<Timestamp>;
+
+    fn add(self, rhs: TimestampOffset) -> Self::Output {
+        self.to_owned() + rhs
+    }
+}
+
+impl Sub<TimestampOffset> for Timestamp {
+    type Output = TimestampResult<Timestamp>;
+
+    fn sub(self, rhs: TimestampOffset) -> Self::Output {
+        self.checked_sub_signed(&rhs.0)
+    }
+}
+
+impl Sub<TimestampOffset> for &Timestamp {
+    type Output = TimestampResult<Timestamp>;
+
+    fn sub(self, rhs: TimestampOffset) -> Self::Output {
+        self.to_owned() - rhs
+    }
+}
+
 macro_rules! try_opt {
     ($e:expr) => {
         match $e {
@@ -208,6 +459,152 @@ macro_rules! try_opt {
 }
 
 impl Timestamp {
+    /// The UNIX epoch, 1970-01-01T00:00:00Z.
+    pub const EPOCH: Timestamp = Timestamp(0, 0);
+
+    /// The earliest Timestamp representable as a chrono::DateTime<Utc>: -262144-01-01T00:00:00Z.
+    /// Any earlier Timestamp fails conversion to chrono types with TimestampError::OutOfRange.
+    pub const MIN: Timestamp = Timestamp(-8_334_632_937_600, 0);
+
+    /// The latest Timestamp representable as a chrono::DateTime<Utc>:
+    /// +262143-12-31T23:59:59.999999999Z.  Any later Timestamp fails conversion to chrono types
+    /// with TimestampError::OutOfRange.
+    pub const MAX: Timestamp = Timestamp(8_210_298_412_799, 999_999_999);
+
+    /// Returns the current system time as a Timestamp.  Only available to host-side code; this
+    /// symbol is deliberately absent from WASM builds, so it can never be linked into a zome.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now() -> Timestamp {
+        Timestamp::from(chrono::offset::Utc::now())
+    }
+
+    /// Parse an rfc3339 time string, eg. as produced by `to_rfc3339`.  Any timezone offset is
+    /// converted to UTC.  Same as `Timestamp::from_str`.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    /// assert_eq!(
+    ///     Timestamp::from_rfc3339( "2021-02-03T04:05:06.789+01:00" ),
+    ///     Ok(Timestamp( 1_612_321_506, 789_000_000 ))
+    /// );
+    /// ```
+    pub fn from_rfc3339(t: &str) -> TimestampResult<Timestamp> {
+        Timestamp::from_str(t)
+    }
+
+    /// Parse a time string from an external source in whichever of these formats it matches first:
+    /// rfc3339, rfc2822, or an integer number of seconds since the UNIX epoch.  Surrounding
+    /// whitespace is ignored.  Returns TimestampError::UnrecognizedFormat if none of them match.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    /// let t = Timestamp( 1_612_321_506, 0 );
+    /// assert_eq!( Timestamp::parse_flexible( "2021-02-03T03:05:06Z" ), Ok(t) );
+    /// assert_eq!( Timestamp::parse_flexible( "Wed, 03 Feb 2021 03:05:06 +0000" ), Ok(t) );
+    /// assert_eq!( Timestamp::parse_flexible( " 1612321506\n" ), Ok(t) );
+    /// assert!( Timestamp::parse_flexible( "yesterday" ).is_err() );
+    /// ```
+    pub fn parse_flexible(t: &str) -> TimestampResult<Timestamp> {
+        let t = t.trim();
+        if let Ok(ts) = Timestamp::from_rfc3339(t) {
+            return Ok(ts);
+        }
+        if let Ok(ts) = chrono::DateTime::parse_from_rfc2822(t) {
+            let ts = chrono::DateTime::from_utc(ts.naive_utc(), chrono::Utc);
+            return Ok(ts.into());
+        }
+        t.parse::<i64>()
+            .map(Timestamp::from)
+            .map_err(|_| TimestampError::UnrecognizedFormat(t.to_string()))
+    }
+
+    /// The rfc3339 time string for this Timestamp, in UTC.  Same as `to_string`, so a Timestamp
+    /// outside 0000-9999AD is formatted as a "(secs,nsecs)" tuple instead, which `from_rfc3339`
+    /// will not parse.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    /// let t = Timestamp( 1_612_321_506, 789_000_000 );
+    /// assert_eq!( t.to_rfc3339(), "2021-02-03T03:05:06.789Z" );
+    /// assert_eq!( Timestamp::from_rfc3339( &t.to_rfc3339() ), Ok(t) );
+    /// ```
+    pub fn to_rfc3339(&self) -> String {
+        self.to_string()
+    }
+
+    /// The rfc3339 representation of this Timestamp, if it lies within the sane value range of
+    /// 0000-9999AD and is representable as a DateTime<Utc>.
+    fn as_rfc3339(&self) -> Option<String> {
+        let ce = -62167219200_i64..=253402214400_i64;
+        if !ce.contains(&self.0) {
+            return None;
+        }
+        chrono::DateTime::<chrono::Utc>::try_from(self)
+            .ok()
+            .map(|ts| ts.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+    }
+
+    /// Construct a Timestamp from the given secs/nsecs, carrying any nanoseconds >= 1e9 over into
+    /// the seconds field, so that the result is always normalized.  Returns
+    /// TimestampError::Overflow if the carried seconds exceed the i64 range.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    /// assert_eq!( Timestamp::new( 0, 1_500_000_000 ), Ok(Timestamp( 1, 500_000_000 )))
+    /// ```
+    pub fn new(secs: i64, nsecs: u32) -> TimestampResult<Timestamp> {
+        Timestamp::normalize(secs, nsecs.into()).ok_or(TimestampError::Overflow)
+    }
+
+    /// The number of whole milliseconds since the UNIX epoch, as a single integer (eg. for metrics
+    /// and logging).  Rounds toward negative infinity, so pre-1970 Timestamps with a fractional
+    /// millisecond yield the next lower value.  An i128 can represent every Timestamp, so this
+    /// cannot overflow.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    /// assert_eq!( Timestamp( -1, 999_999_999 ).as_millis(), -1 );
+    /// ```
+    pub fn as_millis(&self) -> i128 {
+        i128::from(self.0) * 1_000 + i128::from(self.1 / 1_000_000)
+    }
+
+    /// The number of whole microseconds since the UNIX epoch; see `as_millis`.
+    pub fn as_micros(&self) -> i128 {
+        i128::from(self.0) * 1_000_000 + i128::from(self.1 / 1_000)
+    }
+
+    /// Construct a Timestamp from a number of milliseconds since the UNIX epoch, as used by
+    /// Javascript's `Date.now()` and many databases; the inverse of `as_millis`.  Negative values
+    /// are pre-1970, and yield a properly normalized Timestamp.  Every i64 of milliseconds is
+    /// representable, so this cannot fail.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    /// assert_eq!( Timestamp::from_millis( -1 ), Timestamp( -1, 999_000_000 ));
+    /// ```
+    pub fn from_millis(millis: i64) -> Timestamp {
+        // rem_euclid is always positive, so the nanoseconds are a positive offset from the seconds
+        Timestamp(
+            millis.div_euclid(1_000),
+            millis.rem_euclid(1_000) as u32 * 1_000_000,
+        )
+    }
+
+    /// Construct a Timestamp from a number of microseconds since the UNIX epoch; see `from_millis`.
+    pub fn from_micros(micros: i64) -> Timestamp {
+        Timestamp(
+            micros.div_euclid(1_000_000),
+            micros.rem_euclid(1_000_000) as u32 * 1_000,
+        )
+    }
+
+    /// The number of nanoseconds since the UNIX epoch; see `as_millis`.  This is the instant used
+    /// to compare Timestamps, and is the same for a Timestamp and its normalized form.
+    pub fn as_nanos(&self) -> i128 {
+        i128::from(self.0) * 1_000_000_000 + i128::from(self.1)
+    }
+
     /// Construct a normalized Timestamp from the given secs/nanos.  Allows a full, signed range of
     /// seconds and/or nanoseconds; produces a Timestamp with a properly signed i64 seconds, and an
     /// always positive-offset u32 nanoseconds.  Differs from typical `new` implementation in that
@@ -251,6 +648,184 @@ impl Timestamp {
         Some(dur)
     }
 
+    /// Compute the signed difference `self - other` as a chrono::Duration, which may be negative if
+    /// `other` is later than `self`.  Useful for validation checks like "header must be within +/- N
+    /// seconds of received time", where either side may be the later one.  Returns
+    /// TimestampError::Overflow if the difference exceeds the +/- i64 milliseconds range of a
+    /// chrono::Duration.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 0, 0 ).signed_diff(&Timestamp( 1, 500_000_000 )),
+    ///             Ok(chrono::Duration::milliseconds(-1_500)));
+    /// ```
+    pub fn signed_diff(&self, other: &Timestamp) -> TimestampResult<chrono::Duration> {
+        self.checked_difference_signed(other)
+            .ok_or(TimestampError::Overflow)
+    }
+
+    /// Compute the unsigned difference `self - other` as a core::time::Duration.  Since a
+    /// core::time::Duration is unsigned, `self` must not be earlier than `other`; if it is,
+    /// TimestampError::Overflow is returned.  Subtracting two Timestamps instead gives a signed
+    /// chrono::Duration, see `signed_diff`.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 1, 500_000_000 ).unsigned_diff(&Timestamp( 0, 0 )),
+    ///             Ok(core::time::Duration::from_millis(1_500)));
+    /// assert_eq!( Timestamp( 0, 0 ).unsigned_diff(&Timestamp( 1, 500_000_000 )),
+    ///             Err(TimestampError::Overflow));
+    /// ```
+    pub fn unsigned_diff(&self, other: &Timestamp) -> TimestampResult<core::time::Duration> {
+        self.checked_difference(other)
+            .ok_or(TimestampError::Overflow)
+    }
+
+    /// Compute the unsigned difference between two Timestamp, returning `None` if `rhs` is later
+    /// than `self`, or Some(core::time::Duration).  The full +/- i64 range of seconds is supported
+    /// by computing the seconds difference in i128 before narrowing into the u64 seconds of the
+    /// core::time::Duration.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 1, 100 ).checked_difference(&Timestamp( 0, 999_999_999 )),
+    ///             Some(core::time::Duration::from_nanos(101)));
+    /// assert_eq!( Timestamp( 0, 999_999_999 ).checked_difference(&Timestamp( 1, 100 )),
+    ///             None);
+    /// ```
+    pub fn checked_difference(&self, rhs: &Timestamp) -> Option<core::time::Duration> {
+        let dif_secs: i128 = i128::from(self.0) - i128::from(rhs.0);
+        let dif_nano: i64 = i64::from(self.1) - i64::from(rhs.1);
+        let dif_secs: i128 = dif_secs + i128::from(dif_nano.div_euclid(1_000_000_000));
+        let dif_nano: u32 = try_opt!(u32::try_from(dif_nano.rem_euclid(1_000_000_000)).ok());
+        let dif_secs: u64 = try_opt!(u64::try_from(dif_secs).ok());
+        Some(core::time::Duration::new(dif_secs, dif_nano))
+    }
+
+    /// The core::time::Duration elapsed from `earlier` to this Timestamp, or `None` if `earlier` is
+    /// later than `self`, with the same signature as std::time::Instant::checked_duration_since.
+    /// This is checked_difference, taking `earlier` by value as Instant does.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 2, 0 ).checked_duration_since(Timestamp( 1, 250_000_000 )),
+    ///             Some(core::time::Duration::from_millis(750)));
+    /// assert_eq!( Timestamp( 1, 250_000_000 ).checked_duration_since(Timestamp( 2, 0 )),
+    ///             None);
+    /// ```
+    pub fn checked_duration_since(&self, earlier: Timestamp) -> Option<core::time::Duration> {
+        self.checked_difference(&earlier)
+    }
+
+    /// How long ago this Timestamp was, relative to the supplied `now`.  The caller provides the
+    /// current time (eg. from `sys_time` in a zome, or `Timestamp::now()` on the host), so this is
+    /// usable in WASM.  Returns TimestampError::InFuture if `self` is later than `now`, as a
+    /// core::time::Duration cannot be negative; use `signed_diff` if either may be the later one.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// let now = Timestamp( 10, 0 );
+    /// assert_eq!( Timestamp( 8, 500_000_000 ).elapsed_since(now),
+    ///             Ok(core::time::Duration::from_millis(1_500)));
+    /// assert_eq!( Timestamp( 11, 0 ).elapsed_since(now),
+    ///             Err(TimestampError::InFuture));
+    /// ```
+    pub fn elapsed_since(&self, now: Timestamp) -> TimestampResult<core::time::Duration> {
+        now.checked_difference(self).ok_or(TimestampError::InFuture)
+    }
+
+    /// Whether this Timestamp lies within `tolerance` of `other`, in either direction, eg. for
+    /// validation checks like "header must be within N seconds of received time".  The comparison
+    /// is made in nanoseconds, so sub-second differences and tolerances are exact, and a difference
+    /// of exactly `tolerance` is within it.  Every pair of Timestamps can be compared, so this
+    /// cannot overflow.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// let received = Timestamp( 10, 0 );
+    /// let tolerance = core::time::Duration::from_millis(1_500);
+    /// assert!( Timestamp( 8, 500_000_000 ).is_within(&received, tolerance));
+    /// assert!( !Timestamp( 11, 500_000_001 ).is_within(&received, tolerance));
+    /// ```
+    pub fn is_within(&self, other: &Timestamp, tolerance: core::time::Duration) -> bool {
+        // the difference of any two Timestamps in nanoseconds fits comfortably in an i128
+        let dif = (self.as_nanos() - other.as_nanos()).abs() as u128;
+        dif <= tolerance.as_nanos()
+    }
+
+    /// Describe this Timestamp relative to `relative_to`, eg. "3 minutes ago" or "in 5 minutes", for
+    /// logs and UIs.  The reference time is a parameter rather than the clock, so this is usable in
+    /// WASM.  The difference is truncated to whole units of the largest unit that fits, from
+    /// seconds up to years (of 365 days; months are 30 days), and anything under a second is "just
+    /// now".
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// let now = Timestamp( 1_000, 0 );
+    /// assert_eq!( Timestamp( 820, 0 ).humanize(now), "3 minutes ago" );
+    /// assert_eq!( Timestamp( 1_300, 0 ).humanize(now), "in 5 minutes" );
+    /// ```
+    pub fn humanize(&self, relative_to: Timestamp) -> String {
+        const UNITS: [(&str, u128); 7] = [
+            ("year", 365 * 24 * 60 * 60),
+            ("month", 30 * 24 * 60 * 60),
+            ("week", 7 * 24 * 60 * 60),
+            ("day", 24 * 60 * 60),
+            ("hour", 60 * 60),
+            ("minute", 60),
+            ("second", 1),
+        ];
+        // the difference of any two Timestamps in nanoseconds fits comfortably in an i128
+        let dif = self.as_nanos() - relative_to.as_nanos();
+        let secs = dif.abs() as u128 / 1_000_000_000;
+        let (unit, count) = match UNITS.iter().find(|(_, unit_secs)| secs >= *unit_secs) {
+            Some((unit, unit_secs)) => (unit, secs / unit_secs),
+            None => return "just now".to_string(),
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        if dif < 0 {
+            format!("{} {}{} ago", count, unit, plural)
+        } else {
+            format!("in {} {}{}", count, unit, plural)
+        }
+    }
+
+    /// Floor this Timestamp to a multiple of `granularity` since the UNIX epoch, eg. to bucket
+    /// events into fixed time windows.  Sub-second granularities use the nanoseconds field, and
+    /// pre-1970 Timestamps round toward negative infinity, into the window that contains them.
+    /// Returns TimestampError::ZeroGranularity for a zero `granularity`, or
+    /// TimestampError::Overflow if the floor lies before the i64 seconds range.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 1_234, 567_000_000 ).round_to(core::time::Duration::from_secs(10)),
+    ///             Ok(Timestamp( 1_230, 0 )));
+    /// assert_eq!( Timestamp( 1_234, 567_000_000 ).round_to(core::time::Duration::from_millis(250)),
+    ///             Ok(Timestamp( 1_234, 500_000_000 )));
+    /// ```
+    pub fn round_to(&self, granularity: core::time::Duration) -> TimestampResult<Timestamp> {
+        // a u64 of seconds in nanoseconds fits comfortably in an i128
+        let granularity = granularity.as_nanos() as i128;
+        if granularity == 0 {
+            return Err(TimestampError::ZeroGranularity);
+        }
+        let nanos = self.as_nanos();
+        let floor = nanos - nanos.rem_euclid(granularity);
+        let secs =
+            i64::try_from(floor.div_euclid(1_000_000_000)).map_err(|_| TimestampError::Overflow)?;
+        // always in range: (0,999_999_999)
+        let nsecs = floor.rem_euclid(1_000_000_000) as u32;
+        Ok(Timestamp(secs, nsecs))
+    }
+
     /// Add a signed chrono::Duration{ secs: i64, nanos: i32 } (-'ve nanos are invalid) to a
     /// Timestamp( i64, u32 ).  May overflow.  Unfortunately, there is *no way* in the provided API
     /// to actually obtain the raw { secs, nanos }, nor their component parts without overflow!  The
@@ -261,24 +836,36 @@ impl Timestamp {
     ///
     /// assert_eq!( Timestamp::normalize( 0, 1 ).unwrap()
     ///                 .checked_sub_signed(&chrono::Duration::nanoseconds(2)),
-    ///             Some(Timestamp( -1, 999_999_999 )));
-    /// //assert_eq!((Timestamp::normalize( 0, 1 ).unwrap()
-    /// //            - chrono::Duration::nanoseconds(2)),
-    /// //            Some(Timestamp( -1, 999_999_999 )));
+    ///             Ok(Timestamp( -1, 999_999_999 )));
+    /// assert_eq!( Timestamp::normalize( 0, 1 ).unwrap()
+    ///                 .checked_add_signed(&chrono::Duration::nanoseconds(-2)),
+    ///             Ok(Timestamp( -1, 999_999_999 )));
     /// ```
-    pub fn checked_add_signed(&self, rhs: &chrono::Duration) -> Option<Timestamp> {
+    ///
+    /// Accepts negative durations directly, so callers needn't branch on the sign of an offset to
+    /// choose between `+` and `-`.  Overflow is reported as TimestampError::Overflow.
+    pub fn checked_add_signed(&self, rhs: &chrono::Duration) -> TimestampResult<Timestamp> {
         let dur_millis: i64 = rhs.num_milliseconds();
-        let rhs_remains = try_opt!(rhs.checked_sub(&chrono::Duration::milliseconds(dur_millis)));
-        let dur_nanos: i64 =
-            try_opt!(rhs_remains.num_nanoseconds()) + (dur_millis % 1_000) * 1_000_000;
+        let rhs_remains = rhs
+            .checked_sub(&chrono::Duration::milliseconds(dur_millis))
+            .ok_or(TimestampError::Overflow)?;
+        let dur_nanos: i64 = rhs_remains
+            .num_nanoseconds()
+            .ok_or(TimestampError::Overflow)?
+            + (dur_millis % 1_000) * 1_000_000;
         let dur_seconds: i64 = dur_millis / 1_000;
-        let seconds: i64 = try_opt!(self.0.checked_add(dur_seconds));
-        let nanos: i64 = try_opt!(i64::from(self.1).checked_add(dur_nanos));
-        Some(try_opt!(Timestamp::normalize(seconds, nanos)))
+        let seconds: i64 = self
+            .0
+            .checked_add(dur_seconds)
+            .ok_or(TimestampError::Overflow)?;
+        let nanos: i64 = i64::from(self.1)
+            .checked_add(dur_nanos)
+            .ok_or(TimestampError::Overflow)?;
+        Timestamp::normalize(seconds, nanos).ok_or(TimestampError::Overflow)
     }
 
-    /// Subtracts a chrono::Duration from a Timestamp
-    pub fn checked_sub_signed(&self, rhs: &chrono::Duration) -> Option<Timestamp> {
+    /// Subtracts a (possibly negative) chrono::Duration from a Timestamp.
+    pub fn checked_sub_signed(&self, rhs: &chrono::Duration) -> TimestampResult<Timestamp> {
         self.checked_add_signed(&-*rhs)
     }
 
@@ -329,18 +916,127 @@ impl Timestamp {
         let nanos: i64 = try_opt!(i64::from(self.1).checked_sub(dur_nanos));
         Some(try_opt!(Timestamp::normalize(seconds, nanos)))
     }
+
+    /// The `+=` of Timestamp + Into<core::time::Duration>, which can't implement AddAssign because
+    /// the addition may overflow.  On success the Timestamp is advanced in place; on
+    /// TimestampError::Overflow it is left unchanged, eg. for advancing a schedule in a loop.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// let mut t = Timestamp( 1, 0 );
+    /// assert_eq!( t.add_assign_checked(core::time::Duration::from_millis(1_500)), Ok(()));
+    /// assert_eq!( t, Timestamp( 2, 500_000_000 ));
+    /// ```
+    pub fn add_assign_checked<D: Into<core::time::Duration>>(
+        &mut self,
+        rhs: D,
+    ) -> TimestampResult<()> {
+        *self = (*self + rhs)?;
+        Ok(())
+    }
+
+    /// The `-=` of Timestamp - Into<core::time::Duration>.  As for add_assign_checked, the
+    /// Timestamp is only changed on success.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// let mut t = Timestamp( 1, 0 );
+    /// assert_eq!( t.sub_assign_checked(core::time::Duration::from_millis(1_500)), Ok(()));
+    /// assert_eq!( t, Timestamp( -1, 500_000_000 ));
+    /// ```
+    pub fn sub_assign_checked<D: Into<core::time::Duration>>(
+        &mut self,
+        rhs: D,
+    ) -> TimestampResult<()> {
+        *self = (*self - rhs)?;
+        Ok(())
+    }
+
+    /// Add a Duration, clamping the result to the range of Timestamps representable as a
+    /// chrono::DateTime<Utc> (-262144-01-01T00:00:00Z to +262143-12-31T23:59:59.999999999Z)
+    /// instead of failing with an Overflow.  Useful for backoff and rate-limiting deadlines.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 1, 0 ).saturating_add(core::time::Duration::from_secs(1)),
+    ///             Timestamp( 2, 0 ));
+    /// assert_eq!( Timestamp( 1, 0 ).saturating_add(core::time::Duration::from_secs(u64::MAX)),
+    ///             Timestamp( 8_210_298_412_799, 999_999_999 ));
+    /// ```
+    pub fn saturating_add<D: Into<core::time::Duration>>(self, rhs: D) -> Timestamp {
+        Timestamp::clamp_nanos(self.as_nanos() + Timestamp::duration_nanos(rhs.into()))
+    }
+
+    /// Subtract a Duration, clamping the result to the chrono::DateTime<Utc> representable range;
+    /// see saturating_add.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// assert_eq!( Timestamp( 1, 0 ).saturating_sub(core::time::Duration::from_secs(2)),
+    ///             Timestamp( -1, 0 ));
+    /// assert_eq!( Timestamp( 1, 0 ).saturating_sub(core::time::Duration::from_secs(u64::MAX)),
+    ///             Timestamp( -8_334_632_937_600, 0 ));
+    /// ```
+    pub fn saturating_sub<D: Into<core::time::Duration>>(self, rhs: D) -> Timestamp {
+        Timestamp::clamp_nanos(self.as_nanos() - Timestamp::duration_nanos(rhs.into()))
+    }
+
+    /// A core::time::Duration in nanoseconds; at most u64::MAX seconds, which fits in an i128 with
+    /// room to add or subtract any Timestamp's `as_nanos` without overflow.
+    fn duration_nanos(d: core::time::Duration) -> i128 {
+        i128::from(d.as_secs()) * 1_000_000_000 + i128::from(d.subsec_nanos())
+    }
+
+    /// The Timestamp at `nanos` since the UNIX epoch, clamped to the chrono-representable range.
+    fn clamp_nanos(nanos: i128) -> Timestamp {
+        let nanos = std::cmp::min(
+            std::cmp::max(nanos, Timestamp::MIN.as_nanos()),
+            Timestamp::MAX.as_nanos(),
+        );
+        // within MIN..=MAX, the seconds fit in an i64 and the remainder in a u32
+        Timestamp(
+            nanos.div_euclid(1_000_000_000) as i64,
+            nanos.rem_euclid(1_000_000_000) as u32,
+        )
+    }
 }
 
 /// Distance between two Timestamps as a chrono::Duration (subject to overflow).  A Timestamp
 /// represents a *signed* distance from the UNIX Epoch (1970-01-01T00:00:00Z).  A chrono::Duration
-/// is limited to +/- i64::MIN/MAX milliseconds.
+/// is limited to +/- i64::MIN/MAX milliseconds.  See `unsigned_diff` for a core::time::Duration.
 impl Sub<Timestamp> for Timestamp {
     type Output = TimestampResult<chrono::Duration>;
 
     fn sub(self, rhs: Timestamp) -> Self::Output {
-        Ok(self
-            .checked_difference_signed(&rhs)
-            .ok_or(TimestampError::Overflow)?)
+        self.signed_diff(&rhs)
+    }
+}
+
+impl Sub<&Timestamp> for Timestamp {
+    type Output = TimestampResult<chrono::Duration>;
+
+    fn sub(self, rhs: &Timestamp) -> Self::Output {
+        self - rhs.to_owned()
+    }
+}
+
+impl Sub<Timestamp> for &Timestamp {
+    type Output = TimestampResult<chrono::Duration>;
+
+    fn sub(self, rhs: Timestamp) -> Self::Output {
+        self.to_owned() - rhs
+    }
+}
+
+impl Sub<&Timestamp> for &Timestamp {
+    type Output = TimestampResult<chrono::Duration>;
+
+    fn sub(self, rhs: &Timestamp) -> Self::Output {
+        self.to_owned() - rhs.to_owned()
     }
 }
 
@@ -357,9 +1053,627 @@ pub mod tests {
         // trust code that produces Timestamps, it has no intrinsic range limits.
         let t1 = Timestamp((2_i64.pow(31) + 1) * 86_400, 1_000_000_000); // invalid secs for DateTime
         let d1: TimestampResult<chrono::DateTime<chrono::Utc>> = t1.try_into();
-        assert_eq!(d1, Err(TimestampError::Overflow));
+        assert_eq!(d1, Err(TimestampError::OutOfRange));
 
         let t2 = Timestamp(0, 0) + core::time::Duration::new(0, 1);
         assert_eq!(t2, Ok(Timestamp(0, 1)));
     }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn timestamp_now() {
+        // 2020-01-01T00:00:00Z; any sane host clock is later than this.
+        let t = Timestamp::now();
+        assert!(t > Timestamp(1_577_836_800, 0));
+        assert!(t.1 < 1_000_000_000);
+    }
+
+    #[test]
+    fn timestamp_difference() {
+        // Sub-second differences must borrow correctly from the seconds field.
+        assert_eq!(
+            Timestamp(10, 250_000_000).unsigned_diff(&Timestamp(9, 750_000_000)),
+            Ok(core::time::Duration::from_millis(500))
+        );
+        assert_eq!(
+            Timestamp(10, 750_000_000).unsigned_diff(&Timestamp(9, 250_000_000)),
+            Ok(core::time::Duration::from_millis(1_500))
+        );
+        assert_eq!(
+            Timestamp(-1, 999_999_999).unsigned_diff(&Timestamp(-1, 0)),
+            Ok(core::time::Duration::from_nanos(999_999_999))
+        );
+        assert_eq!(
+            Timestamp(0, 1).unsigned_diff(&Timestamp(0, 1)),
+            Ok(core::time::Duration::from_secs(0))
+        );
+
+        // The full i64 range of seconds is representable as a core::time::Duration
+        assert_eq!(
+            Timestamp(i64::MAX, 0).unsigned_diff(&Timestamp(i64::MIN, 0)),
+            Ok(core::time::Duration::from_secs(u64::MAX))
+        );
+
+        // Left earlier than right cannot be represented by an unsigned Duration
+        assert_eq!(
+            Timestamp(9, 750_000_000).unsigned_diff(&Timestamp(10, 250_000_000)),
+            Err(TimestampError::Overflow)
+        );
+        assert_eq!(
+            Timestamp(0, 0).unsigned_diff(&Timestamp(0, 1)),
+            Err(TimestampError::Overflow)
+        );
+
+        // Subtraction is signed, so either side may be the later one
+        assert_eq!(
+            Timestamp(10, 250_000_000) - Timestamp(9, 750_000_000),
+            Ok(chrono::Duration::milliseconds(500))
+        );
+        assert_eq!(
+            &Timestamp(9, 750_000_000) - &Timestamp(10, 250_000_000),
+            Ok(chrono::Duration::milliseconds(-500))
+        );
+        assert_eq!(
+            Timestamp(i64::MAX, 0) - Timestamp(i64::MIN, 0),
+            Err(TimestampError::Overflow)
+        );
+    }
+
+    #[test]
+    fn timestamp_signed_diff() {
+        let t1 = Timestamp(10, 250_000_000);
+        let t2 = Timestamp(9, 750_000_000);
+        assert_eq!(t1.signed_diff(&t2), Ok(chrono::Duration::milliseconds(500)));
+        assert_eq!(
+            t2.signed_diff(&t1),
+            Ok(chrono::Duration::milliseconds(-500))
+        );
+        assert_eq!(t1.signed_diff(&t1), Ok(chrono::Duration::zero()));
+
+        // Differences beyond +/- i64::MAX milliseconds are not representable by chrono::Duration
+        assert_eq!(
+            Timestamp(i64::MAX, 0).signed_diff(&Timestamp(i64::MIN, 0)),
+            Err(TimestampError::Overflow)
+        );
+        assert_eq!(
+            Timestamp(i64::MAX / 1_000 + 1, 0).signed_diff(&Timestamp(0, 0)),
+            Err(TimestampError::Overflow)
+        );
+    }
+
+    #[test]
+    fn timestamp_new_normalizes() {
+        assert_eq!(
+            Timestamp::new(0, 1_500_000_000),
+            Ok(Timestamp(1, 500_000_000))
+        );
+        assert_eq!(
+            Timestamp::new(-1, 999_999_999),
+            Ok(Timestamp(-1, 999_999_999))
+        );
+        assert_eq!(Timestamp::new(-2, u32::MAX), Ok(Timestamp(2, 294_967_295)));
+        assert_eq!(
+            Timestamp::new(i64::MAX, 1_000_000_000),
+            Err(TimestampError::Overflow)
+        );
+
+        // Denormalized nanoseconds must not be mistaken for a leap second by chrono
+        let d1: chrono::DateTime<chrono::Utc> = Timestamp(0, 1_500_000_000).try_into().unwrap();
+        let d2: chrono::DateTime<chrono::Utc> = Timestamp(1, 500_000_000).try_into().unwrap();
+        assert_eq!(d1, d2);
+        assert_eq!(
+            Timestamp(0, 1_500_000_000).to_string(),
+            "1970-01-01T00:00:01.500Z"
+        );
+    }
+
+    #[test]
+    fn timestamp_signed_offsets() {
+        let t = Timestamp(10, 250_000_000);
+        let d = chrono::Duration::milliseconds(1_500);
+        assert_eq!(t.checked_add_signed(&d), Ok(Timestamp(11, 750_000_000)));
+        assert_eq!(t.checked_sub_signed(&d), Ok(Timestamp(8, 750_000_000)));
+        assert_eq!(t.checked_add_signed(&-d), Ok(Timestamp(8, 750_000_000)));
+        assert_eq!(t.checked_sub_signed(&-d), Ok(Timestamp(11, 750_000_000)));
+
+        // Crossing the epoch backwards
+        assert_eq!(
+            Timestamp(0, 0).checked_add_signed(&chrono::Duration::nanoseconds(-1)),
+            Ok(Timestamp(-1, 999_999_999))
+        );
+
+        assert_eq!(
+            Timestamp(i64::MAX, 0).checked_add_signed(&chrono::Duration::seconds(1)),
+            Err(TimestampError::Overflow)
+        );
+        assert_eq!(
+            Timestamp(i64::MIN, 0).checked_sub_signed(&chrono::Duration::seconds(1)),
+            Err(TimestampError::Overflow)
+        );
+    }
+
+    #[test]
+    fn timestamp_as_millis_micros() {
+        assert_eq!(Timestamp(0, 0).as_millis(), 0);
+        assert_eq!(Timestamp(0, 0).as_micros(), 0);
+        assert_eq!(Timestamp(0, 999_999).as_millis(), 0);
+        assert_eq!(Timestamp(0, 1_000_000).as_millis(), 1);
+        assert_eq!(Timestamp(0, 999).as_micros(), 0);
+        assert_eq!(Timestamp(0, 1_000).as_micros(), 1);
+        assert_eq!(Timestamp(1, 500_000_000).as_millis(), 1_500);
+        assert_eq!(Timestamp(1, 500_000_000).as_micros(), 1_500_000);
+
+        // Pre-1970; the nanoseconds are always a positive offset from the (negative) seconds
+        assert_eq!(Timestamp(-1, 0).as_millis(), -1_000);
+        assert_eq!(Timestamp(-1, 500_000_000).as_millis(), -500);
+        assert_eq!(Timestamp(-1, 500_000_000).as_micros(), -500_000);
+        assert_eq!(Timestamp(-1, 999_999_999).as_millis(), -1);
+        assert_eq!(Timestamp(-1, 999_999_999).as_micros(), -1);
+
+        // The extremes are representable
+        assert_eq!(
+            Timestamp(i64::MIN, 0).as_micros(),
+            i128::from(i64::MIN) * 1_000_000
+        );
+        assert_eq!(
+            Timestamp(i64::MAX, 999_999_999).as_micros(),
+            i128::from(i64::MAX) * 1_000_000 + 999_999
+        );
+    }
+
+    #[test]
+    fn timestamp_from_millis_micros() {
+        assert_eq!(Timestamp::from_millis(0), Timestamp::EPOCH);
+        assert_eq!(Timestamp::from_micros(0), Timestamp::EPOCH);
+        assert_eq!(Timestamp::from_millis(1_500), Timestamp(1, 500_000_000));
+        assert_eq!(Timestamp::from_micros(1_500_001), Timestamp(1, 500_001_000));
+
+        // Pre-1970; the remainder borrows from the seconds, keeping the nanoseconds positive
+        assert_eq!(Timestamp::from_millis(-1), Timestamp(-1, 999_000_000));
+        assert_eq!(Timestamp::from_millis(-1_000), Timestamp(-1, 0));
+        assert_eq!(Timestamp::from_millis(-1_500), Timestamp(-2, 500_000_000));
+        assert_eq!(Timestamp::from_micros(-1), Timestamp(-1, 999_999_000));
+        assert_eq!(Timestamp::from_micros(-1_500_000), Timestamp(-2, 500_000));
+
+        // Round-trips through as_millis/as_micros, including the extremes
+        for n in vec![
+            i64::MIN,
+            i64::MIN + 1,
+            -1_000_001,
+            -999,
+            -1,
+            0,
+            1,
+            999,
+            1_000_001,
+            i64::MAX,
+        ] {
+            assert_eq!(Timestamp::from_millis(n).as_millis(), i128::from(n));
+            assert_eq!(Timestamp::from_micros(n).as_micros(), i128::from(n));
+        }
+    }
+
+    #[test]
+    fn timestamp_assign_checked() {
+        let mut t = Timestamp(0, 0);
+        for _ in 0..4 {
+            t.add_assign_checked(core::time::Duration::from_millis(250))
+                .unwrap();
+        }
+        assert_eq!(t, Timestamp(1, 0));
+        t.sub_assign_checked(core::time::Duration::from_millis(2_500))
+            .unwrap();
+        assert_eq!(t, Timestamp(-2, 500_000_000));
+
+        // On overflow the Timestamp is left as it was
+        let mut t = Timestamp(i64::MAX, 999_999_999);
+        assert_eq!(
+            t.add_assign_checked(core::time::Duration::from_nanos(1)),
+            Err(TimestampError::Overflow)
+        );
+        assert_eq!(t, Timestamp(i64::MAX, 999_999_999));
+        let mut t = Timestamp(i64::MIN, 0);
+        assert_eq!(
+            t.sub_assign_checked(core::time::Duration::from_nanos(1)),
+            Err(TimestampError::Overflow)
+        );
+        assert_eq!(t, Timestamp(i64::MIN, 0));
+    }
+
+    #[test]
+    fn timestamp_checked_duration_since() {
+        let t = Timestamp(10, 500_000_000);
+        assert_eq!(
+            t.checked_duration_since(t),
+            Some(core::time::Duration::new(0, 0))
+        );
+        // Sub-second deltas, with and without borrowing from the seconds
+        assert_eq!(
+            t.checked_duration_since(Timestamp(10, 499_999_999)),
+            Some(core::time::Duration::from_nanos(1))
+        );
+        assert_eq!(
+            t.checked_duration_since(Timestamp(9, 750_000_000)),
+            Some(core::time::Duration::from_millis(750))
+        );
+        // Pre-1970 earlier times
+        assert_eq!(
+            t.checked_duration_since(Timestamp(-1, 500_000_000)),
+            Some(core::time::Duration::from_secs(11))
+        );
+        // None if earlier is later, even by a nanosecond
+        assert_eq!(t.checked_duration_since(Timestamp(10, 500_000_001)), None);
+        assert_eq!(Timestamp::MIN.checked_duration_since(Timestamp::MAX), None);
+    }
+
+    #[test]
+    fn timestamp_system_time() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let cases = vec![
+            (UNIX_EPOCH, Timestamp(0, 0)),
+            (
+                UNIX_EPOCH + core::time::Duration::new(1, 500_000_000),
+                Timestamp(1, 500_000_000),
+            ),
+            (
+                UNIX_EPOCH - core::time::Duration::from_millis(500),
+                Timestamp(-1, 500_000_000),
+            ),
+            (
+                UNIX_EPOCH - core::time::Duration::new(86_400, 1),
+                Timestamp(-86_401, 999_999_999),
+            ),
+        ];
+        for (st, ts) in cases {
+            assert_eq!(Timestamp::try_from(st), Ok(ts));
+            assert_eq!(SystemTime::try_from(ts), Ok(st));
+        }
+
+        // Denormalized nanoseconds are carried into the seconds
+        assert_eq!(
+            SystemTime::try_from(Timestamp(-1, 1_500_000_000)),
+            Ok(UNIX_EPOCH + core::time::Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn timestamp_error_kinds() {
+        // Unparseable strings are reported as a ParseError, not an Overflow
+        assert!(matches!(
+            Timestamp::from_str("2020-05-05 not a time"),
+            Err(TimestampError::ParseError(_))
+        ));
+
+        // A valid Timestamp beyond the range of a chrono::DateTime is OutOfRange
+        let d: TimestampResult<chrono::DateTime<chrono::Utc>> = Timestamp(i64::MAX, 0).try_into();
+        assert_eq!(d, Err(TimestampError::OutOfRange));
+
+        // ... but carrying excess nanoseconds past i64::MAX seconds is a genuine Overflow
+        let d: TimestampResult<chrono::DateTime<chrono::Utc>> =
+            Timestamp(i64::MAX, 1_000_000_000).try_into();
+        assert_eq!(d, Err(TimestampError::Overflow));
+        assert_eq!(
+            Timestamp::new(i64::MAX, 1_000_000_000),
+            Err(TimestampError::Overflow)
+        );
+        assert_eq!(
+            Timestamp(i64::MAX, 0) + core::time::Duration::from_secs(1),
+            Err(TimestampError::Overflow)
+        );
+    }
+
+    #[test]
+    fn timestamp_elapsed_since() {
+        let now = Timestamp(1_600_000_000, 250_000_000);
+
+        // past timestamps, including across a nanosecond borrow
+        assert_eq!(
+            now.elapsed_since(now),
+            Ok(core::time::Duration::from_secs(0))
+        );
+        assert_eq!(
+            Timestamp(1_599_999_999, 750_000_000).elapsed_since(now),
+            Ok(core::time::Duration::from_millis(500))
+        );
+        assert_eq!(
+            Timestamp::EPOCH.elapsed_since(now),
+            Ok(core::time::Duration::new(1_600_000_000, 250_000_000))
+        );
+        // before the epoch, and the full span of the i64 seconds range
+        assert_eq!(
+            Timestamp(-1, 0).elapsed_since(Timestamp::EPOCH),
+            Ok(core::time::Duration::from_secs(1))
+        );
+        assert_eq!(
+            Timestamp(i64::MIN, 0).elapsed_since(Timestamp(i64::MAX, 0)),
+            Ok(core::time::Duration::from_secs(u64::MAX))
+        );
+
+        // future timestamps, even by a single nanosecond
+        assert_eq!(
+            Timestamp(1_600_000_000, 250_000_001).elapsed_since(now),
+            Err(TimestampError::InFuture)
+        );
+        assert_eq!(
+            Timestamp(1_600_000_001, 0).elapsed_since(now),
+            Err(TimestampError::InFuture)
+        );
+    }
+
+    #[test]
+    fn timestamp_round_to() {
+        let secs = core::time::Duration::from_secs;
+        let millis = core::time::Duration::from_millis;
+        let ts = Timestamp(1_600_000_007, 123_456_789);
+
+        // second-level buckets
+        assert_eq!(ts.round_to(secs(1)), Ok(Timestamp(1_600_000_007, 0)));
+        assert_eq!(ts.round_to(secs(10)), Ok(Timestamp(1_600_000_000, 0)));
+        assert_eq!(ts.round_to(secs(60)), Ok(Timestamp(1_599_999_960, 0)));
+        // already on a boundary
+        assert_eq!(
+            Timestamp(1_600_000_000, 0).round_to(secs(10)),
+            Ok(Timestamp(1_600_000_000, 0))
+        );
+
+        // millisecond-level buckets
+        assert_eq!(
+            ts.round_to(millis(1)),
+            Ok(Timestamp(1_600_000_007, 123_000_000))
+        );
+        assert_eq!(
+            ts.round_to(millis(100)),
+            Ok(Timestamp(1_600_000_007, 100_000_000))
+        );
+        // windows needn't divide a second evenly
+        assert_eq!(
+            ts.round_to(millis(1_500)),
+            Ok(Timestamp(1_600_000_006, 500_000_000))
+        );
+
+        // before the epoch, floor into the earlier window
+        assert_eq!(
+            Timestamp(-1, 500_000_000).round_to(secs(10)),
+            Ok(Timestamp(-10, 0))
+        );
+        assert_eq!(
+            Timestamp(-1, 500_000_000).round_to(millis(300)),
+            Ok(Timestamp(-1, 400_000_000))
+        );
+        // denormalized nanoseconds are carried
+        assert_eq!(
+            Timestamp(0, 2_500_000_000).round_to(secs(1)),
+            Ok(Timestamp(2, 0))
+        );
+
+        assert_eq!(
+            ts.round_to(core::time::Duration::from_secs(0)),
+            Err(TimestampError::ZeroGranularity)
+        );
+        assert_eq!(
+            Timestamp(i64::MIN, 0).round_to(secs(7)),
+            Err(TimestampError::Overflow)
+        );
+    }
+
+    #[test]
+    fn timestamp_offset_round_trip() {
+        for millis in &[-86_400_000_i64, -1, 0, 1, 1_500, i64::MAX] {
+            let offset = TimestampOffset::from_millis(*millis).unwrap();
+            let bytes = holochain_serialized_bytes::encode(&offset).unwrap();
+            // serialized as plain i64 milliseconds
+            let raw: i64 = holochain_serialized_bytes::decode(&bytes).unwrap();
+            assert_eq!(*millis, raw);
+            let decoded: TimestampOffset = holochain_serialized_bytes::decode(&bytes).unwrap();
+            assert_eq!(offset, decoded);
+        }
+
+        // sub-millisecond precision is not serialized
+        let offset = TimestampOffset(chrono::Duration::microseconds(-2_500));
+        let bytes = holochain_serialized_bytes::encode(&offset).unwrap();
+        let decoded: TimestampOffset = holochain_serialized_bytes::decode(&bytes).unwrap();
+        assert_eq!(TimestampOffset::from_millis(-2).unwrap(), decoded);
+
+        // a chrono::Duration can't hold i64::MIN milliseconds
+        let bytes = holochain_serialized_bytes::encode(&i64::MIN).unwrap();
+        assert!(holochain_serialized_bytes::decode::<_, TimestampOffset>(&bytes).is_err());
+
+        // offsets move a Timestamp either way
+        let t = Timestamp(100, 0);
+        let back = TimestampOffset::from_millis(-250).unwrap();
+        let forward = TimestampOffset::from_millis(250).unwrap();
+        assert_eq!(t + back, Ok(Timestamp(99, 750_000_000)));
+        assert_eq!(t + forward, Ok(Timestamp(100, 250_000_000)));
+        assert_eq!(&t - back, Ok(Timestamp(100, 250_000_000)));
+        assert_eq!(
+            Timestamp(i64::MAX, 0) + TimestampOffset::from_millis(1_000).unwrap(),
+            Err(TimestampError::Overflow)
+        );
+    }
+
+    #[test]
+    fn timestamp_saturating() {
+        // The clamping bounds are exactly the chrono::DateTime<Utc> range
+        let d: TimestampResult<chrono::DateTime<chrono::Utc>> = Timestamp::MAX.try_into();
+        assert!(d.is_ok());
+        let d: TimestampResult<chrono::DateTime<chrono::Utc>> = (Timestamp::MAX
+            + core::time::Duration::from_nanos(1))
+        .unwrap()
+        .try_into();
+        assert_eq!(d, Err(TimestampError::OutOfRange));
+        let d: TimestampResult<chrono::DateTime<chrono::Utc>> = Timestamp::MIN.try_into();
+        assert!(d.is_ok());
+        let d: TimestampResult<chrono::DateTime<chrono::Utc>> = (Timestamp::MIN
+            - core::time::Duration::from_nanos(1))
+        .unwrap()
+        .try_into();
+        assert_eq!(d, Err(TimestampError::OutOfRange));
+
+        // In range, saturating arithmetic matches checked arithmetic
+        let t = Timestamp(1_588_706_164, 266_431_045);
+        let d = core::time::Duration::new(86_400, 999_999_999);
+        assert_eq!(Ok(t.saturating_add(d)), t + d);
+        assert_eq!(Ok(t.saturating_sub(d)), t - d);
+
+        // Pushing past the chrono range, or past the i64 range, clamps
+        let one = core::time::Duration::from_nanos(1);
+        assert_eq!(Timestamp::MAX.saturating_add(one), Timestamp::MAX);
+        assert_eq!(Timestamp(i64::MAX, 0).saturating_add(one), Timestamp::MAX);
+        assert_eq!(Timestamp::MIN.saturating_sub(one), Timestamp::MIN);
+        assert_eq!(Timestamp(i64::MIN, 0).saturating_sub(one), Timestamp::MIN);
+
+        // A Duration beyond the i64 range of seconds may still land in range
+        let huge = core::time::Duration::from_secs(1 << 63);
+        assert_eq!(
+            Timestamp(i64::MIN, 0).saturating_add(huge),
+            Timestamp::EPOCH
+        );
+        assert_eq!(
+            Timestamp(i64::MAX, 1).saturating_sub(huge),
+            Timestamp(-1, 1)
+        );
+        assert_eq!(Timestamp::EPOCH.saturating_add(huge), Timestamp::MAX);
+        assert_eq!(Timestamp::EPOCH.saturating_sub(huge), Timestamp::MIN);
+    }
+
+    #[test]
+    fn timestamp_ord_matches_chrono() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of(t: &Timestamp) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            t.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        // A denormalized Timestamp is the same instant as its normalized form
+        let t = Timestamp(0, 1_500_000_000);
+        assert_eq!(t, Timestamp(1, 500_000_000));
+        assert_eq!(hash_of(&t), hash_of(&Timestamp(1, 500_000_000)));
+        assert!(Timestamp(0, 1_500_000_000) > Timestamp(1, 0));
+        assert!(Timestamp(0, 1_500_000_000) < Timestamp(1, 500_000_001));
+
+        // Random (secs, nsecs) pairs, with nsecs over the full u32 range, must order the same as
+        // the equivalent chrono instants.  A fixed-seed xorshift keeps the test reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let to_chrono = |t: &Timestamp| {
+            chrono::NaiveDateTime::from_timestamp(t.0, 0)
+                + chrono::Duration::nanoseconds(t.1 as i64)
+        };
+        for _ in 0..10_000 {
+            // Few distinct seconds, so that many pairs are close or equal instants
+            let a = Timestamp((next() % 8) as i64 - 4, next() as u32);
+            let b = Timestamp((next() % 8) as i64 - 4, next() as u32);
+            let (ca, cb) = (to_chrono(&a), to_chrono(&b));
+            assert_eq!(a.cmp(&b), ca.cmp(&cb), "{:?} vs {:?}", a, b);
+            assert_eq!(a == b, ca == cb, "{:?} vs {:?}", a, b);
+            if a == b {
+                assert_eq!(hash_of(&a), hash_of(&b));
+            }
+            // Shifting whole seconds between the fields doesn't change the instant
+            if a.1 >= 1_000_000_000 {
+                let shifted = Timestamp(a.0 + 1, a.1 - 1_000_000_000);
+                assert_eq!(shifted, a);
+                assert_eq!(hash_of(&shifted), hash_of(&a));
+            }
+            // ... and the same as their normalized forms
+            let na = Timestamp::normalize(a.0, a.1.into()).unwrap();
+            assert_eq!(na, a);
+            assert_eq!(na.cmp(&b), a.cmp(&b));
+        }
+    }
+
+    #[test]
+    fn timestamp_is_within() {
+        let t = Timestamp(100, 500_000_000);
+        let second = core::time::Duration::from_secs(1);
+
+        // Exactly at the tolerance, in either direction, is within it
+        assert!(Timestamp(101, 500_000_000).is_within(&t, second));
+        assert!(Timestamp(99, 500_000_000).is_within(&t, second));
+        assert!(t.is_within(&Timestamp(99, 500_000_000), second));
+
+        // One nanosecond past the tolerance is not, across a seconds boundary
+        assert!(!Timestamp(101, 500_000_001).is_within(&t, second));
+        assert!(!Timestamp(99, 499_999_999).is_within(&t, second));
+
+        // Sub-second tolerances use the nanoseconds fields
+        let milli = core::time::Duration::from_millis(1);
+        assert!(Timestamp(100, 501_000_000).is_within(&t, milli));
+        assert!(!Timestamp(100, 501_000_001).is_within(&t, milli));
+        assert!(t.is_within(&t, core::time::Duration::from_secs(0)));
+
+        // Denormalized Timestamps compare by the instant they represent
+        assert!(Timestamp(99, 1_500_000_000).is_within(&t, core::time::Duration::from_secs(0)));
+
+        // The extremes can be compared without overflow
+        assert!(!Timestamp(i64::MIN, 0).is_within(&Timestamp(i64::MAX, u32::MAX), second));
+        assert!(Timestamp(i64::MIN, 0).is_within(
+            &Timestamp(i64::MAX, 0),
+            core::time::Duration::from_secs(u64::MAX)
+        ));
+    }
+
+    #[test]
+    fn timestamp_humanize() {
+        let now = Timestamp(1_000_000_000, 0);
+        let ago = |secs: i64| (now - TimestampOffset::from_millis(secs * 1_000).unwrap()).unwrap();
+        let hence =
+            |secs: i64| (now + TimestampOffset::from_millis(secs * 1_000).unwrap()).unwrap();
+
+        // Under a second either way is "just now"
+        assert_eq!(now.humanize(now), "just now");
+        assert_eq!(Timestamp(999_999_999, 1).humanize(now), "just now");
+        assert_eq!(
+            Timestamp(1_000_000_000, 999_999_999).humanize(now),
+            "just now"
+        );
+
+        assert_eq!(ago(1).humanize(now), "1 second ago");
+        assert_eq!(hence(1).humanize(now), "in 1 second");
+        assert_eq!(ago(59).humanize(now), "59 seconds ago");
+
+        // Truncated to whole units of the largest unit that fits
+        assert_eq!(ago(60).humanize(now), "1 minute ago");
+        assert_eq!(ago(3 * 60 + 59).humanize(now), "3 minutes ago");
+        assert_eq!(hence(5 * 60).humanize(now), "in 5 minutes");
+        assert_eq!(ago(2 * 60 * 60).humanize(now), "2 hours ago");
+        assert_eq!(hence(24 * 60 * 60).humanize(now), "in 1 day");
+        assert_eq!(ago(13 * 24 * 60 * 60).humanize(now), "1 week ago");
+        assert_eq!(ago(45 * 24 * 60 * 60).humanize(now), "1 month ago");
+        assert_eq!(hence(364 * 24 * 60 * 60).humanize(now), "in 12 months");
+        assert_eq!(ago(3 * 365 * 24 * 60 * 60).humanize(now), "3 years ago");
+
+        // The extremes can be described without overflow
+        assert_eq!(
+            Timestamp(i64::MIN, 0).humanize(Timestamp(i64::MAX, 0)),
+            "584942417355 years ago"
+        );
+    }
+
+    #[test]
+    fn timestamp_try_from_u64() {
+        assert_eq!(Timestamp::try_from(0_u64), Ok(Timestamp::EPOCH));
+        assert_eq!(
+            Timestamp::try_from(i64::MAX as u64),
+            Ok(Timestamp(i64::MAX, 0))
+        );
+        // One past i64::MAX would wrap to i64::MIN if cast
+        assert_eq!(
+            Timestamp::try_from(i64::MAX as u64 + 1),
+            Err(TimestampError::OutOfRange)
+        );
+        assert_eq!(
+            Timestamp::try_from(u64::MAX),
+            Err(TimestampError::OutOfRange)
+        );
+    }
 }
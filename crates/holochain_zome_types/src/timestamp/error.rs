@@ -4,8 +4,16 @@ use chrono::ParseError;
 pub enum TimestampError {
     #[error("Overflow in adding/subtracting a Duration")]
     Overflow,
+    #[error("Timestamp is outside the representable range of the target time type")]
+    OutOfRange,
+    #[error("Timestamp is later than the supplied current time")]
+    InFuture,
+    #[error("Cannot round a Timestamp to a zero granularity")]
+    ZeroGranularity,
     #[error(transparent)]
     ParseError(#[from] ParseError),
+    #[error("Could not parse {0:?} as an rfc3339, rfc2822 or integer Unix seconds timestamp")]
+    UnrecognizedFormat(String),
 }
 
 pub type TimestampResult<T> = Result<T, TimestampError>;
@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use super::Timestamp;
+
+/// Values kept in Timestamp order, for time-series such as tracking when peers were last gossiped
+/// with.  Since Timestamps are ordered by the instant they represent, a denormalized Timestamp
+/// (nsecs >= 1e9) is the same key as its normalized form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimestampIndex<V>(BTreeMap<Timestamp, V>);
+
+impl<V> Default for TimestampIndex<V> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<V> TimestampIndex<V> {
+    /// An empty TimestampIndex.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value at a Timestamp, returning the value it replaced, if any.
+    pub fn insert(&mut self, at: Timestamp, value: V) -> Option<V> {
+        self.0.insert(at, value)
+    }
+
+    /// The value at a Timestamp, if any.
+    pub fn get(&self, at: &Timestamp) -> Option<&V> {
+        self.0.get(at)
+    }
+
+    /// Remove the value at a Timestamp, returning it, if any.
+    pub fn remove(&mut self, at: &Timestamp) -> Option<V> {
+        self.0.remove(at)
+    }
+
+    /// The number of Timestamps with a value.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The earliest Timestamp and its value, if any.
+    pub fn earliest(&self) -> Option<(&Timestamp, &V)> {
+        self.0.iter().next()
+    }
+
+    /// The latest Timestamp and its value, if any.
+    pub fn latest(&self) -> Option<(&Timestamp, &V)> {
+        self.0.iter().next_back()
+    }
+
+    /// All the Timestamps and their values, earliest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&Timestamp, &V)> {
+        self.0.iter()
+    }
+
+    /// The Timestamps and their values from `start` (inclusive) to `end` (exclusive), earliest
+    /// first.  Empty, rather than panicking as BTreeMap::range does, if `end` is not after `start`.
+    ///
+    /// ```
+    /// use holochain_zome_types::prelude::*;
+    ///
+    /// let index: TimestampIndex<&str> = vec![
+    ///     (Timestamp( 1, 0 ), "a"),
+    ///     (Timestamp( 2, 0 ), "b"),
+    ///     (Timestamp( 3, 0 ), "c"),
+    /// ].into_iter().collect();
+    /// assert_eq!( index.range_between(Timestamp( 1, 500 ), Timestamp( 3, 0 )).collect::<Vec<_>>(),
+    ///             vec![(&Timestamp( 2, 0 ), &"b")]);
+    /// ```
+    pub fn range_between(
+        &self,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> impl DoubleEndedIterator<Item = (&Timestamp, &V)> {
+        let range = if start < end {
+            Some(self.0.range(start..end))
+        } else {
+            None
+        };
+        range.into_iter().flatten()
+    }
+
+    /// Remove every value from before `cutoff`, returning them.  A value at exactly `cutoff` is
+    /// kept.
+    pub fn expire_before(&mut self, cutoff: Timestamp) -> TimestampIndex<V> {
+        let kept = self.0.split_off(&cutoff);
+        Self(std::mem::replace(&mut self.0, kept))
+    }
+}
+
+impl<V> FromIterator<(Timestamp, V)> for TimestampIndex<V> {
+    fn from_iter<I: IntoIterator<Item = (Timestamp, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<V> IntoIterator for TimestampIndex<V> {
+    type Item = (Timestamp, V);
+    type IntoIter = std::collections::btree_map::IntoIter<Timestamp, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys<'a, V: 'a>(iter: impl Iterator<Item = (&'a Timestamp, &'a V)>) -> Vec<Timestamp> {
+        iter.map(|(at, _)| *at).collect()
+    }
+
+    #[test]
+    fn timestamp_index_range_between() {
+        let index: TimestampIndex<u32> = vec![
+            (Timestamp(11, 1), 3),
+            (Timestamp(10, 999_999_999), 1),
+            (Timestamp(11, 0), 2),
+            (Timestamp(9, 0), 0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(Some((&Timestamp(9, 0), &0)), index.earliest());
+        assert_eq!(Some((&Timestamp(11, 1), &3)), index.latest());
+
+        // one nanosecond either side of a whole second
+        assert_eq!(
+            vec![Timestamp(10, 999_999_999), Timestamp(11, 0)],
+            keys(index.range_between(Timestamp(10, 999_999_999), Timestamp(11, 1)))
+        );
+        assert_eq!(
+            vec![Timestamp(11, 0), Timestamp(11, 1)],
+            keys(index.range_between(Timestamp(11, 0), Timestamp(11, 2)))
+        );
+        assert_eq!(
+            vec![Timestamp(10, 999_999_999)],
+            keys(index.range_between(Timestamp(10, 999_999_998), Timestamp(11, 0)))
+        );
+
+        // a denormalized Timestamp is the same instant as its normalized form
+        assert_eq!(
+            keys(index.range_between(Timestamp(11, 0), Timestamp(11, 2))),
+            keys(index.range_between(Timestamp(10, 1_000_000_000), Timestamp(10, 1_000_000_002)))
+        );
+        assert_eq!(Some(&2), index.get(&Timestamp(10, 1_000_000_000)));
+
+        // empty and backwards ranges are empty
+        assert!(keys(index.range_between(Timestamp(11, 0), Timestamp(11, 0))).is_empty());
+        assert!(keys(index.range_between(Timestamp(11, 1), Timestamp(9, 0))).is_empty());
+
+        // and the range can be walked latest first
+        assert_eq!(
+            vec![
+                Timestamp(11, 0),
+                Timestamp(10, 999_999_999),
+                Timestamp(9, 0)
+            ],
+            keys(index.range_between(Timestamp::MIN, Timestamp(11, 1)).rev())
+        );
+    }
+
+    #[test]
+    fn timestamp_index_expire_before() {
+        let mut index: TimestampIndex<u32> = vec![
+            (Timestamp(10, 999_999_999), 1),
+            (Timestamp(11, 0), 2),
+            (Timestamp(11, 1), 3),
+        ]
+        .into_iter()
+        .collect();
+
+        // nothing is before the earliest
+        assert!(index.expire_before(Timestamp(10, 999_999_999)).is_empty());
+        assert_eq!(3, index.len());
+
+        // the cutoff itself is kept
+        let expired = index.expire_before(Timestamp(11, 0));
+        assert_eq!(
+            vec![(Timestamp(10, 999_999_999), 1)],
+            expired.into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![Timestamp(11, 0), Timestamp(11, 1)], keys(index.iter()));
+
+        // a denormalized cutoff is the same instant as its normalized form
+        let expired = index.expire_before(Timestamp(10, 1_000_000_001));
+        assert_eq!(
+            vec![(Timestamp(11, 0), 2)],
+            expired.into_iter().collect::<Vec<_>>()
+        );
+
+        index.insert(Timestamp(12, 0), 4);
+        assert_eq!(2, index.expire_before(Timestamp::MAX).len());
+        assert!(index.is_empty());
+    }
+}
@@ -96,13 +96,16 @@ wasm_io_types! {
     // Header hash of the DeleteLink element.
     fn call_remote (zt::call_remote::CallRemote) -> zt::ZomeCallResponse;
 
+    // Call the same zome fn on several agents near a basis.
+    fn call_remote_multi (zt::call_remote::CallRemoteMulti) -> Vec<(holo_hash::AgentPubKey, zt::ZomeCallResponse)>;
+
     fn call (zt::call::Call) -> zt::ZomeCallResponse;
 
     // @todo List all the local capability claims.
     fn capability_claims (()) -> ();
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List all the local capability grants that have not been revoked.
+    fn capability_grants (()) -> Vec<zt::entry::CapGrantEntry>;
 
     // @todo Get the capability for the current zome call.
     fn capability_info (()) -> ();
@@ -131,6 +134,9 @@ wasm_io_types! {
 
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // Page through the local source chain, as (chain index, header hash) pairs.
+    fn chain_activity (zt::agent_activity::ChainActivityInput) -> Vec<(u32, holo_hash::HeaderHash)>;
+
     fn get_details (zt::entry::GetInput) -> Option<zt::metadata::Details>;
 
     fn get_link_details (zt::link::GetLinksInput) -> zt::link::LinkDetails;
@@ -219,7 +225,52 @@ pub enum ZomeCallResponse {
     /// Cap grant failure.
     /// Something like a 401 http response.
     Unauthorized(CellId, ZomeName, FunctionName, AgentPubKey),
+    /// The target cell has no zome with this name.
+    /// Something like a 404 http response.
+    ZomeNotFound(CellId, ZomeName),
+    /// The target zome has no function with this name.
+    /// Something like a 404 http response.
+    FunctionNotFound(CellId, ZomeName, FunctionName),
     /// This was a zome call made remotely but
     /// something has failed on the network
     NetworkError(String),
+    /// This was a zome call made remotely but
+    /// the remote agent did not respond within the timeout
+    NetworkTimeout,
+}
+
+/// Why a zome call didn't produce output, i.e. every [ `ZomeCallResponse` ]
+/// other than `Ok`, so callers can match on exactly what went wrong.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+pub enum ZomeCallFailure {
+    /// No live cap grant matched the call.
+    Unauthorized(CellId, ZomeName, FunctionName, AgentPubKey),
+    /// The target cell has no zome with this name.
+    ZomeNotFound(CellId, ZomeName),
+    /// The target zome has no function with this name.
+    FunctionNotFound(CellId, ZomeName, FunctionName),
+    /// Something failed on the network during a remote call.
+    NetworkError(String),
+    /// The remote agent did not respond within the timeout.
+    NetworkTimeout,
+}
+
+impl ZomeCallResponse {
+    /// The output of the called function, or why there is none.
+    pub fn into_result(self) -> Result<crate::ExternIO, ZomeCallFailure> {
+        match self {
+            Self::Ok(extern_io) => Ok(extern_io),
+            Self::Unauthorized(cell_id, zome_name, fn_name, agent) => Err(
+                ZomeCallFailure::Unauthorized(cell_id, zome_name, fn_name, agent),
+            ),
+            Self::ZomeNotFound(cell_id, zome_name) => {
+                Err(ZomeCallFailure::ZomeNotFound(cell_id, zome_name))
+            }
+            Self::FunctionNotFound(cell_id, zome_name, fn_name) => Err(
+                ZomeCallFailure::FunctionNotFound(cell_id, zome_name, fn_name),
+            ),
+            Self::NetworkError(e) => Err(ZomeCallFailure::NetworkError(e)),
+            Self::NetworkTimeout => Err(ZomeCallFailure::NetworkTimeout),
+        }
+    }
 }
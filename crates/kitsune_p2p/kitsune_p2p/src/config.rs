@@ -29,6 +29,10 @@ pub struct KitsuneP2pConfig {
     pub transport_pool: Vec<TransportConfig>,
     /// The service used for peers to discover each before they are peers.
     pub bootstrap_service: Option<Url2>,
+    /// Further bootstrap services, tried in order after `bootstrap_service`
+    /// if it can't be reached. More can be added at runtime with `add_bootstrap`.
+    #[serde(default)]
+    pub bootstrap_services: Vec<Url2>,
     /// Network tuning parameters. These are managed loosely,
     /// as they are subject to change. If you specify a tuning parameter
     /// that no longer exists, or a value that does not parse,
@@ -37,6 +41,23 @@ pub struct KitsuneP2pConfig {
     pub tuning_params: KitsuneP2pTuningParams,
     /// The network used for connecting to other peers
     pub network_type: NetworkType,
+    /// How strictly we check the identity of remote peers
+    /// when connecting to them.
+    #[serde(default)]
+    pub transport_security: TransportSecurityConfig,
+    /// The network conditions the in-memory transport simulates,
+    /// set by the test harness. Never read from a config file.
+    #[serde(skip)]
+    pub(crate) mem_sim: MemSimConfig,
+}
+
+/// Latency and message loss for the in-memory transport to simulate in tests.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct MemSimConfig {
+    /// Latency added to every message.
+    pub latency: std::time::Duration,
+    /// The fraction of messages dropped, from 0.0 to 1.0.
+    pub drop_rate: f64,
 }
 
 impl Default for KitsuneP2pConfig {
@@ -44,12 +65,32 @@ impl Default for KitsuneP2pConfig {
         Self {
             transport_pool: Vec::new(),
             bootstrap_service: None,
+            bootstrap_services: Vec::new(),
             tuning_params: KitsuneP2pTuningParams::default(),
             network_type: NetworkType::QuicBootstrap,
+            transport_security: TransportSecurityConfig::default(),
+            mem_sim: MemSimConfig::default(),
         }
     }
 }
 
+/// Per-space overrides of the transport related parts of a KitsuneP2pConfig,
+/// set with `configure_space`. All spaces share the node's transport endpoint,
+/// and so its bindings and proxy settings, but each space can choose how its
+/// agents discover peers, how strictly remote peers' identities are checked,
+/// and its own timeouts and other tuning. Unset fields use the node's config.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SpaceTransportConfig {
+    /// The network used for connecting to other peers in this space.
+    pub network_type: Option<NetworkType>,
+    /// How strictly we check the identity of remote peers in this space.
+    pub transport_security: Option<TransportSecurityConfig>,
+    /// Network tuning parameters for this space.
+    pub tuning_params: Option<KitsuneP2pTuningParams>,
+}
+
 fn cnv_bind_to(bind_to: &Option<url2::Url2>) -> TxUrl {
     match bind_to {
         Some(bind_to) => bind_to.clone().into(),
@@ -58,6 +99,36 @@ fn cnv_bind_to(bind_to: &Option<url2::Url2>) -> TxUrl {
 }
 
 impl KitsuneP2pConfig {
+    /// This config, with a space's overrides applied.
+    pub fn with_space_config(&self, space_config: &SpaceTransportConfig) -> Self {
+        let mut out = self.clone();
+        if let Some(network_type) = &space_config.network_type {
+            out.network_type = network_type.clone();
+        }
+        if let Some(transport_security) = &space_config.transport_security {
+            out.transport_security = transport_security.clone();
+        }
+        if let Some(tuning_params) = &space_config.tuning_params {
+            out.tuning_params = tuning_params.clone();
+        }
+        out
+    }
+
+    /// All configured bootstrap services, in the order they should be tried.
+    pub fn all_bootstrap_services(&self) -> Vec<Url2> {
+        let mut out: Vec<Url2> = Vec::new();
+        for url in self
+            .bootstrap_service
+            .iter()
+            .chain(self.bootstrap_services.iter())
+        {
+            if !out.contains(url) {
+                out.push(url.clone());
+            }
+        }
+        out
+    }
+
     /// tx2 is currently designed to use exactly one proxy wrapped transport
     /// so, convert a bunch of the options from the previous transport
     /// paradigm into that pattern.
@@ -173,3 +244,32 @@ pub enum NetworkType {
     /// Via MDNS to the LAN
     QuicMdns,
 }
+
+/// Transport security options for outgoing connections
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct TransportSecurityConfig {
+    /// If true, a remote agent is only connected to at a url taken from
+    /// agent info signed by that agent's own key, and only if the remote
+    /// presents the tls certificate that url names, otherwise the connection
+    /// is refused.
+    /// Default: true.
+    pub verify_remote_identity: bool,
+}
+
+impl Default for TransportSecurityConfig {
+    fn default() -> Self {
+        Self {
+            verify_remote_identity: true,
+        }
+    }
+}
+
+impl TransportSecurityConfig {
+    /// Skip remote identity verification.
+    /// Only use this for local test networks.
+    pub fn insecure() -> Self {
+        Self {
+            verify_remote_identity: false,
+        }
+    }
+}
@@ -5,6 +5,10 @@ mod actor;
 use actor::*;
 
 /// Spawn a new KitsuneP2p actor.
+/// Returns the sender to control the actor, and the stream of events it
+/// generates, including those for requests from remote nodes.
+/// There is one event stream per actor: the implementor is expected to
+/// route each event, e.g. to the right cell, by its space and to_agent.
 pub async fn spawn_kitsune_p2p(
     config: crate::KitsuneP2pConfig,
     tls_config: kitsune_p2p_types::tls::TlsConfig,
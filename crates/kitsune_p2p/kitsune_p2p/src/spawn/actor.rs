@@ -8,25 +8,38 @@ use crate::metrics::KitsuneMetrics;
 use crate::*;
 use futures::future::FutureExt;
 use futures::stream::StreamExt;
+use ghost_actor::GhostControlSender;
 use kitsune_p2p_proxy::tx2::*;
 use kitsune_p2p_proxy::ProxyUrl;
 use kitsune_p2p_transport_quic::tx2::*;
 use kitsune_p2p_types::async_lazy::AsyncLazy;
+use kitsune_p2p_types::dht_arc::DhtArc;
 use kitsune_p2p_types::tx2::tx2_api::*;
 use kitsune_p2p_types::tx2::tx2_pool_promote::*;
 use kitsune_p2p_types::tx2::*;
 use kitsune_p2p_types::*;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// The bootstrap service is much more thoroughly documented in the default service implementation.
 /// See https://github.com/holochain/bootstrap
 mod bootstrap;
+mod connection_lru;
 mod discover;
 mod gossip;
+mod notify_dedup;
+mod notify_jitter;
+mod outbound_queue;
+mod peer_latency;
 mod space;
+use connection_lru::*;
 use ghost_actor::dependencies::tracing;
+use notify_dedup::*;
+use notify_jitter::*;
+use outbound_queue::*;
+use peer_latency::*;
 use space::*;
 
 ghost_actor::ghost_chan! {
@@ -36,14 +49,99 @@ ghost_actor::ghost_chan! {
     }
 }
 
+/// Counts of outbound operations that have been accepted by the actor
+/// but have not yet completed.
+#[derive(Default)]
+struct PendingCounters {
+    rpc_single: AtomicU64,
+    notify_single: AtomicU64,
+    rpc_multi: AtomicU64,
+    notify_multi: AtomicU64,
+    /// Operations still waiting on their space actor to be ready.
+    queued: AtomicU64,
+}
+
+impl PendingCounters {
+    fn stats(&self) -> actor::KitsuneP2pStats {
+        actor::KitsuneP2pStats {
+            rpc_single_in_flight: self.rpc_single.load(Ordering::Relaxed),
+            notify_single_in_flight: self.notify_single.load(Ordering::Relaxed),
+            rpc_multi_in_flight: self.rpc_multi.load(Ordering::Relaxed),
+            notify_multi_in_flight: self.notify_multi.load(Ordering::Relaxed),
+            outbound_queue_depth: self.queued.load(Ordering::Relaxed),
+        }
+    }
+
+    /// No operations are pending.
+    fn is_idle(&self) -> bool {
+        self.stats() == actor::KitsuneP2pStats::default()
+    }
+}
+
+/// Resolve `fut`, unless the actor is shut down first, in which case
+/// resolve with KitsuneP2pError::Shutdown.
+async fn or_shutdown<T>(
+    mut shutdown: tokio::sync::watch::Receiver<bool>,
+    fut: impl std::future::Future<Output = KitsuneP2pResult<T>>,
+) -> KitsuneP2pResult<T> {
+    let signal = async move {
+        while !*shutdown.borrow() {
+            // the actor was dropped without a shutdown
+            if shutdown.changed().await.is_err() {
+                break;
+            }
+        }
+    };
+    tokio::select! {
+        res = fut => res,
+        _ = signal => Err(KitsuneP2pError::Shutdown),
+    }
+}
+
+/// Holds one count on a pending counter, releasing it when dropped,
+/// so that cancelled operations are released as well as completed ones.
+struct PendingGuard(Arc<PendingCounters>, fn(&PendingCounters) -> &AtomicU64);
+
+impl PendingGuard {
+    fn new(counters: &Arc<PendingCounters>, f: fn(&PendingCounters) -> &AtomicU64) -> Self {
+        f(counters).fetch_add(1, Ordering::Relaxed);
+        Self(counters.clone(), f)
+    }
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        (self.1)(&self.0).fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 pub(crate) struct KitsuneP2pActor {
     this_addr: url2::Url2,
     channel_factory: ghost_actor::actor_builder::GhostActorChannelFactory<Self>,
     internal_sender: ghost_actor::GhostSender<Internal>,
     evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     ep_hnd: Tx2EpHnd<wire::Wire>,
+    connections: Arc<ConnectionLru>,
     spaces: HashMap<Arc<KitsuneSpace>, AsyncLazy<ghost_actor::GhostSender<KitsuneP2p>>>,
     config: Arc<KitsuneP2pConfig>,
+    pending: Arc<PendingCounters>,
+    outbound_queue: Arc<OutboundQueue>,
+    rejoin_policy: tokio::sync::watch::Sender<actor::RejoinPolicy>,
+    rejoin_policy_recv: tokio::sync::watch::Receiver<actor::RejoinPolicy>,
+    gossip_config: tokio::sync::watch::Sender<actor::GossipConfig>,
+    gossip_config_recv: tokio::sync::watch::Receiver<actor::GossipConfig>,
+    bootstrap_services: tokio::sync::watch::Sender<Vec<url2::Url2>>,
+    bootstrap_services_recv: tokio::sync::watch::Receiver<Vec<url2::Url2>>,
+    /// The most notify_multi sends are held back by, see set_notify_jitter.
+    notify_jitter_max: std::time::Duration,
+    /// Per-space transport preferences, see configure_space.
+    space_configs: HashMap<Arc<KitsuneSpace>, SpaceTransportConfig>,
+    /// Set once a shutdown has begun, after which new work is rejected.
+    draining: bool,
+    /// Set to true once a shutdown has finished draining, to abort
+    /// any operations still outstanding.
+    shutdown: Arc<tokio::sync::watch::Sender<bool>>,
+    shutdown_recv: tokio::sync::watch::Receiver<bool>,
 }
 
 impl KitsuneP2pActor {
@@ -64,6 +162,8 @@ impl KitsuneP2pActor {
                 let mut conf = MemConfig::default();
                 conf.tls = Some(tls_config.clone());
                 conf.tuning_params = Some(config.tuning_params.clone());
+                conf.latency = config.mem_sim.latency;
+                conf.drop_rate = config.mem_sim.drop_rate;
                 (
                     tx2_mem_adapter(conf)
                         .await
@@ -99,6 +199,8 @@ impl KitsuneP2pActor {
                 "Wire::CallResp" => KitsuneMetrics::CallResp,
                 "Wire::Notify" => KitsuneMetrics::Notify,
                 "Wire::NotifyResp" => KitsuneMetrics::NotifyResp,
+                "Wire::NotifySingle" => KitsuneMetrics::Notify,
+                "Wire::NotifyWithId" => KitsuneMetrics::Notify,
                 "Wire::FetchOpHashes" => KitsuneMetrics::FetchOpHashes,
                 "Wire::FetchOpHashesResponse" => KitsuneMetrics::FetchOpHashesResp,
                 "Wire::FetchOpData" => KitsuneMetrics::FetchOpData,
@@ -107,6 +209,8 @@ impl KitsuneP2pActor {
                 "Wire::AgentInfoQueryResp" => KitsuneMetrics::AgentInfoQueryResp,
                 "Wire::Gossip" => KitsuneMetrics::Gossip,
                 "Wire::GossipResp" => KitsuneMetrics::GossipResp,
+                "Wire::Ping" => KitsuneMetrics::Ping,
+                "Wire::PingResp" => KitsuneMetrics::PingResp,
                 _ => return,
             };
             KitsuneMetrics::count(t, l);
@@ -166,18 +270,36 @@ impl KitsuneP2pActor {
 
         tracing::info!("this_addr: {}", this_addr);
 
+        let connections = ConnectionLru::new(config.tuning_params.connection_limit);
+
+        let notify_dedup = NotifyDedup::new(config.tuning_params.notify_dedup_cache_size as usize);
+
         tokio::task::spawn({
             let evt_sender = evt_sender.clone();
             let tuning_params = config.tuning_params.clone();
+            let ep_hnd = ep_hnd.clone();
+            let connections = connections.clone();
             ep.for_each_concurrent(tuning_params.concurrent_limit_per_thread, move |event| {
                 let evt_sender = evt_sender.clone();
                 let tuning_params = tuning_params.clone();
+                let ep_hnd = ep_hnd.clone();
+                let connections = connections.clone();
+                let notify_dedup = notify_dedup.clone();
                 async move {
                     let evt_sender = &evt_sender;
                     use tx2_api::Tx2EpEvent::*;
-                    #[allow(clippy::single_match)]
                     match event {
-                        IncomingRequest(Tx2EpIncomingRequest { data, respond, .. }) => {
+                        IncomingConnection(Tx2EpConnection { url, .. })
+                        | OutgoingConnection(Tx2EpConnection { url, .. }) => {
+                            close_evicted(&ep_hnd, connections.touch(url));
+                        }
+                        ConnectionClosed(Tx2EpConnectionClosed { url, .. }) => {
+                            connections.remove(&url);
+                        }
+                        IncomingRequest(Tx2EpIncomingRequest {
+                            url, data, respond, ..
+                        }) => {
+                            close_evicted(&ep_hnd, connections.touch(url));
                             match data {
                                 wire::Wire::Call(wire::Call {
                                     space,
@@ -210,7 +332,6 @@ impl KitsuneP2pActor {
                                     from_agent,
                                     to_agent,
                                     data,
-                                    ..
                                 }) => {
                                     if let Err(err) = evt_sender
                                         .notify(space, to_agent, from_agent, data.into())
@@ -228,6 +349,58 @@ impl KitsuneP2pActor {
                                         .respond(resp, tuning_params.implicit_timeout())
                                         .await;
                                 }
+                                wire::Wire::NotifyWithId(wire::NotifyWithId {
+                                    space,
+                                    from_agent,
+                                    to_agent,
+                                    data,
+                                    msg_id,
+                                }) => {
+                                    // already handled via another path, so
+                                    // just acknowledge it
+                                    if !notify_dedup.is_new(to_agent.clone(), msg_id) {
+                                        tracing::trace!(msg_id, "dropping duplicate notify");
+                                        let resp = wire::Wire::notify_resp();
+                                        let _ = respond
+                                            .respond(resp, tuning_params.implicit_timeout())
+                                            .await;
+                                        return;
+                                    }
+                                    if let Err(err) = evt_sender
+                                        .notify(space, to_agent, from_agent, data.into())
+                                        .await
+                                    {
+                                        let reason = format!("{:?}", err);
+                                        let fail = wire::Wire::failure(reason);
+                                        let _ = respond
+                                            .respond(fail, tuning_params.implicit_timeout())
+                                            .await;
+                                        return;
+                                    }
+                                    let resp = wire::Wire::notify_resp();
+                                    let _ = respond
+                                        .respond(resp, tuning_params.implicit_timeout())
+                                        .await;
+                                }
+                                wire::Wire::NotifySingle(wire::NotifySingle {
+                                    space,
+                                    from_agent,
+                                    to_agent,
+                                    data,
+                                }) => {
+                                    // the sender only waits for receipt, so ack
+                                    // before handing the notify on
+                                    let resp = wire::Wire::notify_resp();
+                                    let _ = respond
+                                        .respond(resp, tuning_params.implicit_timeout())
+                                        .await;
+                                    if let Err(err) = evt_sender
+                                        .notify(space, to_agent, from_agent, data.into())
+                                        .await
+                                    {
+                                        tracing::warn!(?err, "failed to handle notify_single");
+                                    }
+                                }
                                 wire::Wire::FetchOpHashes(wire::FetchOpHashes {
                                     space,
                                     from_agent,
@@ -365,6 +538,31 @@ impl KitsuneP2pActor {
                                         .respond(resp, tuning_params.implicit_timeout())
                                         .await;
                                 }
+                                wire::Wire::Leave(wire::Leave { space, tombstone }) => {
+                                    // the tombstone replaces the agent's info in
+                                    // our peer store, so we stop routing to it
+                                    match local_leave(&evt_sender, space, tombstone).await {
+                                        Ok(true) => (),
+                                        Ok(false) => {
+                                            tracing::warn!(
+                                                "ignoring forged or stale leave tombstone"
+                                            )
+                                        }
+                                        Err(err) => {
+                                            tracing::warn!(?err, "failed to store leave tombstone")
+                                        }
+                                    }
+                                    let resp = wire::Wire::leave_resp();
+                                    let _ = respond
+                                        .respond(resp, tuning_params.implicit_timeout())
+                                        .await;
+                                }
+                                wire::Wire::Ping(_) => {
+                                    let resp = wire::Wire::ping_resp();
+                                    let _ = respond
+                                        .respond(resp, tuning_params.implicit_timeout())
+                                        .await;
+                                }
                                 data => unimplemented!("{:?}", data),
                             }
                         }
@@ -374,16 +572,58 @@ impl KitsuneP2pActor {
             })
         });
 
+        let (rejoin_policy, rejoin_policy_recv) =
+            tokio::sync::watch::channel(actor::RejoinPolicy::default());
+        let (gossip_config, gossip_config_recv) =
+            tokio::sync::watch::channel(actor::GossipConfig::default());
+        let (bootstrap_services, bootstrap_services_recv) =
+            tokio::sync::watch::channel(config.all_bootstrap_services());
+        let (shutdown, shutdown_recv) = tokio::sync::watch::channel(false);
+
+        let outbound_queue =
+            OutboundQueue::new(config.tuning_params.concurrent_outbound_multi_limit);
+
         Ok(Self {
             this_addr: this_addr.into(),
             channel_factory,
             internal_sender,
             evt_sender,
             ep_hnd,
+            connections,
             spaces: HashMap::new(),
             config: Arc::new(config),
+            pending: Arc::new(PendingCounters::default()),
+            outbound_queue,
+            rejoin_policy,
+            rejoin_policy_recv,
+            gossip_config,
+            gossip_config_recv,
+            bootstrap_services,
+            bootstrap_services_recv,
+            notify_jitter_max: std::time::Duration::from_secs(0),
+            space_configs: HashMap::new(),
+            draining: false,
+            shutdown: Arc::new(shutdown),
+            shutdown_recv,
         })
     }
+
+    /// Reject new work once a shutdown has begun.
+    fn check_accepting(&self) -> KitsuneP2pResult<()> {
+        if self.draining {
+            return Err(KitsuneP2pError::Shutdown);
+        }
+        Ok(())
+    }
+
+    /// Reject outbound payloads over the `max_message_bytes` tuning param.
+    fn check_message_size(&self, size: usize) -> KitsuneP2pResult<()> {
+        let limit = self.config.tuning_params.max_message_bytes;
+        if size > limit {
+            return Err(KitsuneP2pError::MessageTooLarge { size, limit });
+        }
+        Ok(())
+    }
 }
 
 async fn agent_info_query(
@@ -527,22 +767,138 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         Ok(async move { Ok(this_addr) }.boxed().into())
     }
 
+    fn handle_pending_stats(&mut self) -> KitsuneP2pHandlerResult<actor::KitsuneP2pStats> {
+        let stats = self.pending.stats();
+        Ok(async move { Ok(stats) }.boxed().into())
+    }
+
+    fn handle_set_rejoin_policy(
+        &mut self,
+        policy: actor::RejoinPolicy,
+    ) -> KitsuneP2pHandlerResult<()> {
+        // we hold a receiver ourselves, so this cannot fail
+        let _ = self.rejoin_policy.send(policy);
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
+    fn handle_set_gossip_config(
+        &mut self,
+        cfg: actor::GossipConfig,
+    ) -> KitsuneP2pHandlerResult<()> {
+        // we hold a receiver ourselves, so this cannot fail
+        let _ = self.gossip_config.send(cfg);
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
+    fn handle_set_connection_limit(&mut self, max: usize) -> KitsuneP2pHandlerResult<()> {
+        close_evicted(&self.ep_hnd, self.connections.set_limit(max));
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
+    fn handle_set_notify_jitter(
+        &mut self,
+        max: std::time::Duration,
+    ) -> KitsuneP2pHandlerResult<()> {
+        self.notify_jitter_max = max;
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
+    fn handle_add_bootstrap(&mut self, url: String) -> KitsuneP2pHandlerResult<()> {
+        let url = url2::Url2::try_parse(url).map_err(KitsuneP2pError::other)?;
+        let mut services = self.bootstrap_services_recv.borrow().clone();
+        if !services.contains(&url) {
+            services.push(url);
+            // we hold a receiver ourselves, so this cannot fail
+            let _ = self.bootstrap_services.send(services);
+        }
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
+    fn handle_shutdown(&mut self, drain_timeout_ms: u64) -> KitsuneP2pHandlerResult<()> {
+        self.draining = true;
+        let pending = self.pending.clone();
+        let shutdown = self.shutdown.clone();
+        let spaces: Vec<_> = self.spaces.values().map(|space| space.get()).collect();
+        let ep_hnd = self.ep_hnd.clone();
+        let internal_sender = self.internal_sender.clone();
+        Ok(async move {
+            let drain = async {
+                while !pending.is_idle() {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+            };
+            let drain_timeout = std::time::Duration::from_millis(drain_timeout_ms);
+            if tokio::time::timeout(drain_timeout, drain).await.is_err() {
+                tracing::warn!(
+                    msg = "Shutting down with operations outstanding",
+                    stats = ?pending.stats()
+                );
+            }
+            // we hold a receiver ourselves, so this cannot fail
+            let _ = shutdown.send(true);
+            for space in spaces {
+                let _ = space.await.ghost_actor_shutdown().await;
+            }
+            ep_hnd.close(0, "shutdown").await;
+            let _ = internal_sender.ghost_actor_shutdown().await;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_configure_space(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        config: SpaceTransportConfig,
+    ) -> KitsuneP2pHandlerResult<()> {
+        self.space_configs.insert(space.clone(), config.clone());
+        // spaces not yet created pick up their config when they are
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Ok(async move { Ok(()) }.boxed().into()),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            space_sender.await.configure_space(space, config).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_join(
         &mut self,
         space: Arc<KitsuneSpace>,
         agent: Arc<KitsuneAgent>,
+        arc: Option<DhtArc>,
     ) -> KitsuneP2pHandlerResult<()> {
+        self.check_accepting()?;
         let internal_sender = self.internal_sender.clone();
         let space2 = space.clone();
         let this_addr = self.this_addr.clone();
         let ep_hnd = self.ep_hnd.clone();
+        let connections = self.connections.clone();
         let config = Arc::clone(&self.config);
+        let rejoin_policy = self.rejoin_policy_recv.clone();
+        let gossip_config = self.gossip_config_recv.clone();
+        let bootstrap_services = self.bootstrap_services_recv.clone();
+        let space_config = self.space_configs.get(&space).cloned().unwrap_or_default();
         let space_sender = match self.spaces.entry(space.clone()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => entry.insert(AsyncLazy::new(async move {
-                let (send, evt_recv) = spawn_space(space2, this_addr, ep_hnd, config)
-                    .await
-                    .expect("cannot fail to create space");
+                let (send, evt_recv) = spawn_space(
+                    space2,
+                    this_addr,
+                    ep_hnd,
+                    connections,
+                    config,
+                    space_config,
+                    rejoin_policy,
+                    gossip_config,
+                    bootstrap_services,
+                )
+                .await
+                .expect("cannot fail to create space");
                 internal_sender
                     .register_space_event_handler(evt_recv)
                     .await
@@ -551,28 +907,111 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
             })),
         };
         let space_sender = space_sender.get();
-        Ok(async move { space_sender.await.join(space, agent).await }
-            .boxed()
-            .into())
+        Ok(async move {
+            space_sender.await.join(space, agent, arc).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
     }
 
     fn handle_leave(
         &mut self,
         space: Arc<KitsuneSpace>,
         agent: Arc<KitsuneAgent>,
+        announce: bool,
     ) -> KitsuneP2pHandlerResult<()> {
         let space_sender = match self.spaces.get_mut(&space) {
             None => return Ok(async move { Ok(()) }.boxed().into()),
             Some(space) => space.get(),
         };
         Ok(async move {
-            space_sender.await.leave(space.clone(), agent).await?;
+            space_sender
+                .await
+                .leave(space.clone(), agent, announce)
+                .await?;
             Ok(())
         }
         .boxed()
         .into())
     }
 
+    fn handle_get_agent_info(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+    ) -> KitsuneP2pHandlerResult<crate::types::agent_store::AgentInfoSigned> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(
+            async move { space_sender.await.get_agent_info(space, agent).await }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_list_joined(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<Vec<Arc<KitsuneAgent>>> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Ok(async move { Ok(Vec::new()) }.boxed().into()),
+            Some(space) => space.get(),
+        };
+        Ok(async move { space_sender.await.list_joined(space).await }
+            .boxed()
+            .into())
+    }
+
+    fn handle_peer_latencies(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<Vec<(Arc<KitsuneAgent>, std::time::Duration)>> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Ok(async move { Ok(Vec::new()) }.boxed().into()),
+            Some(space) => space.get(),
+        };
+        Ok(
+            async move { space_sender.await.peer_latencies(space).await }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_gossip_stats(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<GossipStats> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Ok(async move { Ok(GossipStats::default()) }.boxed().into()),
+            Some(space) => space.get(),
+        };
+        Ok(async move { space_sender.await.gossip_stats(space).await }
+            .boxed()
+            .into())
+    }
+
+    fn handle_ping(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        timeout_ms: Option<u64>,
+    ) -> KitsuneP2pHandlerResult<Option<std::time::Duration>> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(
+            async move { space_sender.await.ping(space, agent, timeout_ms).await }
+                .boxed()
+                .into(),
+        )
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -581,16 +1020,75 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         payload: Vec<u8>,
         timeout_ms: Option<u64>,
     ) -> KitsuneP2pHandlerResult<Vec<u8>> {
+        self.check_accepting()?;
         let space_sender = match self.spaces.get_mut(&space) {
             None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
             Some(space) => space.get(),
         };
-        Ok(async move {
+        self.check_message_size(payload.len())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.rpc_single);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let space_sender = space_sender.await;
+            drop(queued);
             space_sender
-                .await
                 .rpc_single(space, to_agent, from_agent, payload, timeout_ms)
                 .await
-        }
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_rpc_single_with_fallback(
+        &mut self,
+        input: actor::RpcSingleWithFallback,
+    ) -> KitsuneP2pHandlerResult<Vec<u8>> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.rpc_single);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let space_sender = space_sender.await;
+            drop(queued);
+            space_sender.rpc_single_with_fallback(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_notify_single(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        to_agent: Arc<KitsuneAgent>,
+        from_agent: Arc<KitsuneAgent>,
+        payload: Vec<u8>,
+        timeout_ms: Option<u64>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(payload.len())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.notify_single);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let space_sender = space_sender.await;
+            drop(queued);
+            space_sender
+                .notify_single(space, to_agent, from_agent, payload, timeout_ms)
+                .await
+        })
         .boxed()
         .into())
     }
@@ -600,21 +1098,189 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         &mut self,
         input: actor::RpcMulti,
     ) -> KitsuneP2pHandlerResult<Vec<actor::RpcMultiResponse>> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.rpc_multi);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let outbound_queue = self.outbound_queue.clone();
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let space_sender = space_sender.await;
+            let _permit = outbound_queue.acquire(input.priority).await;
+            drop(queued);
+            space_sender.rpc_multi(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_rpc_multi_stream(
+        &mut self,
+        input: actor::RpcMulti,
+    ) -> KitsuneP2pHandlerResult<actor::RpcMultiStream> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        Ok(
+            async move { space_sender.await.rpc_multi_stream(input).await }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_notify_multi(&mut self, input: actor::NotifyMulti) -> KitsuneP2pHandlerResult<u8> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.notify_multi);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let outbound_queue = self.outbound_queue.clone();
+        let jitter = notify_jitter(self.notify_jitter_max);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            tokio::time::sleep(jitter).await;
+            let space_sender = space_sender.await;
+            let _permit = outbound_queue.acquire(input.priority).await;
+            drop(queued);
+            space_sender.notify_multi(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_try_rpc_multi(
+        &mut self,
+        input: actor::RpcMulti,
+    ) -> KitsuneP2pHandlerResult<Vec<actor::RpcMultiResponse>> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        let permit = self
+            .outbound_queue
+            .try_acquire()
+            .ok_or(KitsuneP2pError::WouldBlock)?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.rpc_multi);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let _permit = permit;
+            space_sender.await.rpc_multi(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_try_notify_multi(
+        &mut self,
+        input: actor::NotifyMulti,
+    ) -> KitsuneP2pHandlerResult<u8> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        let permit = self
+            .outbound_queue
+            .try_acquire()
+            .ok_or(KitsuneP2pError::WouldBlock)?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.notify_multi);
+        let jitter = notify_jitter(self.notify_jitter_max);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let _permit = permit;
+            tokio::time::sleep(jitter).await;
+            space_sender.await.notify_multi(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_notify_multi_detailed(
+        &mut self,
+        input: actor::NotifyMulti,
+    ) -> KitsuneP2pHandlerResult<Vec<Arc<KitsuneAgent>>> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.payload.len())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.notify_multi);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let outbound_queue = self.outbound_queue.clone();
+        let jitter = notify_jitter(self.notify_jitter_max);
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            tokio::time::sleep(jitter).await;
+            let space_sender = space_sender.await;
+            let _permit = outbound_queue.acquire(input.priority).await;
+            drop(queued);
+            space_sender.notify_multi_detailed(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_publish_ops(&mut self, input: actor::PublishOps) -> KitsuneP2pHandlerResult<u32> {
+        self.check_accepting()?;
+        let space_sender = match self.spaces.get_mut(&input.space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
+            Some(space) => space.get(),
+        };
+        self.check_message_size(input.ops.iter().map(|(_, data)| data.len()).sum())?;
+        let in_flight = PendingGuard::new(&self.pending, |p| &p.notify_multi);
+        let queued = PendingGuard::new(&self.pending, |p| &p.queued);
+        let outbound_queue = self.outbound_queue.clone();
+        let shutdown = self.shutdown_recv.clone();
+        Ok(or_shutdown(shutdown, async move {
+            let _in_flight = in_flight;
+            let space_sender = space_sender.await;
+            let _permit = outbound_queue.acquire(input.priority).await;
+            drop(queued);
+            space_sender.publish_ops(input).await
+        })
+        .boxed()
+        .into())
+    }
+
+    fn handle_gossip_hashes(
+        &mut self,
+        input: actor::Gossip,
+    ) -> KitsuneP2pHandlerResult<actor::GossipResponse> {
+        self.check_accepting()?;
         let space_sender = match self.spaces.get_mut(&input.space) {
             None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
             Some(space) => space.get(),
         };
-        Ok(async move { space_sender.await.rpc_multi(input).await }
+        Ok(async move { space_sender.await.gossip_hashes(input).await }
             .boxed()
             .into())
     }
 
-    fn handle_notify_multi(&mut self, input: actor::NotifyMulti) -> KitsuneP2pHandlerResult<u8> {
+    fn handle_close(&mut self, input: actor::Close) -> KitsuneP2pHandlerResult<()> {
         let space_sender = match self.spaces.get_mut(&input.space) {
             None => return Err(KitsuneP2pError::RoutingSpaceError(input.space)),
             Some(space) => space.get(),
         };
-        Ok(async move { space_sender.await.notify_multi(input).await }
+        Ok(async move { space_sender.await.close(input).await }
             .boxed()
             .into())
     }
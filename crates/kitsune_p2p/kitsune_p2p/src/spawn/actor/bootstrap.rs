@@ -1,6 +1,7 @@
 use crate::types::agent_store::AgentInfoSigned;
 use crate::types::KitsuneBinType;
 use crate::types::KitsuneSpace;
+use ghost_actor::dependencies::tracing;
 use once_cell::sync::Lazy;
 use once_cell::sync::OnceCell;
 use std::convert::TryFrom;
@@ -142,8 +143,33 @@ pub async fn now_once(url: Option<Url2>) -> crate::types::actor::KitsuneP2pResul
     }
 }
 
+/// `put` to every bootstrap service in `urls`, so that peers using any of them can find us.
+///
+/// Succeeds if any service accepted the agent info (or there are no services),
+/// otherwise returns the error from the first service.
+pub async fn put_any(
+    urls: Vec<Url2>,
+    agent_info_signed: crate::types::agent_store::AgentInfoSigned,
+) -> crate::types::actor::KitsuneP2pResult<()> {
+    let mut first_err = None;
+    let mut any_ok = urls.is_empty();
+    for url in urls {
+        match put(Some(url.clone()), agent_info_signed.clone()).await {
+            Ok(()) => any_ok = true,
+            Err(e) => {
+                tracing::warn!(msg = "Failed to put agent info to bootstrap", ?url, ?e);
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    match first_err {
+        Some(e) if !any_ok => Err(e),
+        _ => Ok(()),
+    }
+}
+
 /// Struct to be encoded for the `random` op.
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
 pub struct RandomQuery {
     // The space to get random agents from.
     pub space: Arc<KitsuneSpace>,
@@ -162,7 +188,7 @@ impl Default for RandomQuery {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, derive_more::From, derive_more::Into)]
+#[derive(Clone, serde::Deserialize, serde::Serialize, derive_more::From, derive_more::Into)]
 pub struct RandomLimit(u32);
 
 impl Default for RandomLimit {
@@ -198,6 +224,30 @@ pub async fn random(
     Ok(ret?)
 }
 
+/// `random` from the first bootstrap service in `urls` that responds.
+///
+/// Returns an empty list if there are no services, and the error from the
+/// last service tried if none of them could be reached.
+pub async fn random_any(
+    urls: Vec<Url2>,
+    query: RandomQuery,
+) -> crate::types::actor::KitsuneP2pResult<Vec<AgentInfoSigned>> {
+    let mut last_err = None;
+    for url in urls {
+        match random(Some(url.clone()), query.clone()).await {
+            Ok(list) => return Ok(list),
+            Err(e) => {
+                tracing::warn!(msg = "Failed to get peers from bootstrap", ?url, ?e);
+                last_err = Some(e);
+            }
+        }
+    }
+    match last_err {
+        Some(e) => Err(e),
+        None => Ok(Vec::new()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,142 @@
+use super::*;
+use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
+use std::sync::Mutex;
+
+/// The transport connections open on the endpoint, in order of last use.
+/// When there are more than `limit`, the least recently used connections
+/// are closed. A closed connection is re-established by the next
+/// `get_connection` to its url, so callers never see the eviction.
+pub(crate) struct ConnectionLru {
+    inner: Mutex<ConnectionLruInner>,
+}
+
+struct ConnectionLruInner {
+    /// 0 means no limit.
+    limit: usize,
+    next_use: u64,
+    last_used: HashMap<TxUrl, u64>,
+}
+
+impl ConnectionLruInner {
+    /// Drop the least recently used connections until we are within the limit.
+    fn evict(&mut self) -> Vec<TxUrl> {
+        if self.limit == 0 || self.last_used.len() <= self.limit {
+            return Vec::new();
+        }
+        let mut by_use = self
+            .last_used
+            .iter()
+            .map(|(url, last_used)| (*last_used, url.clone()))
+            .collect::<Vec<_>>();
+        by_use.sort_unstable();
+        let evicted = by_use
+            .into_iter()
+            .take(self.last_used.len() - self.limit)
+            .map(|(_, url)| url)
+            .collect::<Vec<_>>();
+        for url in &evicted {
+            self.last_used.remove(url);
+        }
+        evicted
+    }
+}
+
+impl ConnectionLru {
+    /// A limit of 0 keeps every connection open.
+    pub fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            inner: Mutex::new(ConnectionLruInner {
+                limit,
+                next_use: 0,
+                last_used: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Change the limit, returning the connections to close to get within it.
+    pub fn set_limit(&self, limit: usize) -> Vec<TxUrl> {
+        let mut inner = self.inner.lock().expect("connection lru poisoned");
+        inner.limit = limit;
+        inner.evict()
+    }
+
+    /// Record a use of the connection to `url`, returning the least
+    /// recently used connections to close if this takes us over the limit.
+    pub fn touch(&self, url: TxUrl) -> Vec<TxUrl> {
+        let mut inner = self.inner.lock().expect("connection lru poisoned");
+        let next_use = inner.next_use;
+        inner.next_use += 1;
+        inner.last_used.insert(url, next_use);
+        inner.evict()
+    }
+
+    /// Forget a connection that has been closed.
+    pub fn remove(&self, url: &TxUrl) {
+        self.inner
+            .lock()
+            .expect("connection lru poisoned")
+            .last_used
+            .remove(url);
+    }
+
+    /// Get a connection from the endpoint, recording the use,
+    /// and closing any connections it evicts.
+    pub fn get_connection<U: Into<TxUrl>>(
+        self: &Arc<Self>,
+        ep_hnd: &Tx2EpHnd<wire::Wire>,
+        remote: U,
+        timeout: KitsuneTimeout,
+    ) -> impl std::future::Future<Output = KitsuneResult<Tx2ConHnd<wire::Wire>>> + 'static + Send
+    {
+        let remote = remote.into();
+        let this = self.clone();
+        let ep_hnd = ep_hnd.clone();
+        let con_fut = ep_hnd.get_connection(remote.clone(), timeout);
+        async move {
+            let con_hnd = con_fut.await?;
+            close_evicted(&ep_hnd, this.touch(remote));
+            Ok(con_hnd)
+        }
+    }
+}
+
+/// Close connections evicted from a `ConnectionLru`, without waiting on them.
+pub(crate) fn close_evicted(ep_hnd: &Tx2EpHnd<wire::Wire>, evicted: Vec<TxUrl>) {
+    for url in evicted {
+        tracing::debug!(%url, "closing least recently used connection");
+        tokio::task::spawn(ep_hnd.close_connection(url, 0, "connection limit reached"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connection_lru_evicts_past_limit() {
+        let lru = ConnectionLru::new(2);
+        let url = |n: u8| -> TxUrl { url2::url2!("kitsune-mem://{}", n).into() };
+
+        assert!(lru.touch(url(1)).is_empty());
+        assert!(lru.touch(url(2)).is_empty());
+        // using 1 again makes 2 the least recently used
+        assert!(lru.touch(url(1)).is_empty());
+        assert_eq!(vec![url(2)], lru.touch(url(3)));
+
+        // an evicted connection can be re-established, evicting the next
+        assert_eq!(vec![url(1)], lru.touch(url(2)));
+
+        // closed connections don't count toward the limit
+        lru.remove(&url(3));
+        assert!(lru.touch(url(4)).is_empty());
+
+        // lowering the limit evicts straight away
+        assert_eq!(vec![url(2)], lru.set_limit(1));
+
+        // 0 is no limit
+        assert!(lru.set_limit(0).is_empty());
+        for n in 5..10 {
+            assert!(lru.touch(url(n)).is_empty());
+        }
+    }
+}
@@ -1,8 +1,10 @@
 #![allow(dead_code)]
 use super::*;
-use crate::agent_store::AgentInfo;
+use crate::agent_store::{AgentInfo, AgentInfoSigned};
+use crate::types::actor::AgentSelection;
 use ghost_actor::dependencies::must_future::MustBoxFuture;
-use std::collections::HashSet;
+use kitsune_p2p_types::dht_arc::shortest_arc_distance;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 
 /// This enum represents the outcomes from peer discovery
@@ -18,6 +20,74 @@ pub(crate) enum PeerDiscoverResult {
     Err(KitsuneP2pError),
 }
 
+/// pick the url to dial for an agent from its signed agent info.
+/// unless `verify_remote_identity` is disabled, the info must be about the
+/// agent we expect to reach, in this space, and signed by that agent's own
+/// key, so no peer can advertise its url under another agent's identity
+pub(crate) async fn verified_url(
+    transport_security: &TransportSecurityConfig,
+    space: &KitsuneSpace,
+    to_agent: &KitsuneAgent,
+    agent_info_signed: &AgentInfoSigned,
+) -> KitsuneP2pResult<url2::Url2> {
+    let info = AgentInfo::try_from(agent_info_signed)?;
+    if transport_security.verify_remote_identity {
+        let verified = agent_info_signed.as_agent_ref() == to_agent
+            && info.as_agent_ref() == to_agent
+            && info.as_space_ref() == space
+            && agent_info_signed.verify_signature().await?;
+        if !verified {
+            tracing::warn!(?to_agent, "remote identity mismatch");
+            return Err("remote identity mismatch".into());
+        }
+    }
+    info.as_urls_ref()
+        .get(0)
+        .cloned()
+        .ok_or_else(|| "no url".into())
+}
+
+/// the tls certificate a connection to `url` presents must be the one
+/// whose digest `url` names
+pub(crate) fn check_peer_cert(url: &url2::Url2, peer_cert: &Tx2Cert) -> KitsuneP2pResult<()> {
+    let expected_cert: Tx2Cert = ProxyUrl::from_full(url.as_str())
+        .map_err(KitsuneP2pError::other)?
+        .digest()
+        .into();
+    if peer_cert != &expected_cert {
+        tracing::warn!(?peer_cert, ?expected_cert, "remote identity mismatch");
+        return Err("remote identity mismatch".into());
+    }
+    Ok(())
+}
+
+/// connect to an agent at the url in its signed agent info. unless
+/// `verify_remote_identity` is disabled, the connection is refused if the
+/// info doesn't verify (see `verified_url`), or if the remote presents a
+/// certificate other than the one the signed url names
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn connect_verified(
+    ep_hnd: &Tx2EpHnd<wire::Wire>,
+    connections: &Arc<ConnectionLru>,
+    transport_security: &TransportSecurityConfig,
+    space: &KitsuneSpace,
+    to_agent: &KitsuneAgent,
+    agent_info_signed: AgentInfoSigned,
+    timeout: KitsuneTimeout,
+) -> KitsuneP2pResult<(url2::Url2, Tx2ConHnd<wire::Wire>)> {
+    let url = verified_url(transport_security, space, to_agent, &agent_info_signed).await?;
+    let con_hnd = connections
+        .get_connection(ep_hnd, url.clone(), timeout)
+        .await?;
+    if transport_security.verify_remote_identity {
+        if let Err(err) = check_peer_cert(&url, &con_hnd.peer_cert()) {
+            con_hnd.close(500, "remote identity mismatch").await;
+            return Err(err);
+        }
+    }
+    Ok((url, con_hnd))
+}
+
 /// attempt to establish a connection to another peer within given timeout
 pub(crate) fn peer_discover(
     space: &mut Space,
@@ -31,12 +101,23 @@ pub(crate) fn peer_discover(
     let i_s = space.i_s.clone();
     let evt_sender = space.evt_sender.clone();
     let ep_hnd = space.ep_hnd.clone();
-    let bootstrap_service = space.config.bootstrap_service.clone();
+    let connections = space.connections.clone();
+    let bootstrap_services = space.bootstrap_services.borrow().clone();
+    let transport_security = space.config.transport_security.clone();
     let space = space.space.clone();
     async move {
         // run tx.create_channel an conver success result into our return type
-        let try_connect = |url: url2::Url2| async {
-            let con_hnd = ep_hnd.get_connection(url.clone(), timeout).await?;
+        let try_connect = |info: AgentInfoSigned| async {
+            let (url, con_hnd) = connect_verified(
+                &ep_hnd,
+                &connections,
+                &transport_security,
+                &space,
+                &to_agent,
+                info,
+                timeout,
+            )
+            .await?;
             KitsuneP2pResult::Ok(PeerDiscoverResult::OkRemote { url, con_hnd })
         };
 
@@ -59,44 +140,123 @@ pub(crate) fn peer_discover(
                 })
                 .await?
             {
-                let info = types::agent_store::AgentInfo::try_from(&info)?;
-                let url = info
-                    .as_urls_ref()
-                    .get(0)
-                    .ok_or_else(|| KitsuneP2pError::from("no url"))?
-                    .clone();
-                return try_connect(url).await;
+                return try_connect(info).await;
             }
 
             KitsuneP2pResult::Err("failed to connect".into())
         };
 
         let check_network = || async {
-            let nodes = get_5_or_less_non_local_agents_near_basis(
+            let req_info = search_remote_agent_info(
                 space.clone(),
                 from_agent.clone(),
-                Arc::new(KitsuneBasis(to_agent.to_vec())),
+                to_agent.clone(),
                 i_s.clone(),
                 evt_sender.clone(),
-                bootstrap_service.clone(),
+                ep_hnd.clone(),
+                connections.clone(),
+                transport_security.clone(),
+                bootstrap_services.clone(),
+                timeout,
             )
             .await?;
 
-            // make an AgentInfoQuery request to the returned agents
-            // return the first one to sucessfully return a result
-            let (req_info, _) = futures::future::select_ok(nodes.into_iter().take(3).map(|info| {
+            // we got a result, try to connect to it
+            try_connect(req_info).await
+        };
+
+        let start_time = std::time::Instant::now();
+        let mut interval_ms = 50;
+
+        loop {
+            if let Ok(res) = check_local().await {
+                return res;
+            }
+
+            if let Ok(res) = check_peer_store().await {
+                return res;
+            }
+
+            let no_peers = match check_network().await {
+                Ok(res) => return res,
+                Err(e) => matches!(e, KitsuneP2pError::NoPeersFound),
+            };
+
+            let elapsed_ms = start_time.elapsed().as_millis() as u64;
+            if elapsed_ms >= timeout_ms {
+                // distinguish having no one to ask from no one answering
+                return PeerDiscoverResult::Err(if no_peers {
+                    KitsuneP2pError::NoPeersFound
+                } else {
+                    KitsuneP2pError::Timeout
+                });
+            }
+
+            interval_ms *= 2;
+            if interval_ms > timeout_ms - elapsed_ms {
+                interval_ms = timeout_ms - elapsed_ms;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(interval_ms)).await;
+        }
+    }
+    .boxed()
+    .into()
+}
+
+/// ask a few remote nodes near the agent for its signed agent info,
+/// storing the first matching result in our peer store
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn search_remote_agent_info(
+    space: Arc<KitsuneSpace>,
+    from_agent: Arc<KitsuneAgent>,
+    to_agent: Arc<KitsuneAgent>,
+    i_s: ghost_actor::GhostSender<SpaceInternal>,
+    evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    ep_hnd: Tx2EpHnd<wire::Wire>,
+    connections: Arc<ConnectionLru>,
+    transport_security: TransportSecurityConfig,
+    bootstrap_services: Vec<url2::Url2>,
+    timeout: KitsuneTimeout,
+) -> MustBoxFuture<'static, KitsuneP2pResult<AgentInfoSigned>> {
+    async move {
+        let nodes = get_5_or_less_non_local_agents_near_basis(
+            space.clone(),
+            from_agent.clone(),
+            Arc::new(KitsuneBasis(to_agent.to_vec())),
+            i_s,
+            evt_sender.clone(),
+            bootstrap_services,
+        )
+        .await?;
+
+        // select_ok panics on an empty list
+        if nodes.is_empty() {
+            return Err("no remote nodes to query".into());
+        }
+
+        // make an AgentInfoQuery request to the returned agents
+        // return the first one to sucessfully return a result
+        let (req_info, _) =
+            futures::future::select_ok(nodes.into_iter().take(3).map(|(info, info_signed)| {
                 // grr we need to move info in but not everything else...
                 // thus, we have to shadow all these with references
                 let ep_hnd = &ep_hnd;
+                let connections = &connections;
+                let transport_security = &transport_security;
                 let space = &space;
                 let to_agent = &to_agent;
                 async move {
-                    let url = info
-                        .as_urls_ref()
-                        .get(0)
-                        .ok_or_else(|| KitsuneP2pError::from("no url"))?
-                        .clone();
-                    let con_hnd = ep_hnd.get_connection(url, timeout).await?;
+                    let (_, con_hnd) = connect_verified(
+                        ep_hnd,
+                        connections,
+                        transport_security,
+                        space,
+                        info.as_agent_ref(),
+                        info_signed,
+                        timeout,
+                    )
+                    .await?;
 
                     // write the query request
                     let msg = wire::Wire::agent_info_query(
@@ -109,14 +269,13 @@ pub(crate) fn peer_discover(
 
                     match res {
                         wire::Wire::AgentInfoQueryResp(wire::AgentInfoQueryResp {
-                            mut agent_infos,
+                            agent_infos,
                         }) => {
-                            if agent_infos.is_empty() {
-                                Err("failed to connect".into())
-                            } else {
-                                // if we have a result, return it
-                                Ok(agent_infos.remove(0))
-                            }
+                            // only accept info that is actually about the requested agent
+                            agent_infos
+                                .into_iter()
+                                .find(|i| i.as_agent_ref() == &**to_agent)
+                                .ok_or_else(|| "failed to connect".into())
                         }
                         _ => KitsuneP2pResult::Err("failed to connect".into()),
                     }
@@ -125,53 +284,16 @@ pub(crate) fn peer_discover(
             }))
             .await?;
 
-            // we got a result - let's add it to our store for the future
-            let _ = evt_sender
-                .put_agent_info_signed(PutAgentInfoSignedEvt {
-                    space: space.clone(),
-                    agent: from_agent.clone(),
-                    agent_info_signed: req_info.clone(),
-                })
-                .await;
-
-            // we got a result, try to connect to it
-            let info = types::agent_store::AgentInfo::try_from(&req_info)?;
-            let url = info
-                .as_urls_ref()
-                .get(0)
-                .ok_or_else(|| KitsuneP2pError::from("no url"))?
-                .clone();
-            try_connect(url).await
-        };
-
-        let start_time = std::time::Instant::now();
-        let mut interval_ms = 50;
-
-        loop {
-            if let Ok(res) = check_local().await {
-                return res;
-            }
-
-            if let Ok(res) = check_peer_store().await {
-                return res;
-            }
-
-            if let Ok(res) = check_network().await {
-                return res;
-            }
-
-            let elapsed_ms = start_time.elapsed().as_millis() as u64;
-            if elapsed_ms >= timeout_ms {
-                return PeerDiscoverResult::Err("timeout".into());
-            }
-
-            interval_ms *= 2;
-            if interval_ms > timeout_ms - elapsed_ms {
-                interval_ms = timeout_ms - elapsed_ms;
-            }
+        // we got a result - let's add it to our store for the future
+        let _ = evt_sender
+            .put_agent_info_signed(PutAgentInfoSignedEvt {
+                space,
+                agent: from_agent,
+                agent_info_signed: req_info.clone(),
+            })
+            .await;
 
-            tokio::time::sleep(std::time::Duration::from_millis(interval_ms)).await;
-        }
+        Ok(req_info)
     }
     .boxed()
     .into()
@@ -183,10 +305,10 @@ pub(crate) fn message_neighborhood<T, F>(
     space: &mut Space,
     from_agent: Arc<KitsuneAgent>,
     target_node_count: u8,
+    max_node_count: usize,
     stage_1_timeout_if_any_ms: u64,
     stage_2_timeout_even_if_none_ms: u64,
-    // ignored while full-sync
-    _basis: Arc<KitsuneBasis>,
+    basis: Arc<KitsuneBasis>,
     payload: wire::Wire,
     accept_result_cb: F,
 ) -> MustBoxFuture<'static, Vec<T>>
@@ -201,7 +323,10 @@ where
     let i_s = space.i_s.clone();
     let evt_sender = space.evt_sender.clone();
     let ep_hnd = space.ep_hnd.clone();
-    let bootstrap_service = space.config.bootstrap_service.clone();
+    let connections = space.connections.clone();
+    let bootstrap_services = space.bootstrap_services.borrow().clone();
+    let transport_security = space.config.transport_security.clone();
+    let peer_latencies = space.peer_latencies.clone();
     let space = space.space.clone();
     let accept_result_cb = Arc::new(accept_result_cb);
     async move {
@@ -238,37 +363,60 @@ where
             if let Ok(nodes) = get_5_or_less_non_local_agents_near_basis(
                 space.clone(),
                 from_agent.clone(),
-                _basis.clone(),
+                basis.clone(),
                 i_s.clone(),
                 evt_sender.clone(),
-                bootstrap_service.clone(),
+                bootstrap_services.clone(),
             )
             .await
             {
-                for node in nodes {
+                for (node, node_signed) in nodes {
+                    if sent_to.len() >= max_node_count {
+                        break;
+                    }
                     let to_agent = Arc::new(node.as_agent_ref().clone());
                     if !sent_to.contains(&to_agent) {
                         sent_to.insert(to_agent.clone());
-                        let url = match node.as_urls_ref().get(0) {
-                            None => continue,
-                            Some(url) => url.clone(),
-                        };
-                        let fut = ep_hnd.get_connection(url, timeout_even_if_none);
+                        if node.as_urls_ref().is_empty() {
+                            continue;
+                        }
+                        let space = space.clone();
+                        let ep_hnd = ep_hnd.clone();
+                        let connections = connections.clone();
+                        let transport_security = transport_security.clone();
+                        let peer_latencies = peer_latencies.clone();
                         let mut payload = payload.clone();
                         let accept_result_cb = accept_result_cb.clone();
                         let out = out.clone();
                         tokio::task::spawn(async move {
-                            let con_hnd = fut.await?;
+                            let (_, con_hnd) = connect_verified(
+                                &ep_hnd,
+                                &connections,
+                                &transport_security,
+                                &space,
+                                &to_agent,
+                                node_signed,
+                                timeout_even_if_none,
+                            )
+                            .await?;
                             match &mut payload {
                                 wire::Wire::Notify(n) => {
                                     n.to_agent = to_agent.clone();
                                 }
+                                wire::Wire::NotifyWithId(n) => {
+                                    n.to_agent = to_agent.clone();
+                                }
                                 wire::Wire::Call(c) => {
                                     c.to_agent = to_agent.clone();
                                 }
+                                wire::Wire::Gossip(g) => {
+                                    g.to_agent = to_agent.clone();
+                                }
                                 _ => panic!("cannot message {:?}", payload),
                             }
+                            let start = std::time::Instant::now();
                             let res = con_hnd.request(&payload, timeout_even_if_none).await?;
+                            peer_latencies.record(to_agent.clone(), start.elapsed());
                             if let Ok(res) = accept_result_cb(to_agent, res) {
                                 out.lock().await.push(res);
                             }
@@ -300,18 +448,47 @@ where
     .into()
 }
 
-/// search for agents to contact
+/// Pick the agents to answer an rpc_multi from the candidates.
+/// An agent's arc is centered on its own location, so `NearestBasis`
+/// keeps the `count` agents located nearest the basis, nearest first.
+/// `Any` keeps every candidate, in the order given.
+pub(crate) fn select_agents(
+    selection: AgentSelection,
+    basis: &KitsuneBasis,
+    count: usize,
+    mut agents: Vec<Arc<KitsuneAgent>>,
+) -> Vec<Arc<KitsuneAgent>> {
+    match selection {
+        AgentSelection::Any => (),
+        AgentSelection::NearestBasis => {
+            let loc = basis.get_loc();
+            agents.sort_by_key(|agent| shortest_arc_distance(loc, agent.get_loc()));
+            agents.truncate(count);
+        }
+    }
+    agents
+}
+
+/// Could this peer be holding data at the basis location, according to the
+/// storage arc it announced? Peers that didn't announce an arc are assumed
+/// to hold everything.
+fn peer_covers_basis(info: &AgentInfo, basis: &KitsuneBasis) -> bool {
+    info.dht_arc()
+        .map(|arc| arc.contains(basis.get_loc()))
+        .unwrap_or(true)
+}
+
+/// search for agents whose storage arc covers the basis to contact
 pub(crate) fn get_5_or_less_non_local_agents_near_basis(
     space: Arc<KitsuneSpace>,
     from_agent: Arc<KitsuneAgent>,
-    // ignored while full-sync
-    _basis: Arc<KitsuneBasis>,
+    basis: Arc<KitsuneBasis>,
     i_s: ghost_actor::GhostSender<SpaceInternal>,
     evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
-    bootstrap_service: Option<url2::Url2>,
-) -> MustBoxFuture<'static, KitsuneP2pResult<HashSet<AgentInfo>>> {
+    bootstrap_services: Vec<url2::Url2>,
+) -> MustBoxFuture<'static, KitsuneP2pResult<HashMap<AgentInfo, AgentInfoSigned>>> {
     async move {
-        let mut out = HashSet::new();
+        let mut out = HashMap::new();
 
         if let Ok(mut list) = evt_sender
             .query_agent_info_signed(QueryAgentInfoSignedEvt {
@@ -328,8 +505,8 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
                         .await
                     {
-                        if !is_local {
-                            out.insert(info);
+                        if !is_local && peer_covers_basis(&info, &basis) {
+                            out.insert(info, item);
                         }
                     }
                 }
@@ -339,8 +516,8 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
             }
         }
 
-        if let Ok(list) = super::bootstrap::random(
-            bootstrap_service,
+        if let Ok(list) = super::bootstrap::random_any(
+            bootstrap_services,
             super::bootstrap::RandomQuery {
                 space: space.clone(),
                 // grap a couple extra incase they happen to be local
@@ -365,7 +542,9 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
                                     agent_info_signed: item.clone(),
                                 })
                                 .await;
-                            out.insert(info);
+                            if peer_covers_basis(&info, &basis) {
+                                out.insert(info, item);
+                            }
                         }
                     }
                 }
@@ -376,7 +555,7 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
         }
 
         if out.is_empty() {
-            return Err("could not find any peers".into());
+            return Err(KitsuneP2pError::NoPeersFound);
         }
 
         Ok(out)
@@ -385,40 +564,40 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
     .into()
 }
 
+/// seed our peer store from the first bootstrap service that responds,
+/// failing if none of them could be reached
 pub(crate) fn add_5_or_less_non_local_agents(
     space: Arc<KitsuneSpace>,
     from_agent: Arc<KitsuneAgent>,
     i_s: ghost_actor::GhostSender<SpaceInternal>,
     evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
-    bootstrap_service: url2::Url2,
+    bootstrap_services: Vec<url2::Url2>,
 ) -> MustBoxFuture<'static, KitsuneP2pResult<()>> {
     async move {
-        if let Ok(list) = super::bootstrap::random(
-            Some(bootstrap_service),
+        let list = super::bootstrap::random_any(
+            bootstrap_services,
             super::bootstrap::RandomQuery {
                 space: space.clone(),
                 limit: 8.into(),
             },
         )
-        .await
-        {
-            for item in list {
-                // TODO - someday some validation here
-                if let Ok(info) = AgentInfo::try_from(&item) {
-                    if let Ok(is_local) = i_s
-                        .is_agent_local(Arc::new(info.as_agent_ref().clone()))
-                        .await
-                    {
-                        if !is_local {
-                            // we got a result - let's add it to our store for the future
-                            let _ = evt_sender
-                                .put_agent_info_signed(PutAgentInfoSignedEvt {
-                                    space: space.clone(),
-                                    agent: from_agent.clone(),
-                                    agent_info_signed: item.clone(),
-                                })
-                                .await;
-                        }
+        .await?;
+        for item in list {
+            // TODO - someday some validation here
+            if let Ok(info) = AgentInfo::try_from(&item) {
+                if let Ok(is_local) = i_s
+                    .is_agent_local(Arc::new(info.as_agent_ref().clone()))
+                    .await
+                {
+                    if !is_local {
+                        // we got a result - let's add it to our store for the future
+                        let _ = evt_sender
+                            .put_agent_info_signed(PutAgentInfoSignedEvt {
+                                space: space.clone(),
+                                agent: from_agent.clone(),
+                                agent_info_signed: item.clone(),
+                            })
+                            .await;
                     }
                 }
             }
@@ -428,3 +607,123 @@ pub(crate) fn add_5_or_less_non_local_agents(
     .boxed()
     .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kitsune_p2p_types::codec::rmp_encode;
+    use lair_keystore_api::internal::sign_ed25519::*;
+
+    /// A 36 byte hash whose location is `loc`.
+    fn with_loc<T: KitsuneBinType>(loc: u32) -> Arc<T> {
+        let mut bytes = vec![0; 32];
+        bytes.extend_from_slice(&loc.to_le_bytes());
+        Arc::new(T::new(bytes))
+    }
+
+    #[test]
+    fn select_agents_nearest_basis() {
+        let basis: Arc<KitsuneBasis> = with_loc(1000);
+        let peers: Vec<Arc<KitsuneAgent>> = vec![
+            with_loc(u32::MAX / 2),
+            with_loc(1100),
+            with_loc(u32::MAX - 100),
+            with_loc(5000),
+            with_loc(990),
+        ];
+
+        let all = select_agents(AgentSelection::Any, &basis, 2, peers.clone());
+        assert_eq!(peers, all);
+
+        // distances wrap around the dht, so u32::MAX - 100 is
+        // only 1101 from the basis
+        let nearest = select_agents(AgentSelection::NearestBasis, &basis, 3, peers.clone());
+        assert_eq!(
+            vec![peers[4].clone(), peers[1].clone(), peers[2].clone()],
+            nearest
+        );
+
+        // asking for more agents than we know just sorts them
+        let nearest = select_agents(AgentSelection::NearestBasis, &basis, 10, peers.clone());
+        assert_eq!(5, nearest.len());
+        assert_eq!(peers[0], nearest[4]);
+    }
+
+    /// Agent info for `agent` at `url`, signed with `signer`.
+    async fn signed_info(
+        space: &KitsuneSpace,
+        agent: &KitsuneAgent,
+        signer: &SignEd25519PrivKey,
+        url: url2::Url2,
+    ) -> AgentInfoSigned {
+        let info = AgentInfo::new(space.clone(), agent.clone(), vec![url], 1000, 60_000);
+        let mut data = Vec::new();
+        rmp_encode(&mut data, &info).unwrap();
+        let sig = sign_ed25519(signer.clone(), Arc::new(data.clone()))
+            .await
+            .unwrap();
+        AgentInfoSigned::try_new(agent.clone(), (**sig).clone().into(), data).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn verified_url_binds_the_agent() {
+        let space = KitsuneSpace::new(vec![0; 36]);
+        let alice = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+        let mallory = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+        let alice_agent = KitsuneAgent::new((**alice.pub_key).clone());
+        let mallory_agent = KitsuneAgent::new((**mallory.pub_key).clone());
+        let alice_url = url2::url2!("kitsune-mem://alice");
+        let mallory_url = url2::url2!("kitsune-mem://mallory");
+        let secure = TransportSecurityConfig::default();
+        let insecure = TransportSecurityConfig::insecure();
+
+        let info = signed_info(&space, &alice_agent, &alice.priv_key, alice_url.clone()).await;
+        assert_eq!(
+            alice_url,
+            verified_url(&secure, &space, &alice_agent, &info)
+                .await
+                .unwrap()
+        );
+
+        // mallory advertising its own url as alice's is refused by default
+        let forged =
+            signed_info(&space, &alice_agent, &mallory.priv_key, mallory_url.clone()).await;
+        assert!(verified_url(&secure, &space, &alice_agent, &forged)
+            .await
+            .is_err());
+        assert_eq!(
+            mallory_url,
+            verified_url(&insecure, &space, &alice_agent, &forged)
+                .await
+                .unwrap()
+        );
+
+        // as is mallory's genuine info when we set out to reach alice
+        let mallory_info = signed_info(
+            &space,
+            &mallory_agent,
+            &mallory.priv_key,
+            mallory_url.clone(),
+        )
+        .await;
+        assert!(verified_url(&secure, &space, &alice_agent, &mallory_info)
+            .await
+            .is_err());
+        assert_eq!(
+            mallory_url,
+            verified_url(&insecure, &space, &alice_agent, &mallory_info)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn peer_cert_must_match_the_url() {
+        let digest = vec![1; 32];
+        let url = ProxyUrl::new("kitsune-quic://127.0.0.1:5778", digest.clone().into())
+            .unwrap()
+            .into_full();
+        assert!(check_peer_cert(&url, &digest.into()).is_ok());
+        assert!(check_peer_cert(&url, &vec![2; 32].into()).is_err());
+    }
+}
@@ -1,16 +1,23 @@
 //! This is a temporary quick-hack gossip module for use with the
 //! in-memory / full-sync / non-sharded networking module
 
+use crate::types::actor::GossipConfig;
+use crate::types::actor::GossipStats;
+use crate::types::actor::GossipStrategy;
 use crate::types::actor::KitsuneP2pResult;
 use crate::types::gossip::*;
 use crate::*;
 use ghost_actor::dependencies::tracing;
-use ghost_actor::GhostError;
+use kitsune_p2p_types::dht_arc::shortest_arc_distance;
 use kitsune_p2p_types::dht_arc::DhtArc;
+use rand::seq::SliceRandom;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
 
 ghost_actor::ghost_chan! {
     /// "Event" requests emitted by the gossip module
@@ -37,26 +44,71 @@ ghost_actor::ghost_chan! {
 
 pub type GossipEventReceiver = futures::channel::mpsc::Receiver<GossipEvent>;
 
+/// Counters the gossip module updates as it goes, for `gossip_stats`.
+#[derive(Default)]
+pub struct GossipCounters {
+    held_hashes: AtomicU64,
+    hashes_sent: AtomicU64,
+    hashes_received: AtomicU64,
+    /// Hashes one side was missing at the last exchange between each pair.
+    divergence: Mutex<HashMap<(Arc<KitsuneAgent>, Arc<KitsuneAgent>), u64>>,
+}
+
+impl GossipCounters {
+    pub fn stats(&self) -> GossipStats {
+        GossipStats {
+            held_hashes: self.held_hashes.load(Ordering::Relaxed),
+            hashes_sent: self.hashes_sent.load(Ordering::Relaxed),
+            hashes_received: self.hashes_received.load(Ordering::Relaxed),
+            outstanding_divergence: self
+                .divergence
+                .lock()
+                .expect("gossip counters poisoned")
+                .values()
+                .sum(),
+        }
+    }
+
+    fn set_divergence(&self, from: &Arc<KitsuneAgent>, to: &Arc<KitsuneAgent>, count: u64) {
+        self.divergence
+            .lock()
+            .expect("gossip counters poisoned")
+            .insert((from.clone(), to.clone()), count);
+    }
+}
+
 /// spawn a gossip module to control gossip for a space
-pub fn spawn_gossip_module(config: Arc<KitsuneP2pConfig>) -> GossipEventReceiver {
+pub fn spawn_gossip_module(
+    config: Arc<KitsuneP2pConfig>,
+    gossip_config: tokio::sync::watch::Receiver<GossipConfig>,
+) -> (GossipEventReceiver, Arc<GossipCounters>) {
     let (evt_send, evt_recv) = futures::channel::mpsc::channel(10);
+    let counters = Arc::new(GossipCounters::default());
 
-    tokio::task::spawn(gossip_loop(config, evt_send));
+    tokio::task::spawn(gossip_loop(
+        config,
+        gossip_config,
+        evt_send,
+        counters.clone(),
+    ));
 
-    evt_recv
+    (evt_recv, counters)
 }
 
-#[tracing::instrument(skip(config, evt_send))]
+#[tracing::instrument(skip(config, gossip_config, evt_send, counters))]
 /// the gossip module is not an actor because we want to pause while
 /// awaiting requests - not process requests in parallel.
 async fn gossip_loop(
     config: Arc<KitsuneP2pConfig>,
+    gossip_config: tokio::sync::watch::Receiver<GossipConfig>,
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
+    counters: Arc<GossipCounters>,
 ) -> KitsuneP2pResult<()> {
-    let mut gossip_data = GossipData::new(evt_send);
+    let mut gossip_data = GossipData::new(evt_send, counters);
     loop {
-        match gossip_data.take_action().await {
-            Err(KitsuneP2pError::GhostError(GhostError::Disconnected)) => {
+        let cfg = gossip_config.borrow().clone();
+        match gossip_data.take_action(&cfg).await {
+            Err(KitsuneP2pError::Shutdown) => {
                 tracing::warn!("Ghost actor is shutting down so gossip loop is exiting");
                 return Ok(());
             }
@@ -66,10 +118,10 @@ async fn gossip_loop(
             Ok(_) => (),
         }
 
-        tokio::time::sleep(std::time::Duration::from_millis(
-            config.tuning_params.gossip_loop_iteration_delay_ms as u64,
-        ))
-        .await;
+        let interval_ms = cfg
+            .interval_ms
+            .unwrap_or(config.tuning_params.gossip_loop_iteration_delay_ms as u64);
+        tokio::time::sleep(std::time::Duration::from_millis(interval_ms)).await;
     }
 }
 
@@ -77,44 +129,82 @@ struct GossipData {
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
     pending_gossip_list: Vec<(Arc<KitsuneAgent>, Arc<KitsuneAgent>)>,
     last_counts: HashMap<Arc<KitsuneAgent>, (u64, u64)>,
+    /// When we last exchanged new data with each peer.
+    last_active: HashMap<Arc<KitsuneAgent>, Instant>,
+    counters: Arc<GossipCounters>,
 }
 
 impl GossipData {
-    pub fn new(evt_send: futures::channel::mpsc::Sender<GossipEvent>) -> Self {
+    pub fn new(
+        evt_send: futures::channel::mpsc::Sender<GossipEvent>,
+        counters: Arc<GossipCounters>,
+    ) -> Self {
         Self {
             evt_send,
             pending_gossip_list: Vec::new(),
             last_counts: HashMap::new(),
+            last_active: HashMap::new(),
+            counters,
         }
     }
 
-    pub async fn take_action(&mut self) -> KitsuneP2pResult<()> {
+    pub async fn take_action(&mut self, cfg: &GossipConfig) -> KitsuneP2pResult<()> {
         if self.pending_gossip_list.is_empty() {
-            self.fetch_pending_gossip_list().await?;
+            self.fetch_pending_gossip_list(cfg).await?;
         } else {
             self.process_next_gossip().await?;
         }
         Ok(())
     }
 
-    async fn fetch_pending_gossip_list(&mut self) -> KitsuneP2pResult<()> {
+    async fn fetch_pending_gossip_list(&mut self, cfg: &GossipConfig) -> KitsuneP2pResult<()> {
         let (local_agents, remote_agents) = self.evt_send.list_neighbor_agents().await?;
         // super naive gossip just processes all combinations
         // also causes duplication because it runs pairs from both sides
         for (i, a1) in local_agents.iter().enumerate() {
-            for a2 in local_agents.iter().skip(i) {
+            let candidates = local_agents
+                .iter()
+                .skip(i)
                 // at the very least, avoid gossiping with ourselves
-                if a1 != a2 {
-                    self.pending_gossip_list.push((a1.clone(), a2.clone()));
-                }
-            }
-            for a2 in remote_agents.iter() {
-                self.pending_gossip_list.push((a1.clone(), a2.clone()));
+                .filter(|a2| *a2 != a1)
+                .chain(remote_agents.iter())
+                .cloned()
+                .collect();
+            for a2 in self.select_peers(cfg, a1, candidates) {
+                self.pending_gossip_list.push((a1.clone(), a2));
             }
         }
         Ok(())
     }
 
+    /// Pick up to `peers_per_round` of the candidates for `agent` to gossip
+    /// with this round, according to the configured strategy.
+    fn select_peers(
+        &self,
+        cfg: &GossipConfig,
+        agent: &Arc<KitsuneAgent>,
+        mut candidates: Vec<Arc<KitsuneAgent>>,
+    ) -> Vec<Arc<KitsuneAgent>> {
+        match cfg.strategy {
+            GossipStrategy::Random => candidates.shuffle(&mut rand::thread_rng()),
+            GossipStrategy::NearestArc => {
+                let loc = agent.get_loc();
+                candidates.sort_by_key(|peer| shortest_arc_distance(loc, peer.get_loc()));
+            }
+            GossipStrategy::RecentlyActive => {
+                candidates.shuffle(&mut rand::thread_rng());
+                // the sort is stable, so peers we never exchanged
+                // new data with stay in random order at the end
+                candidates
+                    .sort_by_key(|peer| std::cmp::Reverse(self.last_active.get(peer).copied()));
+            }
+        }
+        if let Some(peers_per_round) = cfg.peers_per_round {
+            candidates.truncate(peers_per_round);
+        }
+        candidates
+    }
+
     #[tracing::instrument(skip(self))]
     async fn process_next_gossip(&mut self) -> KitsuneP2pResult<()> {
         // !is_empty() checked above in take_action
@@ -159,6 +249,9 @@ impl GossipData {
             OpCount::Variance
         };
         last_count.0 = op_hashes_from.len() as u64;
+        self.counters
+            .held_hashes
+            .store(last_count.0, Ordering::Relaxed);
 
         let op_hashes_from: S = HashSet::from_iter(op_hashes_from);
         let agent_info_from: A = HashSet::from_iter(agent_info_from);
@@ -186,6 +279,7 @@ impl GossipData {
             // There's no new gossip from us or them
             // so our job is done.
             OpConsistency::Consistent => {
+                self.counters.set_divergence(&from_agent, &to_agent, 0);
                 return Ok(());
             }
         };
@@ -225,6 +319,17 @@ impl GossipData {
             tracing::debug!(to_needs_len = ?to_needs.len());
         });
 
+        // whatever isn't delivered below is still outstanding
+        let mut divergence = (from_needs.len() + to_needs.len()) as u64;
+
+        if !from_needs.is_empty()
+            || !from_needs_agents.is_empty()
+            || !to_needs.is_empty()
+            || !to_needs_agents.is_empty()
+        {
+            self.last_active.insert(to_agent.clone(), Instant::now());
+        }
+
         // fetch values that to_agent needs from from_agent
         if !to_needs.is_empty() || !to_needs_agents.is_empty() {
             if let Ok((r_ops, r_peers)) = self
@@ -238,7 +343,8 @@ impl GossipData {
                 .await
             {
                 if !r_ops.is_empty() || !r_peers.is_empty() {
-                    if let Err(e) = self
+                    let sent = r_ops.len() as u64;
+                    match self
                         .evt_send
                         .gossip_ops(GossipEvt::new(
                             from_agent.clone(),
@@ -248,9 +354,15 @@ impl GossipData {
                         ))
                         .await
                     {
-                        span.in_scope(|| {
-                            tracing::error!(gossip_failed_to_send = ?e, ?to_agent);
-                        });
+                        Ok(()) => {
+                            self.counters.hashes_sent.fetch_add(sent, Ordering::Relaxed);
+                            divergence = divergence.saturating_sub(sent);
+                        }
+                        Err(e) => {
+                            span.in_scope(|| {
+                                tracing::error!(gossip_failed_to_send = ?e, ?to_agent);
+                            });
+                        }
                     }
                 }
             }
@@ -269,7 +381,8 @@ impl GossipData {
                 .await
             {
                 if !r_ops.is_empty() || !r_peers.is_empty() {
-                    if let Err(e) = self
+                    let received = r_ops.len() as u64;
+                    match self
                         .evt_send
                         .gossip_ops(GossipEvt::new(
                             to_agent.clone(), // we fetched from to
@@ -279,14 +392,161 @@ impl GossipData {
                         ))
                         .await
                     {
-                        span.in_scope(|| {
-                            tracing::error!(gossip_failed_to_get_from = ?e, ?to_agent);
-                        });
+                        Ok(()) => {
+                            self.counters
+                                .hashes_received
+                                .fetch_add(received, Ordering::Relaxed);
+                            divergence = divergence.saturating_sub(received);
+                        }
+                        Err(e) => {
+                            span.in_scope(|| {
+                                tracing::error!(gossip_failed_to_get_from = ?e, ?to_agent);
+                            });
+                        }
                     }
                 }
             }
         }
 
+        self.counters
+            .set_divergence(&from_agent, &to_agent, divergence);
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    fn agent(loc: u8) -> Arc<KitsuneAgent> {
+        Arc::new(KitsuneAgent::new(vec![loc; 36]))
+    }
+
+    /// Run a gossip round for the given config, answering the neighbor
+    /// query with `local` and `remote`, returning the pending gossip list.
+    async fn round(
+        cfg: GossipConfig,
+        local: Vec<Arc<KitsuneAgent>>,
+        remote: Vec<Arc<KitsuneAgent>>,
+    ) -> Vec<(Arc<KitsuneAgent>, Arc<KitsuneAgent>)> {
+        let (evt_send, mut evt_recv) = futures::channel::mpsc::channel(10);
+        tokio::task::spawn(async move {
+            use futures::StreamExt;
+            while let Some(evt) = evt_recv.next().await {
+                if let GossipEvent::ListNeighborAgents { respond, .. } = evt {
+                    let agents = (local.clone(), remote.clone());
+                    respond.r(Ok(async move { Ok(agents) }.boxed().into()));
+                }
+            }
+        });
+        let mut gossip_data = GossipData::new(evt_send, Default::default());
+        gossip_data.take_action(&cfg).await.unwrap();
+        gossip_data.pending_gossip_list
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn gossip_round_honors_peers_per_round() {
+        let local = vec![agent(0), agent(1)];
+        let remote = (2..10).map(agent).collect::<Vec<_>>();
+
+        // by default every known peer is gossiped with
+        let pending = round(GossipConfig::default(), local.clone(), remote.clone()).await;
+        assert_eq!(pending.len(), 9 + 8);
+
+        for strategy in vec![
+            GossipStrategy::Random,
+            GossipStrategy::NearestArc,
+            GossipStrategy::RecentlyActive,
+        ] {
+            let cfg = GossipConfig {
+                peers_per_round: Some(3),
+                strategy,
+                ..Default::default()
+            };
+            let pending = round(cfg, local.clone(), remote.clone()).await;
+            for a in &local {
+                let peers = pending.iter().filter(|(from, _)| from == a).count();
+                assert_eq!(peers, 3, "{:?}", strategy);
+            }
+            assert!(pending.iter().all(|(from, to)| from != to));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn gossip_round_nearest_arc() {
+        let cfg = GossipConfig {
+            peers_per_round: Some(2),
+            strategy: GossipStrategy::NearestArc,
+            ..Default::default()
+        };
+        let pending = round(cfg, vec![agent(10)], vec![agent(200), agent(12), agent(9)]).await;
+        assert_eq!(pending, vec![(agent(10), agent(9)), (agent(10), agent(12))]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn gossip_stats_advance_after_round() {
+        let op = |n: u8| Arc::new(KitsuneOpHash::new(vec![n; 36]));
+        let local = agent(0);
+        let remote = agent(1);
+
+        let (evt_send, mut evt_recv) = futures::channel::mpsc::channel(10);
+        {
+            let (local, remote) = (local.clone(), remote.clone());
+            tokio::task::spawn(async move {
+                use futures::StreamExt;
+                while let Some(evt) = evt_recv.next().await {
+                    match evt {
+                        GossipEvent::ListNeighborAgents { respond, .. } => {
+                            let agents = (vec![local.clone()], vec![remote.clone()]);
+                            respond.r(Ok(async move { Ok(agents) }.boxed().into()));
+                        }
+                        GossipEvent::ReqOpHashes { respond, input, .. } => {
+                            // local holds 1 and 2, remote holds 2, 3 and 4
+                            let hashes = if input.to_agent == local {
+                                vec![op(1), op(2)]
+                            } else {
+                                vec![op(2), op(3), op(4)]
+                            };
+                            let r = (OpConsistency::Variance(hashes), Vec::new());
+                            respond.r(Ok(async move { Ok(r) }.boxed().into()));
+                        }
+                        GossipEvent::ReqOpData { respond, input, .. } => {
+                            // the remote has since lost the data for 4
+                            let ops = input
+                                .op_hashes
+                                .into_iter()
+                                .filter(|h| *h != op(4))
+                                .map(|h| (h, vec![]))
+                                .collect();
+                            respond.r(Ok(async move { Ok((ops, Vec::new())) }.boxed().into()));
+                        }
+                        GossipEvent::GossipOps { respond, .. } => {
+                            respond.r(Ok(async move { Ok(()) }.boxed().into()));
+                        }
+                    }
+                }
+            });
+        }
+
+        let counters = Arc::new(GossipCounters::default());
+        let mut gossip_data = GossipData::new(evt_send, counters.clone());
+        let cfg = GossipConfig::default();
+        assert_eq!(counters.stats(), GossipStats::default());
+
+        // the first action fetches the peers, the second gossips with them
+        gossip_data.take_action(&cfg).await.unwrap();
+        gossip_data.take_action(&cfg).await.unwrap();
+
+        assert_eq!(
+            counters.stats(),
+            GossipStats {
+                held_hashes: 2,
+                hashes_sent: 1,
+                hashes_received: 1,
+                outstanding_divergence: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use super::*;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// The notifies recently received by this node, by the agent they were
+/// sent to and their `msg_id`. In a mesh the same notify can reach a node
+/// along more than one path, and only the first should be handled.
+/// When more than `limit` are held, the least recently seen are forgotten.
+pub(crate) struct NotifyDedup {
+    inner: Mutex<NotifyDedupInner>,
+}
+
+type NotifyKey = (Arc<KitsuneAgent>, u64);
+
+struct NotifyDedupInner {
+    /// 0 means nothing is remembered, so nothing is dropped.
+    limit: usize,
+    next_use: u64,
+    last_seen: HashMap<NotifyKey, u64>,
+    /// Keys in the order they were seen. Entries whose use doesn't match
+    /// `last_seen` have been seen again since, and are skipped.
+    order: VecDeque<(NotifyKey, u64)>,
+}
+
+impl NotifyDedupInner {
+    /// Forget the least recently seen notifies until we are within the limit.
+    fn evict(&mut self) {
+        let Self {
+            limit,
+            last_seen,
+            order,
+            ..
+        } = self;
+        while last_seen.len() > *limit {
+            match order.pop_front() {
+                Some((key, used)) => {
+                    if last_seen.get(&key) == Some(&used) {
+                        last_seen.remove(&key);
+                    }
+                }
+                None => break,
+            }
+        }
+        // notifies seen over and over would otherwise grow the order forever
+        if order.len() > 2 * *limit {
+            order.retain(|(key, used)| last_seen.get(key) == Some(used));
+        }
+    }
+}
+
+impl NotifyDedup {
+    /// A limit of 0 disables deduplication.
+    pub fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            inner: Mutex::new(NotifyDedupInner {
+                limit,
+                next_use: 0,
+                last_seen: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        })
+    }
+
+    /// Record that the notify `msg_id` reached `to_agent`.
+    /// Returns `false` if it has already been seen, and should be dropped.
+    pub fn is_new(&self, to_agent: Arc<KitsuneAgent>, msg_id: u64) -> bool {
+        let mut inner = self.inner.lock().expect("notify dedup poisoned");
+        if inner.limit == 0 {
+            return true;
+        }
+        let key = (to_agent, msg_id);
+        let next_use = inner.next_use;
+        inner.next_use += 1;
+        let seen = inner.last_seen.insert(key.clone(), next_use).is_some();
+        inner.order.push_back((key, next_use));
+        inner.evict();
+        !seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_dedup_drops_seen() {
+        let dedup = NotifyDedup::new(2);
+        let agent = |n: u8| Arc::new(KitsuneAgent::new(vec![n; 36]));
+
+        assert!(dedup.is_new(agent(1), 1));
+        assert!(!dedup.is_new(agent(1), 1));
+        // the same notify to another agent on this node is handled
+        assert!(dedup.is_new(agent(2), 1));
+
+        // seeing (1, 1) again makes (2, 1) the least recently seen
+        assert!(!dedup.is_new(agent(1), 1));
+        assert!(dedup.is_new(agent(1), 2));
+        assert!(dedup.is_new(agent(2), 1));
+        assert!(!dedup.is_new(agent(1), 2));
+
+        // 0 remembers nothing
+        let dedup = NotifyDedup::new(0);
+        assert!(dedup.is_new(agent(1), 1));
+        assert!(dedup.is_new(agent(1), 1));
+    }
+}
@@ -0,0 +1,43 @@
+use rand::Rng;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Pick how long to hold back a notify_multi, uniformly at random between
+/// zero and `max` inclusive. When many nodes react to the same event, this
+/// spreads their notifies out rather than sending them all at once.
+pub(crate) fn notify_jitter(max: Duration) -> Duration {
+    if max == Duration::from_secs(0) {
+        return max;
+    }
+    // whole microseconds, rounded down, so never more than max
+    let max_us = u64::try_from(max.as_micros()).unwrap_or(u64::MAX);
+    Duration::from_micros(rand::thread_rng().gen_range(0, max_us.saturating_add(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_jitter_within_bound() {
+        assert_eq!(
+            Duration::from_secs(0),
+            notify_jitter(Duration::from_secs(0))
+        );
+        assert_eq!(
+            Duration::from_nanos(0),
+            notify_jitter(Duration::from_nanos(500))
+        );
+
+        let max = Duration::from_millis(10);
+        let delays: Vec<Duration> = (0..1000).map(|_| notify_jitter(max)).collect();
+        assert!(delays.iter().all(|delay| *delay <= max));
+        // the delays are spread over the window, not all the same
+        assert!(delays.iter().any(|delay| *delay < max / 2));
+        assert!(delays.iter().any(|delay| *delay >= max / 2));
+
+        // even the largest windows stay within bounds
+        let max = Duration::from_secs(u64::MAX);
+        assert!(notify_jitter(max) <= max);
+    }
+}
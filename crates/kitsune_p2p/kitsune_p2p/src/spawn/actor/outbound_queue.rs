@@ -0,0 +1,228 @@
+use crate::actor::MessagePriority;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Limits how many outbound multi-agent operations run at once.
+/// When the limit is reached, operations wait their turn in priority order,
+/// and in arrival order within a priority.
+pub(crate) struct OutboundQueue {
+    limit: usize,
+    inner: Mutex<OutboundQueueInner>,
+}
+
+struct OutboundQueueInner {
+    active: usize,
+    next_seq: u64,
+    waiting: BinaryHeap<Waiting>,
+}
+
+struct Waiting {
+    priority: MessagePriority,
+    seq: Reverse<u64>,
+    send: tokio::sync::oneshot::Sender<OutboundPermit>,
+}
+
+impl PartialEq for Waiting {
+    fn eq(&self, other: &Self) -> bool {
+        (self.priority, self.seq) == (other.priority, other.seq)
+    }
+}
+
+impl Eq for Waiting {}
+
+impl PartialOrd for Waiting {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiting {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.priority, self.seq).cmp(&(other.priority, other.seq))
+    }
+}
+
+/// A slot in the outbound queue, held for the duration of the operation.
+/// The slot is handed on to the next waiting operation when dropped.
+pub(crate) struct OutboundPermit(Option<Arc<OutboundQueue>>);
+
+impl Drop for OutboundPermit {
+    fn drop(&mut self) {
+        if let Some(queue) = self.0.take() {
+            queue.release();
+        }
+    }
+}
+
+impl OutboundQueue {
+    /// A limit of 0 is treated as 1, so operations can always make progress.
+    pub fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            limit: std::cmp::max(limit, 1),
+            inner: Mutex::new(OutboundQueueInner {
+                active: 0,
+                next_seq: 0,
+                waiting: BinaryHeap::new(),
+            }),
+        })
+    }
+
+    /// Wait for a slot to send an operation with the given priority.
+    pub async fn acquire(self: &Arc<Self>, priority: MessagePriority) -> OutboundPermit {
+        let recv = {
+            let mut inner = self.inner.lock().expect("outbound queue poisoned");
+            if inner.active < self.limit {
+                inner.active += 1;
+                return OutboundPermit(Some(self.clone()));
+            }
+            let (send, recv) = tokio::sync::oneshot::channel();
+            let seq = Reverse(inner.next_seq);
+            inner.next_seq += 1;
+            inner.waiting.push(Waiting {
+                priority,
+                seq,
+                send,
+            });
+            recv
+        };
+        // we hold a reference to the queue, and the sender is only
+        // dropped after handing over a permit, so this cannot fail
+        recv.await
+            .expect("outbound queue dropped a waiting operation")
+    }
+
+    /// Take a slot only if one is free right now, rather than waiting.
+    /// A slot is never free while operations are waiting for one.
+    pub fn try_acquire(self: &Arc<Self>) -> Option<OutboundPermit> {
+        let mut inner = self.inner.lock().expect("outbound queue poisoned");
+        if inner.active < self.limit {
+            inner.active += 1;
+            return Some(OutboundPermit(Some(self.clone())));
+        }
+        None
+    }
+
+    /// Number of operations waiting for a slot.
+    pub fn waiting(&self) -> usize {
+        self.inner
+            .lock()
+            .expect("outbound queue poisoned")
+            .waiting
+            .len()
+    }
+
+    fn release(self: &Arc<Self>) {
+        let mut inner = self.inner.lock().expect("outbound queue poisoned");
+        while let Some(waiting) = inner.waiting.pop() {
+            match waiting.send.send(OutboundPermit(Some(self.clone()))) {
+                Ok(()) => return,
+                // the waiting operation was cancelled, don't let the
+                // returned permit release (and re-lock) the queue
+                Err(mut permit) => {
+                    permit.0.take();
+                }
+            }
+        }
+        inner.active -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn outbound_queue_admits_by_priority() {
+        let queue = OutboundQueue::new(1);
+        let permit = queue.acquire(MessagePriority::Normal).await;
+
+        let (send, mut recv) = tokio::sync::mpsc::unbounded_channel();
+        let mut tasks = Vec::new();
+        let priorities = vec![
+            MessagePriority::Low,
+            MessagePriority::Normal,
+            MessagePriority::High,
+            MessagePriority::Normal,
+        ];
+        for (i, priority) in priorities.into_iter().enumerate() {
+            let queue = queue.clone();
+            let send = send.clone();
+            tasks.push(tokio::task::spawn(async move {
+                let _permit = queue.acquire(priority).await;
+                send.send((i, priority)).unwrap();
+            }));
+            // make sure the arrival order is deterministic
+            while queue.waiting() < i + 1 {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        drop(permit);
+        futures::future::try_join_all(tasks).await.unwrap();
+
+        let mut order = Vec::new();
+        while let Some(Some(priority)) = recv.recv().now_or_never() {
+            order.push(priority);
+        }
+        assert_eq!(
+            order,
+            vec![
+                (2, MessagePriority::High),
+                (1, MessagePriority::Normal),
+                (3, MessagePriority::Normal),
+                (0, MessagePriority::Low),
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn outbound_queue_try_acquire() {
+        let queue = OutboundQueue::new(2);
+        let first = queue.try_acquire().expect("queue has room");
+        let _second = queue.try_acquire().expect("queue has room");
+        assert!(queue.try_acquire().is_none());
+
+        // a waiting operation gets the next free slot, not try_acquire
+        let waiting = tokio::task::spawn({
+            let queue = queue.clone();
+            async move {
+                let _permit = queue.acquire(MessagePriority::Low).await;
+            }
+        });
+        while queue.waiting() < 1 {
+            tokio::task::yield_now().await;
+        }
+        drop(first);
+        assert!(queue.try_acquire().is_none());
+        waiting.await.unwrap();
+
+        let _third = queue.try_acquire().expect("queue has room");
+        assert!(queue.try_acquire().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn outbound_queue_skips_cancelled() {
+        let queue = OutboundQueue::new(1);
+        let permit = queue.acquire(MessagePriority::Normal).await;
+
+        let cancelled = tokio::task::spawn({
+            let queue = queue.clone();
+            async move {
+                let _permit = queue.acquire(MessagePriority::High).await;
+            }
+        });
+        while queue.waiting() < 1 {
+            tokio::task::yield_now().await;
+        }
+        cancelled.abort();
+        let _ = cancelled.await;
+
+        drop(permit);
+        // the slot was returned rather than lost to the cancelled operation
+        let _permit = queue.acquire(MessagePriority::Low).await;
+        assert_eq!(0, queue.waiting());
+    }
+}
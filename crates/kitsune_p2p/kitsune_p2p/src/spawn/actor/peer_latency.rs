@@ -0,0 +1,69 @@
+use crate::*;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Round-trip times observed on requests to remote agents in a space.
+/// Only the most recent `window` samples are kept per agent,
+/// and the reported latency is their average.
+pub(crate) struct PeerLatencies {
+    window: usize,
+    samples: Mutex<HashMap<Arc<KitsuneAgent>, VecDeque<Duration>>>,
+}
+
+impl PeerLatencies {
+    /// A window of 0 is treated as 1, so the latest sample is always kept.
+    pub fn new(window: usize) -> Arc<Self> {
+        Arc::new(Self {
+            window: std::cmp::max(window, 1),
+            samples: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Record a round-trip time for a request to `agent`.
+    pub fn record(&self, agent: Arc<KitsuneAgent>, rtt: Duration) {
+        let mut samples = self.samples.lock().expect("peer latencies poisoned");
+        let samples = samples.entry(agent).or_insert_with(VecDeque::new);
+        if samples.len() >= self.window {
+            samples.pop_front();
+        }
+        samples.push_back(rtt);
+    }
+
+    /// The average recent round-trip time for each agent we have sampled.
+    pub fn averages(&self) -> Vec<(Arc<KitsuneAgent>, Duration)> {
+        self.samples
+            .lock()
+            .expect("peer latencies poisoned")
+            .iter()
+            .map(|(agent, samples)| {
+                let total: Duration = samples.iter().sum();
+                (agent.clone(), total / samples.len() as u32)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_latencies_rolling_average() {
+        let latencies = PeerLatencies::new(2);
+        let agent = Arc::new(KitsuneAgent::new(vec![0; 36]));
+        let ms = Duration::from_millis;
+
+        latencies.record(agent.clone(), ms(10));
+        assert_eq!(vec![(agent.clone(), ms(10))], latencies.averages());
+
+        latencies.record(agent.clone(), ms(20));
+        assert_eq!(vec![(agent.clone(), ms(15))], latencies.averages());
+
+        // the oldest sample falls out of the window
+        latencies.record(agent.clone(), ms(40));
+        assert_eq!(vec![(agent, ms(30))], latencies.averages());
+    }
+}
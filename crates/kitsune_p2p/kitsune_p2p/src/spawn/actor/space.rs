@@ -3,6 +3,7 @@ use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures::Instrument;
 use kitsune_p2p_mdns::*;
 use kitsune_p2p_types::codec::{rmp_decode, rmp_encode};
+use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::sync::atomic::AtomicBool;
@@ -21,14 +22,23 @@ ghost_actor::ghost_chan! {
 
         /// see if an agent is locally joined
         fn is_agent_local(agent: Arc<KitsuneAgent>) -> bool;
+
+        /// Make a single rpc_single attempt, for rpc_single_with_fallback
+        fn rpc_single_attempt(space: Arc<KitsuneSpace>, to_agent: Arc<KitsuneAgent>, from_agent: Arc<KitsuneAgent>, payload: Vec<u8>, timeout_ms: Option<u64>) -> Vec<u8>;
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_space(
     space: Arc<KitsuneSpace>,
     this_addr: url2::Url2,
     ep_hnd: Tx2EpHnd<wire::Wire>,
-    config: Arc<KitsuneP2pConfig>,
+    connections: Arc<ConnectionLru>,
+    node_config: Arc<KitsuneP2pConfig>,
+    space_config: SpaceTransportConfig,
+    rejoin_policy: tokio::sync::watch::Receiver<actor::RejoinPolicy>,
+    gossip_config: tokio::sync::watch::Receiver<actor::GossipConfig>,
+    bootstrap_services: tokio::sync::watch::Receiver<Vec<url2::Url2>>,
 ) -> KitsuneP2pResult<(
     ghost_actor::GhostSender<KitsuneP2p>,
     KitsuneP2pEventReceiver,
@@ -37,8 +47,10 @@ pub(crate) async fn spawn_space(
 
     let builder = ghost_actor::actor_builder::GhostActorBuilder::new();
 
+    let config = Arc::new(node_config.with_space_config(&space_config));
+
     // initialize gossip module
-    let gossip_recv = gossip::spawn_gossip_module(config.clone());
+    let (gossip_recv, gossip_counters) = gossip::spawn_gossip_module(config.clone(), gossip_config);
     builder
         .channel_factory()
         .attach_receiver(gossip_recv)
@@ -54,7 +66,19 @@ pub(crate) async fn spawn_space(
         .create_channel::<KitsuneP2p>()
         .await?;
 
-    tokio::task::spawn(builder.spawn(Space::new(space, this_addr, i_s, evt_send, ep_hnd, config)));
+    tokio::task::spawn(builder.spawn(Space::new(
+        space,
+        this_addr,
+        i_s,
+        evt_send,
+        ep_hnd,
+        connections,
+        node_config,
+        config,
+        rejoin_policy,
+        bootstrap_services,
+        gossip_counters,
+    )));
 
     Ok((sender, evt_recv))
 }
@@ -116,6 +140,8 @@ impl gossip::GossipEventHandler for Space {
                 op_count,
             } = input;
             let ep_hnd = self.ep_hnd.clone();
+            let connections = self.connections.clone();
+            let transport_security = self.config.transport_security.clone();
             let evt_sender = self.evt_sender.clone();
             let space = self.space.clone();
             let timeout = self.config.tuning_params.implicit_timeout();
@@ -132,17 +158,31 @@ impl gossip::GossipEventHandler for Space {
                     Some(i) => i,
                 };
                 let data = wire::Wire::fetch_op_hashes(
-                    space,
+                    space.clone(),
                     from_agent,
-                    to_agent,
+                    to_agent.clone(),
                     dht_arc,
                     since_utc_epoch_s,
                     until_utc_epoch_s,
                     op_count,
                 );
-                let info = types::agent_store::AgentInfo::try_from(&info)?;
-                let url = info.as_urls_ref().get(0).unwrap().clone();
-                let con_hnd = ep_hnd.get_connection(url, timeout).await?;
+                // a tombstone has no urls, see `leave`
+                if types::agent_store::AgentInfo::try_from(&info)?
+                    .as_urls_ref()
+                    .is_empty()
+                {
+                    return Err(KitsuneP2pError::RoutingAgentError(to_agent));
+                }
+                let (_, con_hnd) = discover::connect_verified(
+                    &ep_hnd,
+                    &connections,
+                    &transport_security,
+                    &space,
+                    &to_agent,
+                    info,
+                    timeout,
+                )
+                .await?;
                 let read = con_hnd.request(&data, timeout).await?;
                 match read {
                     wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
@@ -173,6 +213,8 @@ impl gossip::GossipEventHandler for Space {
                 peer_hashes,
             } = input;
             let ep_hnd = self.ep_hnd.clone();
+            let connections = self.connections.clone();
+            let transport_security = self.config.transport_security.clone();
             let evt_sender = self.evt_sender.clone();
             let space = self.space.clone();
             let timeout = self.config.tuning_params.implicit_timeout();
@@ -188,11 +230,30 @@ impl gossip::GossipEventHandler for Space {
                     None => return Err(KitsuneP2pError::RoutingAgentError(to_agent)),
                     Some(i) => i,
                 };
-                let data =
-                    wire::Wire::fetch_op_data(space, from_agent, to_agent, op_hashes, peer_hashes);
-                let info = types::agent_store::AgentInfo::try_from(&info)?;
-                let url = info.as_urls_ref().get(0).unwrap().clone();
-                let con_hnd = ep_hnd.get_connection(url, timeout).await?;
+                let data = wire::Wire::fetch_op_data(
+                    space.clone(),
+                    from_agent,
+                    to_agent.clone(),
+                    op_hashes,
+                    peer_hashes,
+                );
+                // a tombstone has no urls, see `leave`
+                if types::agent_store::AgentInfo::try_from(&info)?
+                    .as_urls_ref()
+                    .is_empty()
+                {
+                    return Err(KitsuneP2pError::RoutingAgentError(to_agent));
+                }
+                let (_, con_hnd) = discover::connect_verified(
+                    &ep_hnd,
+                    &connections,
+                    &transport_security,
+                    &space,
+                    &to_agent,
+                    info,
+                    timeout,
+                )
+                .await?;
                 let read = con_hnd.request(&data, timeout).await?;
                 match read {
                     wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
@@ -224,6 +285,8 @@ impl gossip::GossipEventHandler for Space {
                 agents,
             } = input;
             let ep_hnd = self.ep_hnd.clone();
+            let connections = self.connections.clone();
+            let transport_security = self.config.transport_security.clone();
             let evt_sender = self.evt_sender.clone();
             let space = self.space.clone();
             let timeout = self.config.tuning_params.implicit_timeout();
@@ -240,15 +303,29 @@ impl gossip::GossipEventHandler for Space {
                     Some(i) => i,
                 };
                 let data = wire::Wire::gossip(
-                    space,
+                    space.clone(),
                     from_agent.clone(),
                     to_agent.clone(),
                     ops.into_iter().map(|(k, v)| (k, v.into())).collect(),
                     agents,
                 );
-                let info = types::agent_store::AgentInfo::try_from(&info)?;
-                let url = info.as_urls_ref().get(0).unwrap().clone();
-                let con_hnd = ep_hnd.get_connection(url.clone(), timeout).await?;
+                // a tombstone has no urls, see `leave`
+                if types::agent_store::AgentInfo::try_from(&info)?
+                    .as_urls_ref()
+                    .is_empty()
+                {
+                    return Err(KitsuneP2pError::RoutingAgentError(to_agent));
+                }
+                let (_, con_hnd) = discover::connect_verified(
+                    &ep_hnd,
+                    &connections,
+                    &transport_security,
+                    &space,
+                    &to_agent,
+                    info,
+                    timeout,
+                )
+                .await?;
                 let read = con_hnd.request(&data, timeout).await?;
                 match read {
                     wire::Wire::Failure(wire::Failure { reason }) => Err(dbg!(reason.into())),
@@ -391,6 +468,47 @@ pub fn local_gossip_ops(
     }
 }
 
+/// Store a remote agent's leave tombstone in place of its agent info.
+/// The tombstone is ignored unless the agent signed it itself, and it is
+/// newer than the info we already hold, so a replayed tombstone cannot
+/// evict an agent that has since rejoined. Returns whether it was stored.
+pub async fn local_leave(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    space: Arc<KitsuneSpace>,
+    tombstone: types::agent_store::AgentInfoSigned,
+) -> KitsuneP2pResult<bool> {
+    let info = types::agent_store::AgentInfo::try_from(&tombstone)?;
+    // a tombstone has no urls, see `leave`
+    if info.as_space_ref() != &*space
+        || info.as_agent_ref() != tombstone.as_agent_ref()
+        || !info.as_urls_ref().is_empty()
+        || !tombstone.verify_signature().await?
+    {
+        return Ok(false);
+    }
+    let agent = Arc::new(tombstone.as_agent_ref().clone());
+    if let Some(held) = evt_sender
+        .get_agent_info_signed(GetAgentInfoSignedEvt {
+            space: space.clone(),
+            agent: agent.clone(),
+        })
+        .await?
+    {
+        let held = types::agent_store::AgentInfo::try_from(&held)?;
+        if held.signed_at_ms() >= info.signed_at_ms() {
+            return Ok(false);
+        }
+    }
+    evt_sender
+        .put_agent_info_signed(PutAgentInfoSignedEvt {
+            space,
+            agent,
+            agent_info_signed: tombstone,
+        })
+        .await?;
+    Ok(true)
+}
+
 impl ghost_actor::GhostHandler<SpaceInternal> for Space {}
 
 impl SpaceInternalHandler for Space {
@@ -424,14 +542,18 @@ impl SpaceInternalHandler for Space {
         let space = self.space.clone();
         let mut mdns_handles = self.mdns_handles.clone();
         let network_type = self.config.network_type.clone();
-        let agent_list: Vec<Arc<KitsuneAgent>> = self.local_joined_agents.iter().cloned().collect();
+        let agent_list: Vec<(Arc<KitsuneAgent>, u32)> = self
+            .local_joined_agents
+            .iter()
+            .map(|agent| (agent.clone(), self.local_agent_half_length(agent)))
+            .collect();
         let bound_url = self.this_addr.clone();
         let evt_sender = self.evt_sender.clone();
-        let bootstrap_service = self.config.bootstrap_service.clone();
+        let bootstrap_services = self.bootstrap_services.borrow().clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         Ok(async move {
             let urls = vec![bound_url];
-            for agent in agent_list {
+            for (agent, half_length) in agent_list {
                 let agent_info = crate::types::agent_store::AgentInfo::new(
                     (*space).clone(),
                     (*agent).clone(),
@@ -440,7 +562,7 @@ impl SpaceInternalHandler for Space {
                     expires_after,
                 )
                 .with_meta_info(crate::types::agent_store::AgentMetaInfo {
-                    dht_storage_arc_half_length: 0,
+                    dht_storage_arc_half_length: half_length,
                 })?;
                 let mut data = Vec::new();
                 rmp_encode(&mut data, &agent_info)?;
@@ -491,8 +613,8 @@ impl SpaceInternalHandler for Space {
                         }
                     }
                     NetworkType::QuicBootstrap => {
-                        crate::spawn::actor::bootstrap::put(
-                            bootstrap_service.clone(),
+                        crate::spawn::actor::bootstrap::put_any(
+                            bootstrap_services.clone(),
                             agent_info_signed,
                         )
                         .await?;
@@ -512,6 +634,17 @@ impl SpaceInternalHandler for Space {
         let res = self.local_joined_agents.contains(&agent);
         Ok(async move { Ok(res) }.boxed().into())
     }
+
+    fn handle_rpc_single_attempt(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        to_agent: Arc<KitsuneAgent>,
+        from_agent: Arc<KitsuneAgent>,
+        payload: Vec<u8>,
+        timeout_ms: Option<u64>,
+    ) -> SpaceInternalHandlerResult<Vec<u8>> {
+        KitsuneP2pHandler::handle_rpc_single(self, space, to_agent, from_agent, payload, timeout_ms)
+    }
 }
 
 impl ghost_actor::GhostControlHandler for Space {}
@@ -525,12 +658,67 @@ impl KitsuneP2pHandler for Space {
         )
     }
 
+    fn handle_pending_stats(&mut self) -> KitsuneP2pHandlerResult<actor::KitsuneP2pStats> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
+
+    fn handle_set_rejoin_policy(
+        &mut self,
+        _policy: actor::RejoinPolicy,
+    ) -> KitsuneP2pHandlerResult<()> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
+
+    fn handle_set_gossip_config(
+        &mut self,
+        _cfg: actor::GossipConfig,
+    ) -> KitsuneP2pHandlerResult<()> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
+
+    fn handle_set_connection_limit(&mut self, _max: usize) -> KitsuneP2pHandlerResult<()> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
+
+    fn handle_add_bootstrap(&mut self, _url: String) -> KitsuneP2pHandlerResult<()> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
+
+    fn handle_shutdown(&mut self, _drain_timeout_ms: u64) -> KitsuneP2pHandlerResult<()> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
+
+    fn handle_configure_space(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+        config: SpaceTransportConfig,
+    ) -> KitsuneP2pHandlerResult<()> {
+        self.config = Arc::new(self.node_config.with_space_config(&config));
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
     fn handle_join(
         &mut self,
         space: Arc<KitsuneSpace>,
         agent: Arc<KitsuneAgent>,
+        arc: Option<DhtArc>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.insert(agent.clone());
+        let half_length = arc.map_or(MAX_HALF_LENGTH, |arc| arc.half_length);
+        self.local_agent_arcs
+            .insert(agent.clone(), DhtArc::new(agent.get_loc(), half_length));
         let fut = self.i_s.update_agent_info();
         let i_s = self.i_s.clone();
         let evt_sender = self.evt_sender.clone();
@@ -582,35 +770,42 @@ impl KitsuneP2pHandler for Space {
                 }
             }
             NetworkType::QuicBootstrap => {
-                let bootstrap_service = self.config.bootstrap_service.clone();
-                if let Some(bootstrap_service) = bootstrap_service {
-                    tokio::task::spawn(async move {
-                        const START_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
-                        const MAX_DELAY: std::time::Duration =
-                            std::time::Duration::from_secs(60 * 60);
-                        let mut delay_len = START_DELAY;
-
-                        loop {
-                            tokio::time::sleep(delay_len).await;
-                            if delay_len <= MAX_DELAY {
-                                delay_len *= 2;
-                            }
+                let mut bootstrap_services = self.bootstrap_services.clone();
+                tokio::task::spawn(async move {
+                    const START_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+                    const MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+                    let mut delay_len = START_DELAY;
 
-                            // TODO - this will make redundant requests to bootstrap server if multiple local agents have joined the same space.
-                            if let Err(e) = super::discover::add_5_or_less_non_local_agents(
-                                space.clone(),
-                                agent.clone(),
-                                i_s.clone(),
-                                evt_sender.clone(),
-                                bootstrap_service.clone(),
-                            )
-                            .await
-                            {
-                                tracing::error!(msg = "Failed to get peers from bootstrap", ?e);
+                    loop {
+                        let services = bootstrap_services.borrow().clone();
+                        if services.is_empty() {
+                            // nothing to ask until a service is added
+                            if bootstrap_services.changed().await.is_err() {
+                                return;
                             }
+                            delay_len = START_DELAY;
+                            continue;
                         }
-                    });
-                }
+
+                        tokio::time::sleep(delay_len).await;
+                        if delay_len <= MAX_DELAY {
+                            delay_len *= 2;
+                        }
+
+                        // TODO - this will make redundant requests to bootstrap server if multiple local agents have joined the same space.
+                        if let Err(e) = super::discover::add_5_or_less_non_local_agents(
+                            space.clone(),
+                            agent.clone(),
+                            i_s.clone(),
+                            evt_sender.clone(),
+                            services,
+                        )
+                        .await
+                        {
+                            tracing::error!(msg = "Failed to get peers from bootstrap", ?e);
+                        }
+                    }
+                });
             }
         }
 
@@ -619,11 +814,226 @@ impl KitsuneP2pHandler for Space {
 
     fn handle_leave(
         &mut self,
-        _space: Arc<KitsuneSpace>,
+        space: Arc<KitsuneSpace>,
         agent: Arc<KitsuneAgent>,
+        announce: bool,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.remove(&agent);
-        Ok(async move { Ok(()) }.boxed().into())
+        self.local_agent_arcs.remove(&agent);
+        if !announce {
+            return Ok(async move { Ok(()) }.boxed().into());
+        }
+
+        let this_addr = self.this_addr.clone();
+        let local_agents = self.local_joined_agents.clone();
+        let evt_sender = self.evt_sender.clone();
+        let ep_hnd = self.ep_hnd.clone();
+        let connections = self.connections.clone();
+        let transport_security = self.config.transport_security.clone();
+        let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
+        let timeout = self.config.tuning_params.implicit_timeout();
+        Ok(async move {
+            let agent_info = types::agent_store::AgentInfo::new(
+                (*space).clone(),
+                (*agent).clone(),
+                Vec::new(),
+                crate::spawn::actor::bootstrap::now_once(None).await?,
+                expires_after,
+            )
+            .with_meta_info(types::agent_store::AgentMetaInfo {
+                dht_storage_arc_half_length: 0,
+            })?;
+            let mut data = Vec::new();
+            rmp_encode(&mut data, &agent_info)?;
+            let sig = evt_sender
+                .sign_network_data(SignNetworkDataEvt {
+                    space: space.clone(),
+                    agent: agent.clone(),
+                    data: Arc::new(data.clone()),
+                })
+                .await?;
+            let tombstone =
+                types::agent_store::AgentInfoSigned::try_new((*agent).clone(), sig, data)?;
+            evt_sender
+                .put_agent_info_signed(PutAgentInfoSignedEvt {
+                    space: space.clone(),
+                    agent: agent.clone(),
+                    agent_info_signed: tombstone.clone(),
+                })
+                .await?;
+
+            // tell each other node in our peer store, once
+            let peers = evt_sender
+                .query_agent_info_signed(QueryAgentInfoSignedEvt {
+                    space: space.clone(),
+                    agent: agent.clone(),
+                })
+                .await?;
+            let nodes = peers
+                .into_iter()
+                .filter_map(|signed| {
+                    let info = types::agent_store::AgentInfo::try_from(&signed).ok()?;
+                    let url = info.as_urls_ref().get(0)?.clone();
+                    Some((url, signed))
+                })
+                .filter(|(url, signed)| {
+                    signed.as_agent_ref() != &*agent
+                        && !local_agents.contains(signed.as_agent_ref())
+                        && url != &this_addr
+                })
+                .collect::<HashMap<_, _>>();
+            let msg = wire::Wire::leave(space.clone(), tombstone);
+            let sends = nodes.into_iter().map(|(_, signed)| {
+                let ep_hnd = ep_hnd.clone();
+                let connections = connections.clone();
+                let transport_security = &transport_security;
+                let space = &space;
+                let msg = &msg;
+                async move {
+                    let to_agent = signed.as_agent_ref().clone();
+                    let (_, con_hnd) = discover::connect_verified(
+                        &ep_hnd,
+                        &connections,
+                        transport_security,
+                        space,
+                        &to_agent,
+                        signed,
+                        timeout,
+                    )
+                    .await?;
+                    match con_hnd.request(msg, timeout).await? {
+                        wire::Wire::LeaveResp(_) => KitsuneP2pResult::Ok(()),
+                        wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
+                        r => Err(format!("unexpected response to leave: {:?}", r).into()),
+                    }
+                }
+            });
+            for res in futures::future::join_all(sends).await {
+                if let Err(err) = res {
+                    tracing::warn!(?err, "failed to announce leave");
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_get_agent_info(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+    ) -> KitsuneP2pHandlerResult<types::agent_store::AgentInfoSigned> {
+        // remote queries are made on behalf of one of our local agents
+        let from_agent = self.local_joined_agents.iter().next().cloned();
+        let i_s = self.i_s.clone();
+        let evt_sender = self.evt_sender.clone();
+        let ep_hnd = self.ep_hnd.clone();
+        let connections = self.connections.clone();
+        let transport_security = self.config.transport_security.clone();
+        let bootstrap_services = self.bootstrap_services.borrow().clone();
+        let timeout = self.config.tuning_params.implicit_timeout();
+        Ok(async move {
+            if let Some(info) = evt_sender
+                .get_agent_info_signed(GetAgentInfoSignedEvt {
+                    space: space.clone(),
+                    agent: agent.clone(),
+                })
+                .await?
+            {
+                return Ok(info);
+            }
+
+            let from_agent = match from_agent {
+                None => return Err(KitsuneP2pError::RoutingAgentError(agent)),
+                Some(a) => a,
+            };
+            discover::search_remote_agent_info(
+                space,
+                from_agent,
+                agent,
+                i_s,
+                evt_sender,
+                ep_hnd,
+                connections,
+                transport_security,
+                bootstrap_services,
+                timeout,
+            )
+            .await
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_list_joined(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<Vec<Arc<KitsuneAgent>>> {
+        let agents = self.local_joined_agents.iter().cloned().collect();
+        Ok(async move { Ok(agents) }.boxed().into())
+    }
+
+    fn handle_peer_latencies(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<Vec<(Arc<KitsuneAgent>, std::time::Duration)>> {
+        let latencies = self.peer_latencies.averages();
+        Ok(async move { Ok(latencies) }.boxed().into())
+    }
+
+    fn handle_gossip_stats(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<actor::GossipStats> {
+        let stats = self.gossip_counters.stats();
+        Ok(async move { Ok(stats) }.boxed().into())
+    }
+
+    fn handle_ping(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        timeout_ms: Option<u64>,
+    ) -> KitsuneP2pHandlerResult<Option<std::time::Duration>> {
+        let peer_latencies = self.peer_latencies.clone();
+
+        let timeout_ms = match timeout_ms {
+            None | Some(0) => self.config.tuning_params.default_rpc_single_timeout_ms as u64,
+            _ => timeout_ms.unwrap(),
+        };
+        let timeout = KitsuneTimeout::from_millis(timeout_ms);
+
+        // any of our joined agents can look up the remote agent's info
+        let from_agent = self
+            .local_joined_agents
+            .iter()
+            .next()
+            .cloned()
+            .unwrap_or_else(|| agent.clone());
+        let discover_fut = discover::peer_discover(self, agent.clone(), from_agent, timeout_ms);
+
+        Ok(async move {
+            match discover_fut.await {
+                discover::PeerDiscoverResult::OkShortcut => {
+                    Ok(Some(std::time::Duration::default()))
+                }
+                discover::PeerDiscoverResult::OkRemote { con_hnd, .. } => {
+                    let start = std::time::Instant::now();
+                    match con_hnd.request(&wire::Wire::ping(), timeout).await {
+                        Ok(wire::Wire::PingResp(_)) => {
+                            let rtt = start.elapsed();
+                            peer_latencies.record(agent, rtt);
+                            Ok(Some(rtt))
+                        }
+                        _ => Ok(None),
+                    }
+                }
+                discover::PeerDiscoverResult::Err(_) => Ok(None),
+            }
+        }
+        .boxed()
+        .into())
     }
 
     fn handle_rpc_single(
@@ -635,6 +1045,7 @@ impl KitsuneP2pHandler for Space {
         timeout_ms: Option<u64>,
     ) -> KitsuneP2pHandlerResult<Vec<u8>> {
         let evt_sender = self.evt_sender.clone();
+        let peer_latencies = self.peer_latencies.clone();
 
         let timeout_ms = match timeout_ms {
             None | Some(0) => self.config.tuning_params.default_rpc_single_timeout_ms as u64,
@@ -648,8 +1059,16 @@ impl KitsuneP2pHandler for Space {
         Ok(async move {
             match discover_fut.await {
                 discover::PeerDiscoverResult::OkShortcut => {
-                    // reflect this request locally
-                    evt_sender.call(space, to_agent, from_agent, payload).await
+                    // reflect this request locally, bounded by the same timeout
+                    match tokio::time::timeout(
+                        timeout.time_remaining(),
+                        evt_sender.call(space, to_agent, from_agent, payload),
+                    )
+                    .await
+                    {
+                        Ok(r) => r,
+                        Err(_) => Err(KitsuneP2pError::Timeout),
+                    }
                 }
                 discover::PeerDiscoverResult::OkRemote { con_hnd, .. } => {
                     let payload = wire::Wire::call(
@@ -658,7 +1077,9 @@ impl KitsuneP2pHandler for Space {
                         to_agent.clone(),
                         payload.into(),
                     );
+                    let start = std::time::Instant::now();
                     let res = con_hnd.request(&payload, timeout).await?;
+                    peer_latencies.record(to_agent, start.elapsed());
                     match res {
                         wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
                         wire::Wire::CallResp(wire::CallResp { data }) => Ok(data.into()),
@@ -672,70 +1093,192 @@ impl KitsuneP2pHandler for Space {
         .into())
     }
 
-    fn handle_rpc_multi(
+    fn handle_rpc_single_with_fallback(
         &mut self,
-        mut input: actor::RpcMulti,
-    ) -> KitsuneP2pHandlerResult<Vec<actor::RpcMultiResponse>> {
-        // if the user doesn't care about remote_agent_count, apply default
-        match input.remote_agent_count {
-            None | Some(0) => {
-                input.remote_agent_count = Some(
-                    self.config
-                        .tuning_params
-                        .default_rpc_multi_remote_agent_count as u8,
-                );
+        input: actor::RpcSingleWithFallback,
+    ) -> KitsuneP2pHandlerResult<Vec<u8>> {
+        let actor::RpcSingleWithFallback {
+            space,
+            to_agent,
+            from_agent,
+            basis,
+            max_retries,
+            attempt_timeout_ms,
+            payload,
+        } = input;
+        let i_s = self.i_s.clone();
+        Ok(async move {
+            let mut last_err = match i_s
+                .rpc_single_attempt(
+                    space.clone(),
+                    to_agent.clone(),
+                    from_agent.clone(),
+                    payload.clone(),
+                    attempt_timeout_ms,
+                )
+                .await
+            {
+                Ok(res) => return Ok(res),
+                Err(e) => e,
+            };
+            if max_retries == 0 {
+                return Err(last_err);
             }
-            _ => {}
-        }
 
-        // if the user doesn't care about timeout_ms, apply default
-        match input.timeout_ms {
-            None | Some(0) => {
-                input.timeout_ms =
-                    Some(self.config.tuning_params.default_rpc_multi_timeout_ms as u64);
-            }
-            _ => {}
-        }
+            let candidates = i_s
+                .list_online_agents_for_basis_hash(space.clone(), from_agent.clone(), basis.clone())
+                .await?
+                .into_iter()
+                .filter(|agent| agent != &to_agent && agent != &from_agent)
+                .collect();
+            let fallbacks = discover::select_agents(
+                actor::AgentSelection::NearestBasis,
+                &basis,
+                max_retries as usize,
+                candidates,
+            );
 
-        // if the user doesn't care about race_timeout_ms, apply default
-        match input.race_timeout_ms {
-            None | Some(0) => {
-                input.race_timeout_ms = Some(DEFAULT_RPC_MULTI_RACE_TIMEOUT_MS);
+            for agent in fallbacks {
+                tracing::debug!(?last_err, ?agent, "rpc_single failed, trying next agent");
+                match i_s
+                    .rpc_single_attempt(
+                        space.clone(),
+                        agent,
+                        from_agent.clone(),
+                        payload.clone(),
+                        attempt_timeout_ms,
+                    )
+                    .await
+                {
+                    Ok(res) => return Ok(res),
+                    Err(e) => last_err = e,
+                }
             }
-            _ => {}
-        }
-
-        // race timeout > timeout is nonesense
-        if input.as_race && input.race_timeout_ms.unwrap() > input.timeout_ms.unwrap() {
-            input.race_timeout_ms = Some(input.timeout_ms.unwrap());
+            Err(last_err)
         }
-
-        self.handle_rpc_multi_inner(input)
+        .boxed()
+        .into())
     }
 
-    fn handle_notify_multi(
+    fn handle_notify_single(
         &mut self,
-        mut input: actor::NotifyMulti,
-    ) -> KitsuneP2pHandlerResult<u8> {
-        // if the user doesn't care about remote_agent_count, apply default
-        match input.remote_agent_count {
-            None | Some(0) => {
-                input.remote_agent_count =
-                    Some(self.config.tuning_params.default_notify_remote_agent_count as u8);
+        space: Arc<KitsuneSpace>,
+        to_agent: Arc<KitsuneAgent>,
+        from_agent: Arc<KitsuneAgent>,
+        payload: Vec<u8>,
+        timeout_ms: Option<u64>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+
+        let timeout_ms = match timeout_ms {
+            None | Some(0) => self.config.tuning_params.default_rpc_single_timeout_ms as u64,
+            _ => timeout_ms.unwrap(),
+        };
+        let timeout = KitsuneTimeout::from_millis(timeout_ms);
+
+        let discover_fut =
+            discover::peer_discover(self, to_agent.clone(), from_agent.clone(), timeout_ms);
+
+        Ok(async move {
+            match discover_fut.await {
+                discover::PeerDiscoverResult::OkShortcut => {
+                    // hand this notify to the local agent, without waiting for it to be handled
+                    tokio::task::spawn(async move {
+                        if let Err(err) = evt_sender
+                            .notify(space, to_agent, from_agent, payload)
+                            .await
+                        {
+                            tracing::warn!(?err, "failed to handle notify_single");
+                        }
+                    });
+                    Ok(())
+                }
+                discover::PeerDiscoverResult::OkRemote { con_hnd, .. } => {
+                    let payload =
+                        wire::Wire::notify_single(space, from_agent, to_agent, payload.into());
+                    match con_hnd.request(&payload, timeout).await? {
+                        wire::Wire::NotifyResp(_) => Ok(()),
+                        r => Err(format!("invalid response: {:?}", r).into()),
+                    }
+                }
+                discover::PeerDiscoverResult::Err(e) => Err(e),
             }
-            _ => {}
         }
+        .boxed()
+        .into())
+    }
 
-        // if the user doesn't care about timeout_ms, apply default
-        // also - if set to 0, we want to return immediately, but
-        // spawn a task with that default timeout.
-        let do_spawn = match input.timeout_ms {
-            None | Some(0) => {
-                input.timeout_ms = Some(self.config.tuning_params.default_notify_timeout_ms as u64);
-                true
+    fn handle_rpc_multi(
+        &mut self,
+        mut input: actor::RpcMulti,
+    ) -> KitsuneP2pHandlerResult<Vec<actor::RpcMultiResponse>> {
+        self.apply_rpc_multi_defaults(&mut input);
+        self.handle_rpc_multi_inner(input)
+    }
+
+    fn handle_try_rpc_multi(
+        &mut self,
+        input: actor::RpcMulti,
+    ) -> KitsuneP2pHandlerResult<Vec<actor::RpcMultiResponse>> {
+        // the outbound queue is checked before reaching the space
+        self.handle_rpc_multi(input)
+    }
+
+    fn handle_rpc_multi_stream(
+        &mut self,
+        mut input: actor::RpcMulti,
+    ) -> KitsuneP2pHandlerResult<actor::RpcMultiStream> {
+        self.apply_rpc_multi_defaults(&mut input);
+        let response_count = input.remote_agent_count.unwrap() as usize;
+        let timeout = KitsuneTimeout::from_millis(input.timeout_ms.unwrap());
+
+        let mut calls = self
+            .local_rpc_multi_calls(&input)
+            .into_iter()
+            .collect::<futures::stream::FuturesUnordered<_>>();
+        let (mut send, recv) = futures::channel::mpsc::channel(calls.len());
+
+        tokio::task::spawn(async move {
+            let mut sent = 0;
+            while sent < response_count {
+                let next = match tokio::time::timeout(timeout.time_remaining(), calls.next()).await
+                {
+                    Ok(Some(next)) => next,
+                    // every call has completed, or we are out of time
+                    Ok(None) | Err(_) => break,
+                };
+                if let Some(response) = next {
+                    if futures::sink::SinkExt::send(&mut send, response)
+                        .await
+                        .is_err()
+                    {
+                        // the receiver was dropped
+                        break;
+                    }
+                    sent += 1;
+                }
             }
-            _ => false,
-        };
+            // dropping the sender closes the stream
+        });
+
+        Ok(async move { Ok(recv) }.boxed().into())
+    }
+
+    fn handle_try_notify_multi(
+        &mut self,
+        input: actor::NotifyMulti,
+    ) -> KitsuneP2pHandlerResult<u8> {
+        // the outbound queue is checked before reaching the space
+        self.handle_notify_multi(input)
+    }
+
+    fn handle_notify_multi(
+        &mut self,
+        mut input: actor::NotifyMulti,
+    ) -> KitsuneP2pHandlerResult<u8> {
+        // if set to 0, we want to return immediately, but
+        // spawn a task with the default timeout.
+        let do_spawn = self.apply_notify_multi_defaults(&mut input);
 
         // gather the inner future
         let inner_fut = match self.handle_notify_multi_inner(input) {
@@ -748,8 +1291,181 @@ impl KitsuneP2pHandler for Space {
             tokio::task::spawn(inner_fut);
             Ok(async move { Ok(0) }.boxed().into())
         } else {
-            Ok(inner_fut)
+            Ok(async move { Ok(inner_fut.await?.len() as u8) }
+                .boxed()
+                .into())
+        }
+    }
+
+    fn handle_notify_multi_detailed(
+        &mut self,
+        mut input: actor::NotifyMulti,
+    ) -> KitsuneP2pHandlerResult<Vec<Arc<KitsuneAgent>>> {
+        // the caller wants to know who acknowledged,
+        // so always await the default timeout rather than spawning
+        self.apply_notify_multi_defaults(&mut input);
+        self.handle_notify_multi_inner(input)
+    }
+
+    fn handle_publish_ops(&mut self, input: actor::PublishOps) -> KitsuneP2pHandlerResult<u32> {
+        let actor::PublishOps {
+            space,
+            from_agent,
+            basis,
+            remote_agent_count,
+            timeout_ms,
+            ops,
+            ..
+        } = input;
+
+        let remote_agent_count = match remote_agent_count {
+            None | Some(0) => self.config.tuning_params.default_notify_remote_agent_count as u8,
+            Some(count) => count,
+        };
+        let timeout_ms = match timeout_ms {
+            None | Some(0) => self.config.tuning_params.default_notify_timeout_ms as u64,
+            Some(timeout_ms) => timeout_ms,
+        };
+        let max_nodes = self.config.tuning_params.default_notify_max_nodes as usize;
+
+        // don't send the same op twice
+        let mut seen = HashSet::new();
+        let ops: Vec<(Arc<KitsuneOpHash>, Vec<u8>)> = ops
+            .into_iter()
+            .filter(|(op_hash, _)| seen.insert(op_hash.clone()))
+            .collect();
+
+        // as with notify_multi, local joins get the ops
+        // but don't count toward our publish total
+        let local_all = self
+            .local_joined_agents
+            .iter()
+            .flat_map(|agent| {
+                ops.iter()
+                    .map(|(op_hash, op_data)| {
+                        self.evt_sender.gossip(
+                            space.clone(),
+                            agent.clone(),
+                            from_agent.clone(),
+                            op_hash.clone(),
+                            op_data.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let remote_fut = discover::message_neighborhood(
+            self,
+            from_agent.clone(),
+            remote_agent_count,
+            max_nodes,
+            timeout_ms / 2,
+            timeout_ms,
+            basis,
+            wire::Wire::gossip(
+                space,
+                from_agent.clone(),
+                from_agent,
+                ops.into_iter().map(|(k, v)| (k, v.into())).collect(),
+                Vec::new(),
+            ),
+            |a, w| match w {
+                wire::Wire::GossipResp(_) => Ok(a),
+                _ => Err(()),
+            },
+        );
+
+        Ok(async move {
+            futures::future::try_join_all(local_all).await?;
+
+            Ok(remote_fut.await.len() as u32)
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_gossip_hashes(
+        &mut self,
+        input: actor::Gossip,
+    ) -> KitsuneP2pHandlerResult<actor::GossipResponse> {
+        let actor::Gossip {
+            from_agent,
+            to_agent,
+            since_utc_epoch_s,
+            until_utc_epoch_s,
+            op_hashes,
+            ..
+        } = input;
+
+        // the gossip module's request handles both local and remote agents
+        let to_fut = gossip::GossipEventHandler::handle_req_op_hashes(
+            self,
+            ReqOpHashesEvt::new(
+                from_agent,
+                to_agent,
+                kitsune_p2p_types::dht_arc::DhtArc::new(0, u32::MAX),
+                since_utc_epoch_s,
+                until_utc_epoch_s,
+                // we always want the full list of hashes back
+                OpCount::Variance,
+            ),
+        )?;
+
+        Ok(async move {
+            let to_hashes = match to_fut.await?.0 {
+                OpConsistency::Variance(hashes) => hashes,
+                OpConsistency::Consistent => {
+                    return Err("expected op hashes in response to a variance".into())
+                }
+            };
+            let from_hashes: HashSet<Arc<KitsuneOpHash>> = op_hashes.into_iter().collect();
+            let to_hashes: HashSet<Arc<KitsuneOpHash>> = to_hashes.into_iter().collect();
+            Ok(actor::GossipResponse {
+                missing_locally: to_hashes.difference(&from_hashes).cloned().collect(),
+                missing_remotely: from_hashes.difference(&to_hashes).cloned().collect(),
+            })
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_close(&mut self, input: actor::Close) -> KitsuneP2pHandlerResult<()> {
+        let actor::Close {
+            space,
+            to_agent,
+            code,
+            reason,
+        } = input;
+
+        // there is no connection to close for a local agent
+        if self.local_joined_agents.contains(&to_agent) {
+            return Ok(async move { Ok(()) }.boxed().into());
         }
+
+        let ep_hnd = self.ep_hnd.clone();
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let info = match evt_sender
+                .get_agent_info_signed(GetAgentInfoSignedEvt {
+                    space,
+                    agent: to_agent.clone(),
+                })
+                .await?
+            {
+                None => return Err(KitsuneP2pError::RoutingAgentError(to_agent)),
+                Some(i) => i,
+            };
+            let info = types::agent_store::AgentInfo::try_from(&info)?;
+            for url in info.as_urls_ref() {
+                ep_hnd
+                    .close_connection(url.clone().into(), code, &reason)
+                    .await;
+            }
+            Ok(())
+        }
+        .boxed()
+        .into())
     }
 }
 
@@ -761,29 +1477,67 @@ pub(crate) struct Space {
     pub(crate) i_s: ghost_actor::GhostSender<SpaceInternal>,
     pub(crate) evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     pub(crate) ep_hnd: Tx2EpHnd<wire::Wire>,
+    /// The actor's open connections, shared by all spaces.
+    pub(crate) connections: Arc<ConnectionLru>,
     pub(crate) local_joined_agents: HashSet<Arc<KitsuneAgent>>,
+    /// The storage arc each locally joined agent announced at join time.
+    pub(crate) local_agent_arcs: HashMap<Arc<KitsuneAgent>, DhtArc>,
+    /// The node's config, with this space's overrides applied.
     pub(crate) config: Arc<KitsuneP2pConfig>,
+    /// The node's config, before this space's overrides.
+    node_config: Arc<KitsuneP2pConfig>,
+    pub(crate) peer_latencies: Arc<PeerLatencies>,
+    pub(crate) gossip_counters: Arc<gossip::GossipCounters>,
+    /// The bootstrap services to use, in the order they should be tried.
+    pub(crate) bootstrap_services: tokio::sync::watch::Receiver<Vec<url2::Url2>>,
     mdns_handles: HashMap<Vec<u8>, Arc<AtomicBool>>,
     mdns_listened_spaces: HashSet<String>,
 }
 
 impl Space {
     /// space constructor
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         space: Arc<KitsuneSpace>,
         this_addr: url2::Url2,
         i_s: ghost_actor::GhostSender<SpaceInternal>,
         evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
         ep_hnd: Tx2EpHnd<wire::Wire>,
+        connections: Arc<ConnectionLru>,
+        node_config: Arc<KitsuneP2pConfig>,
         config: Arc<KitsuneP2pConfig>,
+        rejoin_policy: tokio::sync::watch::Receiver<actor::RejoinPolicy>,
+        bootstrap_services: tokio::sync::watch::Receiver<Vec<url2::Url2>>,
+        gossip_counters: Arc<gossip::GossipCounters>,
     ) -> Self {
         let i_s_c = i_s.clone();
         tokio::task::spawn(async move {
+            let refresh = std::time::Duration::from_secs(5 * 60);
+            let mut delay = refresh;
+            let mut attempt = 0;
             loop {
-                tokio::time::sleep(std::time::Duration::from_secs(5 * 60)).await;
-                if let Err(e) = i_s_c.update_agent_info().await {
-                    tracing::error!(failed_to_update_agent_info_for_space = ?e);
-                }
+                tokio::time::sleep(delay).await;
+                delay = match i_s_c.update_agent_info().await {
+                    Ok(()) => {
+                        attempt = 0;
+                        refresh
+                    }
+                    Err(e) => {
+                        tracing::error!(failed_to_update_agent_info_for_space = ?e);
+                        // re-announce our joined agents according to the policy
+                        let retry = rejoin_policy.borrow().delay_for_attempt(attempt);
+                        match retry {
+                            Some(retry) => {
+                                attempt += 1;
+                                retry
+                            }
+                            None => {
+                                attempt = 0;
+                                refresh
+                            }
+                        }
+                    }
+                };
             }
         });
 
@@ -793,13 +1547,27 @@ impl Space {
             i_s,
             evt_sender,
             ep_hnd,
+            connections,
             local_joined_agents: HashSet::new(),
+            local_agent_arcs: HashMap::new(),
+            peer_latencies: PeerLatencies::new(config.tuning_params.peer_latency_window),
+            gossip_counters,
             config,
+            node_config,
+            bootstrap_services,
             mdns_handles: HashMap::new(),
             mdns_listened_spaces: HashSet::new(),
         }
     }
 
+    /// The half length of the storage arc a local agent announced,
+    /// or the full arc if it didn't announce one.
+    fn local_agent_half_length(&self, agent: &Arc<KitsuneAgent>) -> u32 {
+        self.local_agent_arcs
+            .get(agent)
+            .map_or(MAX_HALF_LENGTH, |arc| arc.half_length)
+    }
+
     /// actual logic for handle_rpc_multi ...
     /// the top-level handler may or may not spawn a task for this
     #[tracing::instrument(skip(self, input))]
@@ -807,18 +1575,6 @@ impl Space {
         &mut self,
         input: actor::RpcMulti,
     ) -> KitsuneP2pHandlerResult<Vec<actor::RpcMultiResponse>> {
-        let actor::RpcMulti {
-            space,
-            from_agent,
-            //basis,
-            //remote_agent_count,
-            //timeout_ms,
-            //as_race,
-            //race_timeout_ms,
-            payload,
-            ..
-        } = input;
-
         // TODO - FIXME - david.b - removing the parts of this that
         // actually make remote requests. We can get this data locally
         // while we are still full sync after gossip, and the timeouts
@@ -832,27 +1588,14 @@ impl Space {
 
         // as an optimization - request to all local joins
         // but don't count that toward our request total
-        let local_all = self
-            .local_joined_agents
-            .iter()
-            .map(|agent| {
-                let agent = agent.clone();
-                self.evt_sender
-                    .call(
-                        space.clone(),
-                        agent.clone(),
-                        from_agent.clone(),
-                        payload.clone(),
-                    )
-                    .then(|r| async move { (r, agent) })
-            })
-            .collect::<Vec<_>>();
+        let local_all = self.local_rpc_multi_calls(&input);
 
         /*
         let remote_fut = discover::message_neighborhood(
             self,
             from_agent.clone(),
             remote_agent_count,
+            usize::MAX,
             stage_1_timeout_ms,
             timeout_ms,
             basis,
@@ -866,6 +1609,7 @@ impl Space {
                 wire::Wire::CallResp(c) => Ok(actor::RpcMultiResponse {
                     agent: a,
                     response: c.data.into(),
+                    authoritative: false,
                 }),
                 _ => Err(()),
             },
@@ -877,16 +1621,7 @@ impl Space {
             let out: Vec<actor::RpcMultiResponse> = futures::future::join_all(local_all)
                 .await
                 .into_iter()
-                .filter_map(|(r, a)| {
-                    if let Ok(r) = r {
-                        Some(actor::RpcMultiResponse {
-                            agent: a,
-                            response: r,
-                        })
-                    } else {
-                        None
-                    }
-                })
+                .flatten()
                 .collect();
 
             //out.append(&mut remote_fut.await);
@@ -898,22 +1633,148 @@ impl Space {
         .into())
     }
 
+    /// Fill in the defaults for `remote_agent_count`, `timeout_ms`
+    /// and `race_timeout_ms`.
+    fn apply_rpc_multi_defaults(&self, input: &mut actor::RpcMulti) {
+        // if the user doesn't care about remote_agent_count, apply default
+        match input.remote_agent_count {
+            None | Some(0) => {
+                input.remote_agent_count = Some(
+                    self.config
+                        .tuning_params
+                        .default_rpc_multi_remote_agent_count as u8,
+                );
+            }
+            _ => {}
+        }
+
+        // if the user doesn't care about timeout_ms, apply default
+        match input.timeout_ms {
+            None | Some(0) => {
+                input.timeout_ms =
+                    Some(self.config.tuning_params.default_rpc_multi_timeout_ms as u64);
+            }
+            _ => {}
+        }
+
+        // if the user doesn't care about race_timeout_ms, apply default
+        match input.race_timeout_ms {
+            None | Some(0) => {
+                input.race_timeout_ms = Some(DEFAULT_RPC_MULTI_RACE_TIMEOUT_MS);
+            }
+            _ => {}
+        }
+
+        // race timeout > timeout is nonesense
+        if input.as_race && input.race_timeout_ms.unwrap() > input.timeout_ms.unwrap() {
+            input.race_timeout_ms = Some(input.timeout_ms.unwrap());
+        }
+    }
+
+    /// One call per locally joined agent, each resolving to that agent's
+    /// response, or `None` if the call failed.
+    fn local_rpc_multi_calls(
+        &self,
+        input: &actor::RpcMulti,
+    ) -> Vec<futures::future::BoxFuture<'static, Option<actor::RpcMultiResponse>>> {
+        let actor::RpcMulti {
+            space,
+            from_agent,
+            basis,
+            remote_agent_count,
+            selection,
+            payload,
+            ..
+        } = input;
+
+        let agents = discover::select_agents(
+            *selection,
+            basis,
+            remote_agent_count.unwrap_or(u8::MAX) as usize,
+            self.local_joined_agents.iter().cloned().collect(),
+        );
+
+        agents
+            .into_iter()
+            .map(|agent| {
+                let evt_sender = self.evt_sender.clone();
+                let space = space.clone();
+                let basis = basis.clone();
+                let call = self.evt_sender.call(
+                    space.clone(),
+                    agent.clone(),
+                    from_agent.clone(),
+                    payload.clone(),
+                );
+                async move {
+                    let response = call.await.ok()?;
+                    let authoritative =
+                        agent_covers_basis(&evt_sender, space, agent.clone(), &basis).await;
+                    Some(actor::RpcMultiResponse {
+                        agent,
+                        response,
+                        authoritative,
+                    })
+                }
+                .boxed()
+            })
+            .collect()
+    }
+
+    /// Fill in the defaults for `remote_agent_count`, `max_nodes` and `timeout_ms`,
+    /// and generate a `msg_id` if there isn't one.
+    /// Returns `true` if the caller didn't specify a timeout, i.e. doesn't
+    /// care to wait on the result.
+    fn apply_notify_multi_defaults(&self, input: &mut actor::NotifyMulti) -> bool {
+        // if the user doesn't care about remote_agent_count, apply default
+        match input.remote_agent_count {
+            None | Some(0) => {
+                input.remote_agent_count =
+                    Some(self.config.tuning_params.default_notify_remote_agent_count as u8);
+            }
+            _ => {}
+        }
+
+        // if the user doesn't care about max_nodes, apply default
+        if input.max_nodes == 0 {
+            input.max_nodes = self.config.tuning_params.default_notify_max_nodes;
+        }
+
+        if input.msg_id.is_none() {
+            input.msg_id = Some(rand::random());
+        }
+
+        // if the user doesn't care about timeout_ms, apply default
+        match input.timeout_ms {
+            None | Some(0) => {
+                input.timeout_ms = Some(self.config.tuning_params.default_notify_timeout_ms as u64);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// actual logic for handle_notify_multi ...
-    /// the top-level handler may or may not spawn a task for this
+    /// the top-level handler may or may not spawn a task for this.
+    /// Resolves to the remote agents that acknowledged the notify.
     fn handle_notify_multi_inner(
         &mut self,
         input: actor::NotifyMulti,
-    ) -> KitsuneP2pHandlerResult<u8> {
+    ) -> KitsuneP2pHandlerResult<Vec<Arc<KitsuneAgent>>> {
         let actor::NotifyMulti {
             space,
             from_agent,
             basis,
             remote_agent_count,
             timeout_ms,
+            max_nodes,
             payload,
+            msg_id,
+            ..
         } = input;
 
         let remote_agent_count = remote_agent_count.expect("set by handle_notify_multi");
+        let msg_id = msg_id.unwrap_or_else(rand::random);
         let timeout_ms = timeout_ms.expect("set by handle_notify_multi");
         let stage_1_timeout_ms = timeout_ms / 2;
 
@@ -936,17 +1797,19 @@ impl Space {
             self,
             from_agent.clone(),
             remote_agent_count,
+            max_nodes as usize,
             stage_1_timeout_ms,
             timeout_ms,
             basis,
-            wire::Wire::notify(
+            wire::Wire::notify_with_id(
                 space.clone(),
                 from_agent.clone(),
                 from_agent,
                 payload.into(),
+                msg_id,
             ),
-            |_, w| match w {
-                wire::Wire::NotifyResp(_) => Ok(()),
+            |a, w| match w {
+                wire::Wire::NotifyResp(_) => Ok(a),
                 _ => Err(()),
             },
         );
@@ -954,9 +1817,135 @@ impl Space {
         Ok(async move {
             futures::future::try_join_all(local_all).await?;
 
-            Ok(remote_fut.await.len() as u8)
+            Ok(remote_fut.await)
         }
         .boxed()
         .into())
     }
 }
+
+/// Does this local agent's published storage arc cover the basis location?
+/// An agent without (decodable) agent info is never considered authoritative.
+async fn agent_covers_basis(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    space: Arc<KitsuneSpace>,
+    agent: Arc<KitsuneAgent>,
+    basis: &KitsuneBasis,
+) -> bool {
+    let info = match evt_sender
+        .get_agent_info_signed(GetAgentInfoSignedEvt { space, agent })
+        .await
+    {
+        Ok(Some(info)) => info,
+        _ => return false,
+    };
+    types::agent_store::AgentInfo::try_from(&info)
+        .and_then(|info| info.dht_arc())
+        .map(|arc| arc.contains(basis.get_loc()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lair_keystore_api::internal::sign_ed25519::*;
+
+    /// An agent info for `space` signed by `signer` on behalf of `agent`.
+    async fn signed_info(
+        space: &KitsuneSpace,
+        agent: &KitsuneAgent,
+        signer: &SignEd25519PrivKey,
+        urls: Vec<url2::Url2>,
+        signed_at_ms: u64,
+    ) -> types::agent_store::AgentInfoSigned {
+        let info = types::agent_store::AgentInfo::new(
+            space.clone(),
+            agent.clone(),
+            urls,
+            signed_at_ms,
+            60_000,
+        );
+        let mut data = Vec::new();
+        rmp_encode(&mut data, &info).unwrap();
+        let sig = sign_ed25519(signer.clone(), Arc::new(data.clone()))
+            .await
+            .unwrap();
+        types::agent_store::AgentInfoSigned::try_new(agent.clone(), (**sig).clone().into(), data)
+            .unwrap()
+    }
+
+    /// An event sender backed by a single slot peer store.
+    fn peer_store(
+        held: Option<types::agent_store::AgentInfoSigned>,
+    ) -> (
+        futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+        Arc<std::sync::Mutex<Option<types::agent_store::AgentInfoSigned>>>,
+    ) {
+        let store = Arc::new(std::sync::Mutex::new(held));
+        let (evt_send, mut evt_recv) = futures::channel::mpsc::channel(10);
+        let task_store = store.clone();
+        tokio::task::spawn(async move {
+            while let Some(evt) = evt_recv.next().await {
+                match evt {
+                    KitsuneP2pEvent::GetAgentInfoSigned { respond, .. } => {
+                        let held = task_store.lock().unwrap().clone();
+                        respond.r(Ok(async move { Ok(held) }.boxed().into()));
+                    }
+                    KitsuneP2pEvent::PutAgentInfoSigned { respond, input, .. } => {
+                        *task_store.lock().unwrap() = Some(input.agent_info_signed);
+                        respond.r(Ok(async move { Ok(()) }.boxed().into()));
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        });
+        (evt_send, store)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn leave_tombstone_must_be_signed_by_agent() {
+        let space: Arc<KitsuneSpace> = Arc::new(KitsuneSpace::new(vec![0; 36]));
+        let alice = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+        let mallory = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+        let agent = KitsuneAgent::new((**alice.pub_key).clone());
+        let url = url2::url2!("kitsune-mem://alice");
+
+        let held = signed_info(&space, &agent, &alice.priv_key, vec![url], 1000).await;
+        let (evt_sender, store) = peer_store(Some(held.clone()));
+
+        // mallory cannot evict alice
+        let forged = signed_info(&space, &agent, &mallory.priv_key, Vec::new(), 2000).await;
+        assert!(!forged.verify_signature().await.unwrap());
+        assert!(!local_leave(&evt_sender, space.clone(), forged)
+            .await
+            .unwrap());
+        assert_eq!(Some(held), *store.lock().unwrap());
+
+        // but alice can leave
+        let tombstone = signed_info(&space, &agent, &alice.priv_key, Vec::new(), 2000).await;
+        assert!(local_leave(&evt_sender, space.clone(), tombstone.clone())
+            .await
+            .unwrap());
+        assert_eq!(Some(tombstone), *store.lock().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn leave_tombstone_must_be_newer_than_held_info() {
+        let space: Arc<KitsuneSpace> = Arc::new(KitsuneSpace::new(vec![0; 36]));
+        let alice = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+        let agent = KitsuneAgent::new((**alice.pub_key).clone());
+        let url = url2::url2!("kitsune-mem://alice");
+
+        // alice left, then rejoined
+        let stale = signed_info(&space, &agent, &alice.priv_key, Vec::new(), 1000).await;
+        let rejoined = signed_info(&space, &agent, &alice.priv_key, vec![url], 2000).await;
+        let (evt_sender, store) = peer_store(Some(rejoined.clone()));
+
+        // so replaying the old tombstone changes nothing
+        assert!(stale.verify_signature().await.unwrap());
+        assert!(!local_leave(&evt_sender, space.clone(), stale)
+            .await
+            .unwrap());
+        assert_eq!(Some(rejoined), *store.lock().unwrap());
+    }
+}
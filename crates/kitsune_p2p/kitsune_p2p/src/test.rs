@@ -40,6 +40,162 @@ mod tests {
         crate::types::metrics::print_all_metrics();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_simulated_network() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem_sim(50, 0.0).await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        // both the request and the response are delayed
+        let start = std::time::Instant::now();
+        let res = p2p1
+            .rpc_single(space.clone(), a2, a1, b"m1".to_vec(), None)
+            .await?;
+        assert_eq!(b"echo: m1".to_vec(), res);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+
+        harness.ghost_actor_shutdown().await?;
+
+        // nothing gets through a network that drops every message
+        let (harness, _evt) = spawn_test_harness_mem_sim(0, 1.0).await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        harness.magic_peer_info_exchange().await?;
+
+        let res = p2p1
+            .rpc_single(space, a2, a1, b"m1".to_vec(), Some(500))
+            .await;
+        assert!(res.is_err());
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_notify_single() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, evt) = spawn_test_harness_mem().await?;
+        let mut rcv = evt.receive();
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        // resolves on receipt, so the notify may not have been handled yet
+        p2p1.notify_single(space, a2, a1, b"test-notify".to_vec(), None)
+            .await?;
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while let Some(evt) = tokio_stream::StreamExt::next(&mut rcv).await {
+                if &**evt.nick != "two" {
+                    continue;
+                }
+                if let test_util::HarnessEventType::Notify { payload, .. } = &evt.ty {
+                    assert_eq!(&**payload, "test-notify");
+                    return;
+                }
+            }
+            panic!("event stream ended");
+        })
+        .await
+        .expect("notify was not delivered");
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_peer_latencies() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        let (a3, _p2p3) = harness.add_direct_agent("tre".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        assert!(p2p1.peer_latencies(space.clone()).await?.is_empty());
+
+        // a2 is slow to respond, a3 is not
+        for _ in 0..2 {
+            p2p1.rpc_single(
+                space.clone(),
+                a2.clone(),
+                a1.clone(),
+                b"delay:200".to_vec(),
+                None,
+            )
+            .await?;
+            p2p1.rpc_single(space.clone(), a3.clone(), a1.clone(), b"m".to_vec(), None)
+                .await?;
+        }
+
+        let latencies: std::collections::HashMap<_, _> = p2p1
+            .peer_latencies(space.clone())
+            .await?
+            .into_iter()
+            .collect();
+        assert_eq!(2, latencies.len());
+        let slow = latencies[&a2];
+        let fast = latencies[&a3];
+        assert!(slow >= std::time::Duration::from_millis(200));
+        assert!(fast < slow);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_ping() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        // a locally joined agent is always reachable
+        let rtt = p2p1.ping(space.clone(), a1, Some(1000)).await?;
+        assert_eq!(Some(std::time::Duration::default()), rtt);
+
+        let rtt = p2p1.ping(space.clone(), a2.clone(), Some(1000)).await?;
+        assert!(rtt.is_some());
+
+        // the ping round-trip is also tracked as a peer latency
+        let latencies = p2p1.peer_latencies(space.clone()).await?;
+        assert_eq!(
+            vec![a2],
+            latencies.into_iter().map(|(a, _)| a).collect::<Vec<_>>()
+        );
+
+        // nobody has heard of this agent
+        let unknown = Arc::new(KitsuneAgent::new(vec![0xdb; 36]));
+        let s = std::time::Instant::now();
+        let rtt = p2p1.ping(space, unknown, Some(100)).await?;
+        assert_eq!(None, rtt);
+        assert!(s.elapsed() < std::time::Duration::from_secs(5));
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[ignore] // david.b disabled while we're full sync, not actually making
               //         get requests
@@ -65,6 +221,8 @@ mod tests {
                 timeout_ms: Some(200),
                 as_race: true,
                 race_timeout_ms: Some(100),
+                priority: actor::MessagePriority::Normal,
+                selection: actor::AgentSelection::Any,
                 payload: b"test-multi-request".to_vec(),
             })
             .await
@@ -102,7 +260,10 @@ mod tests {
             basis: TestVal::test_val(),
             remote_agent_count: Some(42),
             timeout_ms: Some(40),
+            max_nodes: 0,
+            priority: actor::MessagePriority::Normal,
             payload: b"test-broadcast".to_vec(),
+            msg_id: None,
         })
         .await?;
 
@@ -116,84 +277,706 @@ mod tests {
             }
         }
 
-        assert_eq!(3, recv_count);
+        assert_eq!(3, recv_count);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transport_notify_jitter() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, evt) = spawn_test_harness_mem().await?;
+        let mut rcv = evt.receive();
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (_a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let jitter = std::time::Duration::from_millis(100);
+        p2p1.set_notify_jitter(jitter).await?;
+
+        // held back by at most the jitter, then sent as usual
+        let start = std::time::Instant::now();
+        p2p1.notify_multi(actor::NotifyMulti {
+            space: space,
+            from_agent: a1,
+            basis: TestVal::test_val(),
+            remote_agent_count: Some(42),
+            timeout_ms: Some(40),
+            max_nodes: 0,
+            priority: actor::MessagePriority::Normal,
+            payload: b"test-jitter".to_vec(),
+            msg_id: None,
+        })
+        .await?;
+        assert!(start.elapsed() < jitter + std::time::Duration::from_secs(2));
+
+        harness.ghost_actor_shutdown().await?;
+
+        let mut recv_count = 0_usize;
+        while let Some(evt) = tokio_stream::StreamExt::next(&mut rcv).await {
+            if let test_util::HarnessEventType::Notify { payload, .. } = &evt.ty {
+                assert_eq!(&**payload, "test-jitter");
+                recv_count += 1;
+            }
+        }
+
+        assert_eq!(2, recv_count);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transport_notify_dedup() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, evt) = spawn_test_harness_mem().await?;
+        let mut rcv = evt.receive();
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (_a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        let (_a3, _p2p3) = harness.add_direct_agent("tre".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        // the same notify arriving twice, as it might along two paths
+        for _ in 0..2 {
+            let reached = p2p1
+                .notify_multi_detailed(actor::NotifyMulti {
+                    space: space.clone(),
+                    from_agent: a1.clone(),
+                    // this is just a dummy value right now
+                    basis: TestVal::test_val(),
+                    remote_agent_count: Some(2),
+                    timeout_ms: Some(1000),
+                    max_nodes: 0,
+                    priority: actor::MessagePriority::Normal,
+                    payload: b"test-broadcast".to_vec(),
+                    msg_id: Some(42),
+                })
+                .await?;
+            // duplicates are still acknowledged
+            assert_eq!(2, reached.len());
+        }
+
+        harness.ghost_actor_shutdown().await?;
+
+        let mut recv_count = 0_usize;
+        while let Some(evt) = tokio_stream::StreamExt::next(&mut rcv).await {
+            // the sender hands it to its own agents directly
+            if &**evt.nick == "one" {
+                continue;
+            }
+            if let test_util::HarnessEventType::Notify { payload, .. } = &evt.ty {
+                assert_eq!(&**payload, "test-broadcast");
+                recv_count += 1;
+            }
+        }
+
+        assert_eq!(2, recv_count);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transport_notify_detailed() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        let (a3, _p2p3) = harness.add_direct_agent("tre".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let mut reached = p2p1
+            .notify_multi_detailed(actor::NotifyMulti {
+                space: space,
+                from_agent: a1,
+                // this is just a dummy value right now
+                basis: TestVal::test_val(),
+                remote_agent_count: Some(2),
+                timeout_ms: Some(1000),
+                max_nodes: 0,
+                priority: actor::MessagePriority::Normal,
+                payload: b"test-broadcast".to_vec(),
+                msg_id: None,
+            })
+            .await?;
+
+        harness.ghost_actor_shutdown().await?;
+
+        // only the remote agents are reported
+        reached.sort();
+        let mut expected = vec![a2, a3];
+        expected.sort();
+        assert_eq!(expected, reached);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transport_notify_max_nodes() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, evt) = spawn_test_harness_mem().await?;
+        let mut rcv = evt.receive();
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        for name in &["two", "tre", "for", "fiv"] {
+            harness.add_direct_agent(name.to_string()).await?;
+        }
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        // ask for more nodes than the cap allows
+        let reached = p2p1
+            .notify_multi_detailed(actor::NotifyMulti {
+                space: space,
+                from_agent: a1,
+                // this is just a dummy value right now
+                basis: TestVal::test_val(),
+                remote_agent_count: Some(42),
+                timeout_ms: Some(200),
+                max_nodes: 2,
+                priority: actor::MessagePriority::Normal,
+                payload: b"test-broadcast".to_vec(),
+                msg_id: None,
+            })
+            .await?;
+
+        harness.ghost_actor_shutdown().await?;
+
+        assert_eq!(2, reached.len());
+
+        // the local agent, plus the two capped remotes
+        let mut recv_count = 0_usize;
+        while let Some(evt) = tokio_stream::StreamExt::next(&mut rcv).await {
+            if let test_util::HarnessEventType::Notify { payload, .. } = &evt.ty {
+                assert_eq!(&**payload, "test-broadcast");
+                recv_count += 1;
+            }
+        }
+        assert_eq!(3, recv_count);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_join_with_arc() -> Result<(), KitsuneP2pError> {
+        use kitsune_p2p_types::dht_arc::{DhtArc, MAX_HALF_LENGTH};
+        use std::convert::TryFrom;
+
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, p2p2) = harness.add_direct_agent("two".into()).await?;
+        let (a3, _p2p3) = harness.add_direct_agent("tre".into()).await?;
+
+        // "two" re-announces itself as holding nothing
+        p2p2.join(space.clone(), a2.clone(), Some(DhtArc::new(0, 0)))
+            .await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let half_length = |info: &agent_store::AgentInfoSigned| {
+            agent_store::AgentInfo::try_from(info)
+                .and_then(|info| info.dht_arc())
+                .map(|arc| arc.half_length)
+        };
+        let info = p2p1.get_agent_info(space.clone(), a2.clone()).await?;
+        assert_eq!(0, half_length(&info)?);
+        let info = p2p1.get_agent_info(space.clone(), a3.clone()).await?;
+        assert_eq!(MAX_HALF_LENGTH, half_length(&info)?);
+
+        // only the agent covering the basis is messaged
+        let reached = p2p1
+            .notify_multi_detailed(actor::NotifyMulti {
+                space: space,
+                from_agent: a1,
+                basis: TestVal::test_val(),
+                remote_agent_count: Some(2),
+                timeout_ms: Some(200),
+                max_nodes: 0,
+                priority: actor::MessagePriority::Normal,
+                payload: b"test-broadcast".to_vec(),
+                msg_id: None,
+            })
+            .await?;
+        assert_eq!(vec![a3], reached);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transport_publish_ops() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, evt) = spawn_test_harness_mem().await?;
+        let mut rcv = evt.receive();
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (_a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        let (_a3, _p2p3) = harness.add_direct_agent("tre".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let op_1: Arc<KitsuneOpHash> = TestVal::test_val();
+        let op_2: Arc<KitsuneOpHash> = TestVal::test_val();
+        let accepted = p2p1
+            .publish_ops(actor::PublishOps {
+                space: space,
+                from_agent: a1,
+                // this is just a dummy value right now
+                basis: TestVal::test_val(),
+                remote_agent_count: Some(2),
+                timeout_ms: Some(1000),
+                priority: actor::MessagePriority::High,
+                ops: vec![
+                    (op_1.clone(), b"op-1".to_vec()),
+                    (op_2, b"op-2".to_vec()),
+                    (op_1, b"op-1-again".to_vec()),
+                ],
+            })
+            .await?;
+
+        harness.ghost_actor_shutdown().await?;
+
+        // only the remote agents are counted
+        assert_eq!(2, accepted);
+
+        let mut received = std::collections::HashSet::new();
+        while let Some(evt) = tokio_stream::StreamExt::next(&mut rcv).await {
+            if let test_util::HarnessEventType::Gossip { op_data, .. } = &evt.ty {
+                received.insert(op_data.clone());
+            }
+        }
+        // the duplicate op hash was only sent once
+        let expected: std::collections::HashSet<String> =
+            vec!["op-1".to_string(), "op-2".to_string()]
+                .into_iter()
+                .collect();
+        assert_eq!(expected, received);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_peer_info_store() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, evt) = spawn_test_harness_mem().await?;
+        let mut recv = evt.receive();
+
+        harness.add_space().await?;
+        let (agent, _p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+
+        harness.ghost_actor_shutdown().await?;
+
+        let mut agent_info_signed = None;
+
+        use tokio_stream::StreamExt;
+        while let Some(item) = recv.next().await {
+            if let HarnessEventType::StoreAgentInfo { agent, .. } = item.ty {
+                agent_info_signed = Some((agent,));
+            }
+        }
+
+        if let Some(i) = agent_info_signed {
+            assert_eq!(i.0, Slug::from(agent));
+            return Ok(());
+        }
+
+        panic!("Failed to receive agent_info_signed");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transport_binding() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_quic().await?;
+
+        // Create a p2p config with a local proxy that rejects proxying anyone else
+        // and binds to `kitsune-quic://0.0.0.0:0`.
+        // This allows the OS to assign an interface / port.
+        harness.add_space().await?;
+        let (_, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+
+        // List the bindings and assert that we have one binding that is a
+        // kitsune-proxy scheme with a kitsune-quic url.
+        let bindings = p2p.list_transport_bindings().await?;
+        tracing::warn!("BINDINGS: {:?}", bindings);
+        assert_eq!(1, bindings.len());
+        let binding = &bindings[0];
+        assert_eq!("kitsune-proxy", binding.scheme());
+        assert_eq!(
+            "kitsune-quic",
+            binding.path_segments().unwrap().next().unwrap()
+        );
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_workflow() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_quic().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        // TODO when networking works, just add_*_agent again...
+        // but for now, we need the two agents to be on the same node:
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        let res = p2p
+            .rpc_single(space, a2, a1, b"hello".to_vec(), None)
+            .await?;
+        assert_eq!(b"echo: hello".to_vec(), res);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_timeout() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_quic().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        // the harness never replies to this payload
+        let s = std::time::Instant::now();
+        let res = p2p
+            .rpc_single(space, a2, a1, b"no-reply".to_vec(), Some(100))
+            .await;
+        assert!(matches!(res, Err(KitsuneP2pError::Timeout)), "{:?}", res);
+        assert!(s.elapsed() < std::time::Duration::from_secs(5));
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_errors_by_cause() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+
+        // with no peers at all, there is no one to route to
+        let unknown: Arc<KitsuneAgent> = TestVal::test_val();
+        let res = p2p1
+            .rpc_single(
+                space.clone(),
+                unknown,
+                a1.clone(),
+                b"m1".to_vec(),
+                Some(200),
+            )
+            .await;
+        assert!(
+            matches!(res, Err(KitsuneP2pError::NoPeersFound)),
+            "{:?}",
+            res
+        );
+
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        harness.magic_peer_info_exchange().await?;
+
+        // a remote agent that never replies
+        let res = p2p1
+            .rpc_single(space, a2, a1, b"no-reply".to_vec(), Some(200))
+            .await;
+        assert!(matches!(res, Err(KitsuneP2pError::Timeout)), "{:?}", res);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rpc_single_with_fallback() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (_a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        harness.magic_peer_info_exchange().await?;
+
+        // no one can reach the first agent
+        let unknown: Arc<KitsuneAgent> = TestVal::test_val();
+        let input = actor::RpcSingleWithFallback {
+            space: space.clone(),
+            to_agent: unknown,
+            from_agent: a1,
+            basis: TestVal::test_val(),
+            max_retries: 0,
+            attempt_timeout_ms: Some(200),
+            payload: b"m1".to_vec(),
+        };
+
+        let res = p2p1.rpc_single_with_fallback(input.clone()).await;
+        assert!(res.is_err());
+
+        // the request is retried with the only other agent
+        let res = p2p1
+            .rpc_single_with_fallback(actor::RpcSingleWithFallback {
+                max_retries: 3,
+                ..input
+            })
+            .await?;
+        assert_eq!(b"echo: m1".to_vec(), res);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_try_multi_would_block() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let mut tuning_params =
+            kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::default();
+        tuning_params.concurrent_outbound_multi_limit = 1;
+        let (harness, _evt) =
+            spawn_test_harness_with_tuning_params(TransportConfig::Mem {}, Arc::new(tuning_params))
+                .await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (_a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+        harness.magic_peer_info_exchange().await?;
+
+        let rpc = |payload: &[u8]| actor::RpcMulti {
+            space: space.clone(),
+            from_agent: a1.clone(),
+            basis: TestVal::test_val(),
+            remote_agent_count: Some(2),
+            timeout_ms: Some(1000),
+            as_race: false,
+            race_timeout_ms: None,
+            priority: actor::MessagePriority::Normal,
+            selection: actor::AgentSelection::Any,
+            payload: payload.to_vec(),
+        };
+        let notify = actor::NotifyMulti {
+            space: space.clone(),
+            from_agent: a1.clone(),
+            basis: TestVal::test_val(),
+            remote_agent_count: Some(2),
+            timeout_ms: Some(40),
+            max_nodes: 0,
+            priority: actor::MessagePriority::Normal,
+            payload: b"test-notify".to_vec(),
+            msg_id: None,
+        };
+
+        // no one replies, so this holds the only slot until it times out
+        let blocking = tokio::task::spawn({
+            let p2p1 = p2p1.clone();
+            let input = rpc(b"no-reply");
+            async move { p2p1.rpc_multi(input).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let res = p2p1.try_rpc_multi(rpc(b"m1")).await;
+        assert!(matches!(res, Err(KitsuneP2pError::WouldBlock)), "{:?}", res);
+        let res = p2p1.try_notify_multi(notify.clone()).await;
+        assert!(matches!(res, Err(KitsuneP2pError::WouldBlock)), "{:?}", res);
+
+        // once the slot is free again, they are sent
+        let _ = blocking.await.unwrap();
+        let res = p2p1.try_rpc_multi(rpc(b"m1")).await?;
+        assert!(!res.is_empty());
+        p2p1.try_notify_multi(notify).await?;
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_message_too_large() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        let limit =
+            kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::default()
+                .max_message_bytes;
+
+        let res = p2p
+            .rpc_single(
+                space.clone(),
+                a2.clone(),
+                a1.clone(),
+                vec![0; limit + 1],
+                None,
+            )
+            .await;
+        match res {
+            Err(KitsuneP2pError::MessageTooLarge { size, limit: l }) => {
+                assert_eq!(limit + 1, size);
+                assert_eq!(limit, l);
+            }
+            r => panic!("expected MessageTooLarge, got {:?}", r),
+        }
+
+        let res = p2p
+            .notify_multi(actor::NotifyMulti {
+                space: space.clone(),
+                from_agent: a1.clone(),
+                basis: TestVal::test_val(),
+                remote_agent_count: None,
+                timeout_ms: None,
+                max_nodes: 0,
+                priority: actor::MessagePriority::Normal,
+                payload: vec![0; limit + 1],
+                msg_id: None,
+            })
+            .await;
+        assert!(matches!(res, Err(KitsuneP2pError::MessageTooLarge { .. })));
 
+        // a payload at the limit is still sent
+        let res = p2p
+            .rpc_single(space, a2, a1, vec![b'a'; limit], None)
+            .await?;
+        assert_eq!(limit + "echo: ".len(), res.len());
+
+        harness.ghost_actor_shutdown().await?;
         Ok(())
     }
 
     #[tokio::test(flavor = "multi_thread")]
-    async fn test_peer_info_store() -> Result<(), KitsuneP2pError> {
+    async fn test_pending_stats() -> Result<(), KitsuneP2pError> {
         observability::test_run().ok();
 
-        let (harness, evt) = spawn_test_harness_mem().await?;
-        let mut recv = evt.receive();
-
-        harness.add_space().await?;
-        let (agent, _p2p) = harness.add_direct_agent("DIRECT".into()).await?;
-
-        harness.ghost_actor_shutdown().await?;
+        let (harness, _evt) = spawn_test_harness_quic().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
 
-        let mut agent_info_signed = None;
+        assert_eq!(
+            actor::KitsuneP2pStats::default(),
+            p2p.pending_stats().await?
+        );
 
-        use tokio_stream::StreamExt;
-        while let Some(item) = recv.next().await {
-            if let HarnessEventType::StoreAgentInfo { agent, .. } = item.ty {
-                agent_info_signed = Some((agent,));
+        // the harness never replies to this payload
+        let pending = tokio::task::spawn({
+            let p2p = p2p.clone();
+            async move {
+                p2p.rpc_single(space, a2, a1, b"no-reply".to_vec(), Some(500))
+                    .await
             }
-        }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
-        if let Some(i) = agent_info_signed {
-            assert_eq!(i.0, Slug::from(agent));
-            return Ok(());
-        }
+        let stats = p2p.pending_stats().await?;
+        assert_eq!(1, stats.rpc_single_in_flight);
+        assert_eq!(0, stats.rpc_multi_in_flight);
 
-        panic!("Failed to receive agent_info_signed");
+        assert!(pending.await.unwrap().is_err());
+        assert_eq!(
+            actor::KitsuneP2pStats::default(),
+            p2p.pending_stats().await?
+        );
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
     }
 
     #[tokio::test(flavor = "multi_thread")]
-    async fn test_transport_binding() -> Result<(), KitsuneP2pError> {
+    async fn test_shutdown_drains_outstanding() -> Result<(), KitsuneP2pError> {
         observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
 
-        let (harness, _evt) = spawn_test_harness_quic().await?;
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        let slow = tokio::task::spawn({
+            let p2p = p2p.clone();
+            let (space, a1, a2) = (space.clone(), a1.clone(), a2.clone());
+            async move {
+                p2p.rpc_single(space, a2, a1, b"delay:300".to_vec(), Some(5000))
+                    .await
+            }
+        });
+        while p2p.pending_stats().await?.rpc_single_in_flight == 0 {
+            tokio::task::yield_now().await;
+        }
 
-        // Create a p2p config with a local proxy that rejects proxying anyone else
-        // and binds to `kitsune-quic://0.0.0.0:0`.
-        // This allows the OS to assign an interface / port.
-        harness.add_space().await?;
-        let (_, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let shutdown = tokio::task::spawn({
+            let p2p = p2p.clone();
+            async move { p2p.shutdown(5000).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 
-        // List the bindings and assert that we have one binding that is a
-        // kitsune-proxy scheme with a kitsune-quic url.
-        let bindings = p2p.list_transport_bindings().await?;
-        tracing::warn!("BINDINGS: {:?}", bindings);
-        assert_eq!(1, bindings.len());
-        let binding = &bindings[0];
-        assert_eq!("kitsune-proxy", binding.scheme());
-        assert_eq!(
-            "kitsune-quic",
-            binding.path_segments().unwrap().next().unwrap()
-        );
+        // new work is rejected while draining
+        let res = p2p
+            .rpc_single(space, a2, a1, b"hello".to_vec(), Some(5000))
+            .await;
+        assert!(matches!(res, Err(KitsuneP2pError::Shutdown)));
+
+        // but the outstanding request is allowed to complete
+        assert_eq!(b"echo: delay:300".to_vec(), slow.await.unwrap()?);
+        shutdown.await.unwrap()?;
 
         harness.ghost_actor_shutdown().await?;
         Ok(())
     }
 
     #[tokio::test(flavor = "multi_thread")]
-    async fn test_request_workflow() -> Result<(), KitsuneP2pError> {
+    async fn test_shutdown_drain_timeout() -> Result<(), KitsuneP2pError> {
         observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
 
-        let (harness, _evt) = spawn_test_harness_quic().await?;
         let space = harness.add_space().await?;
         let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
-        // TODO when networking works, just add_*_agent again...
-        // but for now, we need the two agents to be on the same node:
         let a2: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a2.clone()).await?;
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        // the harness never replies to this payload
+        let stuck = tokio::task::spawn({
+            let p2p = p2p.clone();
+            async move {
+                p2p.rpc_single(space, a2, a1, b"no-reply".to_vec(), Some(30_000))
+                    .await
+            }
+        });
+        while p2p.pending_stats().await?.rpc_single_in_flight == 0 {
+            tokio::task::yield_now().await;
+        }
 
-        let res = p2p
-            .rpc_single(space, a2, a1, b"hello".to_vec(), None)
-            .await?;
-        assert_eq!(b"echo: hello".to_vec(), res);
+        let start = std::time::Instant::now();
+        p2p.shutdown(100).await?;
+        assert!(matches!(
+            stuck.await.unwrap(),
+            Err(KitsuneP2pError::Shutdown)
+        ));
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
 
         harness.ghost_actor_shutdown().await?;
         Ok(())
@@ -214,9 +997,9 @@ mod tests {
         // TODO when networking works, just add_*_agent again...
         // but for now, we need the two agents to be on the same node:
         let a2: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a2.clone()).await?;
+        p2p.join(space.clone(), a2.clone(), None).await?;
         let a3: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a3.clone()).await?;
+        p2p.join(space.clone(), a3.clone(), None).await?;
 
         p2p.notify_multi(actor::NotifyMulti {
             space: space,
@@ -225,7 +1008,10 @@ mod tests {
             basis: TestVal::test_val(),
             remote_agent_count: Some(42),
             timeout_ms: Some(40),
+            max_nodes: 0,
+            priority: actor::MessagePriority::Normal,
             payload: b"test-broadcast".to_vec(),
+            msg_id: None,
         })
         .await?;
 
@@ -259,9 +1045,9 @@ mod tests {
         // TODO when networking works, just add_*_agent again...
         // but for now, we need the two agents to be on the same node:
         let a2: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a2.clone()).await?;
+        p2p.join(space.clone(), a2.clone(), None).await?;
         let a3: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a3.clone()).await?;
+        p2p.join(space.clone(), a3.clone(), None).await?;
 
         let res = p2p
             .rpc_multi(actor::RpcMulti {
@@ -273,6 +1059,8 @@ mod tests {
                 timeout_ms: Some(20),
                 as_race: true,
                 race_timeout_ms: Some(20),
+                priority: actor::MessagePriority::Normal,
+                selection: actor::AgentSelection::Any,
                 payload: b"test-multi-request".to_vec(),
             })
             .await
@@ -290,6 +1078,52 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_multi_request_stream() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+
+        let (harness, _evt) = spawn_test_harness_quic().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+        let a3: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a3.clone(), None).await?;
+
+        let input = |remote_agent_count| actor::RpcMulti {
+            space: space.clone(),
+            from_agent: a1.clone(),
+            // this is just a dummy value right now
+            basis: TestVal::test_val(),
+            remote_agent_count: Some(remote_agent_count),
+            timeout_ms: Some(1000),
+            as_race: true,
+            race_timeout_ms: Some(20),
+            priority: actor::MessagePriority::Normal,
+            selection: actor::AgentSelection::Any,
+            payload: b"test-multi-request".to_vec(),
+        };
+
+        // the stream ends once enough responses are delivered
+        let res: Vec<_> =
+            tokio_stream::StreamExt::collect(p2p.rpc_multi_stream(input(2)).await?).await;
+        assert_eq!(2, res.len());
+
+        // or once every agent has responded
+        let res: Vec<_> =
+            tokio_stream::StreamExt::collect(p2p.rpc_multi_stream(input(5)).await?).await;
+        assert_eq!(3, res.len());
+        for r in res {
+            let data = String::from_utf8_lossy(&r.response);
+            assert_eq!("echo: test-multi-request", &data);
+            assert!(r.agent == a1 || r.agent == a2 || r.agent == a3);
+        }
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_single_agent_multi_request_workflow() -> Result<(), KitsuneP2pError> {
         observability::test_run().ok();
@@ -309,6 +1143,8 @@ mod tests {
                 timeout_ms: Some(20),
                 as_race: true,
                 race_timeout_ms: Some(20),
+                priority: actor::MessagePriority::Normal,
+                selection: actor::AgentSelection::Any,
                 payload: b"test-multi-request".to_vec(),
             })
             .await
@@ -319,6 +1155,8 @@ mod tests {
             let data = String::from_utf8_lossy(&r.response);
             assert_eq!("echo: test-multi-request", &data);
             assert!(r.agent == a1);
+            // agents joined without an arc announce the full arc
+            assert!(r.authoritative);
         }
 
         harness.ghost_actor_shutdown().await.unwrap();
@@ -336,7 +1174,7 @@ mod tests {
         // TODO when networking works, just add_*_agent again...
         // but for now, we need the two agents to be on the same node:
         let a2: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a2.clone()).await?;
+        p2p.join(space.clone(), a2.clone(), None).await?;
 
         let op1 = harness
             .inject_gossip_data(a1.clone(), "agent-1-data".to_string())
@@ -380,7 +1218,7 @@ mod tests {
         assert_eq!(num_agent_info, 1);
 
         let a2: Arc<KitsuneAgent> = TestVal::test_val();
-        p2p.join(space.clone(), a2.clone()).await?;
+        p2p.join(space.clone(), a2.clone(), None).await?;
 
         tokio::time::sleep(std::time::Duration::from_millis(200)).await;
 
@@ -460,4 +1298,304 @@ mod tests {
         harness.ghost_actor_shutdown().await?;
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_agent_info() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (_a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let info = p2p1.get_agent_info(space.clone(), a2.clone()).await?;
+        assert_eq!(&*a2, info.as_agent_ref());
+
+        // no node knows about this agent
+        let unknown: Arc<KitsuneAgent> = TestVal::test_val();
+        assert!(p2p1.get_agent_info(space, unknown).await.is_err());
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejoin_policy_backoff() {
+        let ms = std::time::Duration::from_millis;
+        let policy = actor::RejoinPolicy {
+            base_delay_ms: 100,
+            max_delay_ms: 500,
+            max_attempts: 5,
+        };
+        assert_eq!(Some(ms(100)), policy.delay_for_attempt(0));
+        assert_eq!(Some(ms(200)), policy.delay_for_attempt(1));
+        assert_eq!(Some(ms(400)), policy.delay_for_attempt(2));
+        assert_eq!(Some(ms(500)), policy.delay_for_attempt(3));
+        assert_eq!(Some(ms(500)), policy.delay_for_attempt(4));
+        assert_eq!(None, policy.delay_for_attempt(5));
+
+        // retries are disabled by default
+        assert_eq!(None, actor::RejoinPolicy::default().delay_for_attempt(0));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_rejoin_policy() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+
+        p2p.set_rejoin_policy(actor::RejoinPolicy {
+            base_delay_ms: 10,
+            max_delay_ms: 100,
+            max_attempts: 3,
+        })
+        .await?;
+
+        // agents joined after the policy is set are tracked as usual
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+        let mut joined = p2p.list_joined(space).await?;
+        joined.sort();
+        let mut expected = vec![a1, a2];
+        expected.sort();
+        assert_eq!(expected, joined);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_bootstrap_services() {
+        let mut config = KitsuneP2pConfig::default();
+        assert!(config.all_bootstrap_services().is_empty());
+
+        let a = url2::url2!("https://bootstrap-a.example");
+        let b = url2::url2!("https://bootstrap-b.example");
+        config.bootstrap_service = Some(a.clone());
+        config.bootstrap_services = vec![b.clone(), a.clone()];
+        // the primary service is tried first, and never twice
+        assert_eq!(vec![a, b], config.all_bootstrap_services());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_add_bootstrap() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+
+        assert!(p2p.add_bootstrap("not a url".into()).await.is_err());
+
+        // nothing is listening here, so the bootstrap service is unreachable
+        p2p.add_bootstrap("http://127.0.0.1:1".into()).await?;
+
+        // joining fails, as our agent info can't be published anywhere,
+        // but the agent is still joined locally, and will be re-announced
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        assert!(p2p.join(space.clone(), a2.clone(), None).await.is_err());
+        let mut joined = p2p.list_joined(space).await?;
+        joined.sort();
+        let mut expected = vec![a1, a2];
+        expected.sort();
+        assert_eq!(expected, joined);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_space_config() {
+        let node = KitsuneP2pConfig::default();
+        assert_eq!(
+            node,
+            node.with_space_config(&SpaceTransportConfig::default())
+        );
+
+        let mut space_config = SpaceTransportConfig::default();
+        space_config.network_type = Some(NetworkType::QuicMdns);
+        space_config.transport_security = Some(TransportSecurityConfig::insecure());
+        let config = node.with_space_config(&space_config);
+        assert_eq!(NetworkType::QuicMdns, config.network_type);
+        assert!(!config.transport_security.verify_remote_identity);
+        // everything else is the node's
+        assert_eq!(node.tuning_params, config.tuning_params);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_configure_space() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        // reconfigure the space while agents are joined
+        let mut tuning_params =
+            kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::default();
+        tuning_params.default_rpc_single_timeout_ms = 100;
+        let mut space_config = SpaceTransportConfig::default();
+        space_config.tuning_params = Some(Arc::new(tuning_params));
+        p2p.configure_space(space.clone(), space_config).await?;
+
+        // the harness never replies to this payload, so the request
+        // is bounded by the space's default timeout, not the node's
+        let start = std::time::Instant::now();
+        let res = p2p
+            .rpc_single(space.clone(), a2, a1, b"no-reply".to_vec(), None)
+            .await;
+        assert!(matches!(res, Err(KitsuneP2pError::Timeout)));
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+        // spaces can be configured before anyone joins
+        let other_space = harness.add_space().await?;
+        p2p.configure_space(other_space, SpaceTransportConfig::default())
+            .await?;
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_joined() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p) = harness.add_direct_agent("DIRECT".into()).await?;
+        let a2: Arc<KitsuneAgent> = TestVal::test_val();
+        p2p.join(space.clone(), a2.clone(), None).await?;
+
+        let mut joined = p2p.list_joined(space.clone()).await?;
+        joined.sort();
+        let mut expected = vec![a1.clone(), a2.clone()];
+        expected.sort();
+        assert_eq!(expected, joined);
+
+        p2p.leave(space.clone(), a2, false).await?;
+        assert_eq!(vec![a1], p2p.list_joined(space.clone()).await?);
+
+        // an unknown space simply has no joined agents
+        let other: Arc<KitsuneSpace> = TestVal::test_val();
+        assert!(p2p.list_joined(other).await?.is_empty());
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_leave_announce() -> Result<(), KitsuneP2pError> {
+        use std::convert::TryFrom;
+
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (_a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let info = p2p1.get_agent_info(space.clone(), a2.clone()).await?;
+        assert!(!agent_store::AgentInfo::try_from(&info)?
+            .as_urls_ref()
+            .is_empty());
+
+        p2p2.leave(space.clone(), a2.clone(), true).await?;
+        assert!(p2p2.list_joined(space.clone()).await?.is_empty());
+
+        // by the time leave returns, "one" holds the tombstone in place of
+        // the agent's info, so it neither routes to the agent nor treats it
+        // as an authority, without waiting for the info to expire
+        let info = p2p1.get_agent_info(space.clone(), a2.clone()).await?;
+        let info = agent_store::AgentInfo::try_from(&info)?;
+        assert!(info.as_urls_ref().is_empty());
+        assert_eq!(0, info.dht_arc()?.half_length);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    /// Test that closing a connection leaves both agents joined and reachable.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_close_keeps_membership() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        let r = p2p1
+            .rpc_single(space.clone(), a2.clone(), a1.clone(), b"m1".to_vec(), None)
+            .await?;
+        assert_eq!(b"echo: m1".to_vec(), r);
+
+        p2p1.close(actor::Close {
+            space: space.clone(),
+            to_agent: a2.clone(),
+            code: 42,
+            reason: "test close".into(),
+        })
+        .await?;
+
+        // a new connection is established on demand
+        let r = p2p1
+            .rpc_single(space.clone(), a2.clone(), a1.clone(), b"m2".to_vec(), None)
+            .await?;
+        assert_eq!(b"echo: m2".to_vec(), r);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
+
+    /// Test that we can compare held op hashes with a remote agent on demand.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_gossip_hashes() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        let space = harness.add_space().await?;
+        let (a1, p2p1) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _p2p2) = harness.add_direct_agent("two".into()).await?;
+
+        let op2 = harness
+            .inject_gossip_data(a2.clone(), "agent-2-data".to_string())
+            .await?;
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        // an op hash that agent two cannot be holding
+        let unknown: Arc<KitsuneOpHash> = TestVal::test_val();
+
+        let res = p2p1
+            .gossip_hashes(actor::Gossip {
+                space,
+                from_agent: a1,
+                to_agent: a2,
+                since_utc_epoch_s: i64::MIN,
+                until_utc_epoch_s: i64::MAX,
+                op_hashes: vec![unknown.clone()],
+            })
+            .await?;
+
+        assert!(res.missing_locally.contains(&op2));
+        assert!(!res.missing_locally.contains(&unknown));
+        assert_eq!(vec![unknown], res.missing_remotely);
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
 }
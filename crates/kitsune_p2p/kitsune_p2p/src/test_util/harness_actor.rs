@@ -1,4 +1,6 @@
 use super::*;
+use crate::config::MemSimConfig;
+use kitsune_p2p_types::config::KitsuneP2pTuningParams;
 
 ghost_actor::ghost_chan! {
     /// The api for the test harness controller
@@ -52,6 +54,26 @@ pub async fn spawn_test_harness_mem() -> Result<
     spawn_test_harness(TransportConfig::Mem {}).await
 }
 
+/// construct a test suite around a mem transport that simulates a real
+/// network, adding `latency_ms` to every message and dropping `drop_rate`
+/// (0.0 to 1.0) of them. Drops are spread evenly, so tests are deterministic.
+pub async fn spawn_test_harness_mem_sim(
+    latency_ms: u32,
+    drop_rate: f32,
+) -> Result<
+    (
+        ghost_actor::GhostSender<HarnessControlApi>,
+        HarnessEventChannel,
+    ),
+    KitsuneP2pError,
+> {
+    let mem_sim = MemSimConfig {
+        latency: std::time::Duration::from_millis(latency_ms as u64),
+        drop_rate: f64::from(drop_rate),
+    };
+    spawn_test_harness_inner(TransportConfig::Mem {}, Default::default(), mem_sim).await
+}
+
 /// construct a test suite around a quic transport
 pub async fn spawn_test_harness_quic() -> Result<
     (
@@ -77,6 +99,35 @@ pub async fn spawn_test_harness(
         HarnessEventChannel,
     ),
     KitsuneP2pError,
+> {
+    spawn_test_harness_with_tuning_params(sub_config, Default::default()).await
+}
+
+/// construct a test suite around a sub transport config concept,
+/// with the given tuning params for every agent's node
+pub async fn spawn_test_harness_with_tuning_params(
+    sub_config: TransportConfig,
+    tuning_params: KitsuneP2pTuningParams,
+) -> Result<
+    (
+        ghost_actor::GhostSender<HarnessControlApi>,
+        HarnessEventChannel,
+    ),
+    KitsuneP2pError,
+> {
+    spawn_test_harness_inner(sub_config, tuning_params, MemSimConfig::default()).await
+}
+
+async fn spawn_test_harness_inner(
+    sub_config: TransportConfig,
+    tuning_params: KitsuneP2pTuningParams,
+    mem_sim: MemSimConfig,
+) -> Result<
+    (
+        ghost_actor::GhostSender<HarnessControlApi>,
+        HarnessEventChannel,
+    ),
+    KitsuneP2pError,
 > {
     let harness_chan = HarnessEventChannel::new("");
 
@@ -92,7 +143,13 @@ pub async fn spawn_test_harness(
         .create_channel::<HarnessInner>()
         .await?;
 
-    tokio::task::spawn(builder.spawn(HarnessActor::new(i_s, harness_chan.clone(), sub_config)));
+    tokio::task::spawn(builder.spawn(HarnessActor::new(
+        i_s,
+        harness_chan.clone(),
+        sub_config,
+        tuning_params,
+        mem_sim,
+    )));
 
     Ok((controller, harness_chan))
 }
@@ -112,6 +169,8 @@ struct HarnessActor {
     i_s: ghost_actor::GhostSender<HarnessInner>,
     harness_chan: HarnessEventChannel,
     sub_config: TransportConfig,
+    tuning_params: KitsuneP2pTuningParams,
+    mem_sim: MemSimConfig,
     space_list: Vec<Arc<KitsuneSpace>>,
     agents: HashMap<
         Arc<KitsuneAgent>,
@@ -127,11 +186,15 @@ impl HarnessActor {
         i_s: ghost_actor::GhostSender<HarnessInner>,
         harness_chan: HarnessEventChannel,
         sub_config: TransportConfig,
+        tuning_params: KitsuneP2pTuningParams,
+        mem_sim: MemSimConfig,
     ) -> Self {
         Self {
             i_s,
             harness_chan,
             sub_config,
+            tuning_params,
+            mem_sim,
             space_list: Vec::new(),
             agents: HashMap::new(),
         }
@@ -170,7 +233,7 @@ impl HarnessInnerHandler for HarnessActor {
         let space_list = self.space_list.clone();
         Ok(async move {
             for space in space_list {
-                p2p.join(space.clone(), agent.clone()).await?;
+                p2p.join(space.clone(), agent.clone(), None).await?;
 
                 harness_chan.publish(HarnessEventType::Join {
                     agent: (&agent).into(),
@@ -192,7 +255,7 @@ impl HarnessControlApiHandler for HarnessActor {
         self.space_list.push(space.clone());
         let mut all = Vec::new();
         for (agent, (p2p, _)) in self.agents.iter() {
-            all.push(p2p.join(space.clone(), agent.clone()));
+            all.push(p2p.join(space.clone(), agent.clone(), None));
         }
         Ok(async move {
             futures::future::try_join_all(all).await?;
@@ -208,6 +271,8 @@ impl HarnessControlApiHandler for HarnessActor {
     ) -> HarnessControlApiHandlerResult<(Arc<KitsuneAgent>, ghost_actor::GhostSender<KitsuneP2p>)>
     {
         let mut proxy_agent_config = KitsuneP2pConfig::default();
+        proxy_agent_config.tuning_params = self.tuning_params.clone();
+        proxy_agent_config.mem_sim = self.mem_sim;
         proxy_agent_config
             .transport_pool
             .push(TransportConfig::Proxy {
@@ -236,6 +301,8 @@ impl HarnessControlApiHandler for HarnessActor {
     ) -> HarnessControlApiHandlerResult<(Arc<KitsuneAgent>, ghost_actor::GhostSender<KitsuneP2p>)>
     {
         let mut direct_agent_config = KitsuneP2pConfig::default();
+        direct_agent_config.tuning_params = self.tuning_params.clone();
+        direct_agent_config.mem_sim = self.mem_sim;
         direct_agent_config
             .transport_pool
             .push(TransportConfig::Proxy {
@@ -265,6 +332,8 @@ impl HarnessControlApiHandler for HarnessActor {
     ) -> HarnessControlApiHandlerResult<(Arc<KitsuneAgent>, ghost_actor::GhostSender<KitsuneP2p>)>
     {
         let mut nat_agent_config = KitsuneP2pConfig::default();
+        nat_agent_config.tuning_params = self.tuning_params.clone();
+        nat_agent_config.mem_sim = self.mem_sim;
         nat_agent_config
             .transport_pool
             .push(TransportConfig::Proxy {
@@ -172,9 +172,29 @@ impl KitsuneP2pEventHandler for AgentHarness {
             from_agent: from_agent.into(),
             payload: data.to_string(),
         });
+        if data == "no-reply" {
+            // simulate a peer which never responds
+            return Ok(
+                futures::future::pending::<Result<Vec<u8>, KitsuneP2pError>>()
+                    .boxed()
+                    .into(),
+            );
+        }
+        // simulate a slow peer, e.g. "delay:200" replies after 200ms
+        let delay = data
+            .strip_prefix("delay:")
+            .and_then(|ms| ms.parse().ok())
+            .map(std::time::Duration::from_millis);
         let data = format!("echo: {}", data);
         let data = data.into_bytes();
-        Ok(async move { Ok(data) }.boxed().into())
+        Ok(async move {
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+            Ok(data)
+        }
+        .boxed()
+        .into())
     }
 
     fn handle_notify(
@@ -4,13 +4,26 @@ use std::sync::Arc;
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum KitsuneP2pError {
-    /// GhostError
+    /// GhostError, other than a disconnect, which is reported as `Shutdown`
     #[error(transparent)]
-    GhostError(#[from] ghost_actor::GhostError),
+    GhostError(ghost_actor::GhostError),
 
-    /// Base Kitsune Error
+    /// Base Kitsune Error, other than a timeout, which is reported as `Timeout`
     #[error(transparent)]
-    KitsuneError(#[from] kitsune_p2p_types::KitsuneError),
+    KitsuneError(kitsune_p2p_types::KitsuneError),
+
+    /// The operation did not complete within its timeout.
+    #[error("Timeout")]
+    Timeout,
+
+    /// No peers could be found to route the operation to,
+    /// neither in our peer store nor from the bootstrap service.
+    #[error("No Peers Found")]
+    NoPeersFound,
+
+    /// The actor, or one it depends on, has shut down.
+    #[error("Shutdown")]
+    Shutdown,
 
     /// RoutingSpaceError
     #[error("Routing Space Error: {0:?}")]
@@ -20,11 +33,11 @@ pub enum KitsuneP2pError {
     #[error("Routing Agent Error: {0:?}")]
     RoutingAgentError(Arc<KitsuneAgent>),
 
-    /// DecodingError
+    /// DecodingError, for any failure to (de)serialize data
     #[error("Decoding Error: {0}")]
     DecodingError(Box<str>),
 
-    /// TransportError
+    /// TransportError, for any failure of the underlying transport
     #[error(transparent)]
     TransportError(#[from] kitsune_p2p_types::transport::TransportError),
 
@@ -48,11 +61,44 @@ pub enum KitsuneP2pError {
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
 
+    /// A payload exceeded the `max_message_bytes` tuning param,
+    /// and was not sent.
+    #[error("Message Too Large: {size} bytes exceeds the limit of {limit} bytes")]
+    MessageTooLarge {
+        /// The size of the rejected payload in bytes.
+        size: usize,
+        /// The configured `max_message_bytes`.
+        limit: usize,
+    },
+
+    /// The outbound queue already holds `concurrent_outbound_multi_limit`
+    /// operations, so a `try_rpc_multi` or `try_notify_multi` was not sent.
+    #[error("Would Block: the outbound queue is full")]
+    WouldBlock,
+
     /// Other
     #[error("Other: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl From<ghost_actor::GhostError> for KitsuneP2pError {
+    fn from(e: ghost_actor::GhostError) -> Self {
+        match e {
+            ghost_actor::GhostError::Disconnected => Self::Shutdown,
+            e => Self::GhostError(e),
+        }
+    }
+}
+
+impl From<kitsune_p2p_types::KitsuneError> for KitsuneP2pError {
+    fn from(e: kitsune_p2p_types::KitsuneError) -> Self {
+        match e.kind() {
+            kitsune_p2p_types::KitsuneErrorKind::TimedOut => Self::Timeout,
+            _ => Self::KitsuneError(e),
+        }
+    }
+}
+
 impl KitsuneP2pError {
     /// promote a custom error type to a KitsuneP2pError
     pub fn other(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
@@ -63,6 +109,15 @@ impl KitsuneP2pError {
     pub fn decoding_error(s: String) -> Self {
         Self::DecodingError(s.into_boxed_str())
     }
+
+    /// did we give up waiting for the operation to complete?
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout => true,
+            Self::KitsuneError(e) => *e.kind() == kitsune_p2p_types::KitsuneErrorKind::TimedOut,
+            _ => false,
+        }
+    }
 }
 
 impl From<String> for KitsuneP2pError {
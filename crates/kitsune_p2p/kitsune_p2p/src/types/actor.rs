@@ -3,8 +3,43 @@
 use std::sync::Arc;
 use url2::Url2;
 
+/// How urgently an outbound message should be sent.
+/// When the network is congested, higher priority messages are sent
+/// ahead of lower priority ones that are still waiting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessagePriority {
+    /// Bulk traffic that can wait, e.g. background sync.
+    Low,
+    /// Most traffic.
+    Normal,
+    /// Traffic that should not be held up, e.g. validation-critical messages.
+    High,
+}
+
+impl Default for MessagePriority {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// How the agents answering an `RpcMulti` are picked from the candidates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AgentSelection {
+    /// Every candidate agent is treated equally.
+    Any,
+    /// Only the `remote_agent_count` agents whose arcs are centered
+    /// nearest the basis, i.e. those most likely to be authoritative.
+    NearestBasis,
+}
+
+impl Default for AgentSelection {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
 /// Make a request to multiple destination agents - awaiting/aggregating the responses.
-/// The remote sides will see these messages as "RequestEvt" events.
+/// The remote sides will see these messages as "Call" events.
 #[derive(Clone, Debug)]
 pub struct RpcMulti {
     /// The "space" context.
@@ -13,9 +48,9 @@ pub struct RpcMulti {
     pub from_agent: Arc<super::KitsuneAgent>,
     /// The "basis" hash/coordinate of destination neigborhood.
     pub basis: Arc<super::KitsuneBasis>,
-    /// See docs on Broadcast
+    /// See docs on NotifyMulti
     pub remote_agent_count: Option<u8>,
-    /// See docs on Broadcast
+    /// See docs on NotifyMulti
     pub timeout_ms: Option<u64>,
     /// We are interested in speed. If `true` and we have any results
     /// when `race_timeout_ms` is expired, those results will be returned.
@@ -25,6 +60,35 @@ pub struct RpcMulti {
     /// See `as_race` for details.
     /// Set to `None` for a default "best-effort" race.
     pub race_timeout_ms: Option<u64>,
+    /// See docs on MessagePriority
+    pub priority: MessagePriority,
+    /// See docs on AgentSelection
+    pub selection: AgentSelection,
+    /// Request data.
+    pub payload: Vec<u8>,
+}
+
+/// Make a request of a single agent, as `rpc_single`, but if that fails
+/// retry with the other agents located nearest the "basis" in turn, eg. for
+/// a get that any agent holding the basis could answer.
+/// The remote side will see the message as a "Call" event.
+#[derive(Clone, Debug)]
+pub struct RpcSingleWithFallback {
+    /// The "space" context.
+    pub space: Arc<super::KitsuneSpace>,
+    /// The agent to try first.
+    pub to_agent: Arc<super::KitsuneAgent>,
+    /// The agent making the request.
+    pub from_agent: Arc<super::KitsuneAgent>,
+    /// The "basis" hash/coordinate the fallback agents are chosen nearest to.
+    pub basis: Arc<super::KitsuneBasis>,
+    /// How many other agents to try after `to_agent` fails, nearest first.
+    /// Set to 0 to behave exactly as `rpc_single`.
+    pub max_retries: u8,
+    /// The timeout for each attempt, so the whole request may take up to
+    /// `max_retries + 1` times this long.
+    /// Set to `None` for the default rpc_single timeout.
+    pub attempt_timeout_ms: Option<u64>,
     /// Request data.
     pub payload: Vec<u8>,
 }
@@ -36,8 +100,14 @@ pub struct RpcMultiResponse {
     pub agent: Arc<super::KitsuneAgent>,
     /// Response data.
     pub response: Vec<u8>,
+    /// `true` if the responding agent's storage arc covers the basis,
+    /// `false` if it merely forwarded or answered from partial data.
+    pub authoritative: bool,
 }
 
+/// Responses to an `rpc_multi_stream` request, delivered as they arrive.
+pub type RpcMultiStream = futures::channel::mpsc::Receiver<RpcMultiResponse>;
+
 /// Publish data to a "neighborhood" of remote nodes surrounding the "basis" hash.
 /// Returns an approximate number of nodes reached.
 #[derive(Clone, Debug)]
@@ -59,8 +129,195 @@ pub struct NotifyMulti {
     /// broadcast will immediately return 0, but give a best effort to meet
     /// remote_agent_count.
     pub timeout_ms: Option<u64>,
+    /// The maximum number of remote nodes to send to, regardless of how many
+    /// have acknowledged. Set to 0 to use the network default.
+    pub max_nodes: u32,
+    /// See docs on MessagePriority
+    pub priority: MessagePriority,
     /// Notify data.
     pub payload: Vec<u8>,
+    /// Identifies this notify, so that a node reached along more than
+    /// one path only handles it once. Set to None to generate a new id.
+    /// Only reuse an id to resend the same notify.
+    pub msg_id: Option<u64>,
+}
+
+/// Publish DHT ops to a "neighborhood" of remote nodes surrounding the "basis" hash.
+/// Returns the number of remote nodes that accepted the ops.
+#[derive(Clone, Debug)]
+pub struct PublishOps {
+    /// The "space" context.
+    pub space: Arc<super::KitsuneSpace>,
+    /// The agent publishing the ops.
+    pub from_agent: Arc<super::KitsuneAgent>,
+    /// The "basis" hash/coordinate of destination neigborhood.
+    pub basis: Arc<super::KitsuneBasis>,
+    /// See docs on NotifyMulti
+    pub remote_agent_count: Option<u8>,
+    /// The timeout to await for nodes to accept the ops.
+    /// Set to None to use the network default.
+    pub timeout_ms: Option<u64>,
+    /// See docs on MessagePriority
+    pub priority: MessagePriority,
+    /// The (op hash, op data) pairs to publish.
+    /// If an op hash is listed more than once, only the first is sent.
+    pub ops: Vec<(Arc<super::KitsuneOpHash>, Vec<u8>)>,
+}
+
+/// Exchange lists of held op hashes with a remote agent, to discover which ops
+/// each side is missing. This is the on-demand form of the background gossip
+/// loop, covering the full dht arc within the given time window.
+#[derive(Clone, Debug)]
+pub struct Gossip {
+    /// The "space" context.
+    pub space: Arc<super::KitsuneSpace>,
+    /// The agent initiating the gossip, and holding `op_hashes`.
+    pub from_agent: Arc<super::KitsuneAgent>,
+    /// The agent to compare held op hashes with.
+    pub to_agent: Arc<super::KitsuneAgent>,
+    /// Only compare ops received since this time (INCLUSIVE).
+    pub since_utc_epoch_s: i64,
+    /// Only compare ops received until this time (EXCLUSIVE).
+    pub until_utc_epoch_s: i64,
+    /// The op hashes held by `from_agent` within the time window.
+    pub op_hashes: Vec<Arc<super::KitsuneOpHash>>,
+}
+
+/// The set difference of held op hashes, in both directions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GossipResponse {
+    /// Op hashes held by `to_agent`, but not in the provided `op_hashes`.
+    pub missing_locally: Vec<Arc<super::KitsuneOpHash>>,
+    /// Op hashes in the provided `op_hashes`, but not held by `to_agent`.
+    pub missing_remotely: Vec<Arc<super::KitsuneOpHash>>,
+}
+
+/// Gracefully close the transport connection to a remote agent's node.
+/// Unlike `leave`, this has no effect on DHT membership: both the local and
+/// remote agents remain joined, and a later request to the same agent will
+/// simply establish a new connection.
+#[derive(Clone, Debug)]
+pub struct Close {
+    /// The "space" context.
+    pub space: Arc<super::KitsuneSpace>,
+    /// The remote agent whose connection should be closed.
+    pub to_agent: Arc<super::KitsuneAgent>,
+    /// Application-defined reason code, forwarded to the remote node.
+    pub code: u32,
+    /// Human-readable reason, forwarded to the remote node.
+    pub reason: String,
+}
+
+/// Counts of outbound operations currently pending in the actor.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KitsuneP2pStats {
+    /// `rpc_single` requests awaiting a response.
+    pub rpc_single_in_flight: u64,
+    /// `notify_single` messages awaiting receipt by the remote node.
+    pub notify_single_in_flight: u64,
+    /// `rpc_multi` requests still aggregating responses.
+    pub rpc_multi_in_flight: u64,
+    /// `notify_multi`, `notify_multi_detailed` and `publish_ops` publishes
+    /// still awaiting acks.
+    /// Publishes with no timeout return immediately and are not counted.
+    pub notify_multi_in_flight: u64,
+    /// Operations accepted but not yet dispatched, because their space
+    /// is still being initialized, or they are waiting their turn in
+    /// the priority ordered outbound queue.
+    pub outbound_queue_depth: u64,
+}
+
+/// How close a space's gossip is to consistency with its neighbors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GossipStats {
+    /// Op hashes held by the local agent that gossiped most recently.
+    pub held_hashes: u64,
+    /// Op hashes sent to neighbors since the space was joined.
+    pub hashes_sent: u64,
+    /// Op hashes received from neighbors since the space was joined.
+    pub hashes_received: u64,
+    /// Op hashes one side was missing at the last exchange between each
+    /// local agent and neighbor, summed over all of them.
+    /// This reaches 0 once every neighbor has been gossiped with
+    /// and nothing new has been authored since.
+    pub outstanding_divergence: u64,
+}
+
+/// How to retry re-announcing joined agents after the periodic agent info
+/// update fails, e.g. due to a lost network connection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RejoinPolicy {
+    /// The delay before the first retry.
+    pub base_delay_ms: u64,
+    /// The delay doubles after each failed retry, up to this limit.
+    pub max_delay_ms: u64,
+    /// Give up retrying after this many attempts, and wait for the next
+    /// periodic update instead. Set to 0 to disable retries.
+    pub max_attempts: u32,
+}
+
+impl Default for RejoinPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: 1000,
+            max_delay_ms: 1000 * 60,
+            max_attempts: 0,
+        }
+    }
+}
+
+impl RejoinPolicy {
+    /// The delay before the given retry attempt (starting at 0),
+    /// or `None` if we should stop retrying.
+    pub fn delay_for_attempt(&self, attempt: u32) -> Option<std::time::Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let delay_ms = self
+            .base_delay_ms
+            .saturating_mul(2_u64.saturating_pow(attempt));
+        Some(std::time::Duration::from_millis(std::cmp::min(
+            delay_ms,
+            self.max_delay_ms,
+        )))
+    }
+}
+
+/// How the gossip module picks which peers to gossip with each round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GossipStrategy {
+    /// A random selection of the known peers.
+    Random,
+    /// The peers located nearest the gossiping agent,
+    /// i.e. those most likely to hold the same data.
+    NearestArc,
+    /// The peers we most recently exchanged new data with,
+    /// followed by the rest in random order.
+    RecentlyActive,
+}
+
+/// The cadence and peer selection of gossip.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GossipConfig {
+    /// The delay between gossip loop iterations, i.e. between exchanges
+    /// with each selected peer. `None` uses the space's
+    /// `gossip_loop_iteration_delay_ms` tuning param.
+    pub interval_ms: Option<u64>,
+    /// How many peers each local agent gossips with per round.
+    /// `None` gossips with every known peer, as full-sync requires.
+    pub peers_per_round: Option<usize>,
+    /// How the peers for each round are picked.
+    pub strategy: GossipStrategy,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: None,
+            peers_per_round: None,
+            strategy: GossipStrategy::Random,
+        }
+    }
 }
 
 ghost_actor::ghost_chan! {
@@ -69,23 +326,164 @@ ghost_actor::ghost_chan! {
         /// Get the calculated transport bindings.
         fn list_transport_bindings() -> Vec<Url2>;
 
-        /// Announce a space/agent pair on this network.
-        fn join(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>) -> ();
+        /// Get counts of outbound operations currently pending in the actor.
+        fn pending_stats() -> KitsuneP2pStats;
+
+        /// Set how joined agents are re-announced after a failed
+        /// agent info update. Applies to all current and future spaces.
+        fn set_rejoin_policy(policy: RejoinPolicy) -> ();
+
+        /// Set how often gossip runs and which peers it selects.
+        /// Applies to all current and future spaces, from their
+        /// next gossip round.
+        fn set_gossip_config(cfg: GossipConfig) -> ();
+
+        /// Set how many transport connections may be open at once, closing
+        /// the least recently used ones whenever there are more. 0 means no
+        /// limit. Closed connections are re-established on their next use.
+        /// Initially the `connection_limit` tuning param.
+        fn set_connection_limit(max: usize) -> ();
+
+        /// Hold back each `notify_multi` (and `notify_multi_detailed`) by a
+        /// random delay of up to `max`, so when many nodes notify in reaction
+        /// to the same event their traffic is spread out rather than arriving
+        /// in a burst. Applies to notifies made after it is set.
+        /// Initially zero, ie. notifies are sent immediately.
+        fn set_notify_jitter(max: std::time::Duration) -> ();
+
+        /// Add a bootstrap service to try after those already configured.
+        /// Applies to all current and future spaces. Agents joined to a
+        /// space publish their agent info to every bootstrap service, and
+        /// seed the peer table from the first one that responds. If none
+        /// can be reached, `join` returns an error, but the agent remains
+        /// joined locally, and publishing and discovery are retried.
+        fn add_bootstrap(url: String) -> ();
+
+        /// Shut down this actor, waiting up to `drain_timeout_ms` for
+        /// outstanding `rpc_single`, `notify_single`, `rpc_multi`,
+        /// `notify_multi` and `publish_ops` operations to complete before
+        /// tearing down the transports. New operations are rejected with
+        /// `KitsuneP2pError::Shutdown` as soon as the shutdown begins, and
+        /// any still outstanding when the timeout elapses resolve with it.
+        fn shutdown(drain_timeout_ms: u64) -> ();
+
+        /// Set transport preferences for a space, overriding the node's
+        /// config. May be called before any agent joins the space.
+        /// If the space already has agents joined, requests made after
+        /// reconfiguring use the new settings, but agents already joined
+        /// keep discovering peers as they did when they joined, until
+        /// they rejoin, and gossip keeps the tuning the space started with.
+        fn configure_space(space: Arc<super::KitsuneSpace>, config: crate::SpaceTransportConfig) -> ();
+
+        /// Announce a space/agent pair on this network, along with the
+        /// storage arc the agent covers. `None` announces the full arc.
+        /// Arcs are always centered on the agent's location, so only the
+        /// half length of the given arc is announced.
+        /// Joining again with a different arc updates the announcement.
+        fn join(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>, arc: Option<kitsune_p2p_types::dht_arc::DhtArc>) -> ();
 
         /// Withdraw this space/agent pair from this network.
-        fn leave(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>) -> ();
+        /// This affects DHT membership - to drop a connection while
+        /// remaining joined, see `close`.
+        ///
+        /// If `announce` is set, the agent signs a tombstone: agent info
+        /// with no urls and an empty arc. It is put in our peer store, and
+        /// sent to every node in it, which put it in theirs in place of
+        /// the agent's earlier info, so the agent is neither routed to nor
+        /// treated as an authority straight away, rather than once that
+        /// info expires. The tombstone is remembered until it expires in
+        /// turn, after `agent_info_expires_after_ms`, by which time any
+        /// info the agent signed before leaving has expired too. Nodes
+        /// that can't be reached are skipped, and still find out when
+        /// the agent's earlier info expires.
+        fn leave(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>, announce: bool) -> ();
+
+        /// Get the signed agent info for an agent, from our peer store if held,
+        /// otherwise by querying remote nodes. Info fetched from remote nodes
+        /// is added to our peer store.
+        fn get_agent_info(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>) -> super::agent_store::AgentInfoSigned;
+
+        /// List the agents currently joined to a space on this node.
+        fn list_joined(space: Arc<super::KitsuneSpace>) -> Vec<Arc<super::KitsuneAgent>>;
+
+        /// Get the average recent round-trip time of requests to each remote
+        /// agent in a space. Agents we have not yet had a response from
+        /// are not included.
+        fn peer_latencies(space: Arc<super::KitsuneSpace>) -> Vec<(Arc<super::KitsuneAgent>, std::time::Duration)>;
+
+        /// Get counters describing how close gossip in a space is to consistency.
+        fn gossip_stats(space: Arc<super::KitsuneSpace>) -> GossipStats;
+
+        /// Check whether an agent is reachable, without involving any
+        /// application logic on the remote side. Returns the round-trip time,
+        /// or `None` if the agent could not be reached within `timeout_ms`
+        /// (or the default rpc_single timeout, if unset).
+        /// Locally joined agents are always reachable, with a zero round-trip time.
+        fn ping(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>, timeout_ms: Option<u64>) -> Option<std::time::Duration>;
 
         /// Make a request of a single remote agent, expecting a response.
         /// The remote side will receive a "Call" event.
         fn rpc_single(space: Arc<super::KitsuneSpace>, to_agent: Arc<super::KitsuneAgent>, from_agent: Arc<super::KitsuneAgent>, payload: Vec<u8>, timeout_ms: Option<u64>) -> Vec<u8>;
 
+        /// As `rpc_single`, but if `to_agent` can't answer, retry with up to
+        /// `max_retries` other agents, nearest the basis first.
+        /// Returns the first response, or the error from the last attempt.
+        fn rpc_single_with_fallback(input: RpcSingleWithFallback) -> Vec<u8>;
+
+        /// Send data to a single agent without awaiting a response.
+        /// Resolves once the remote node has received the message, not once
+        /// it has been handled. Delivery is best-effort and at-most-once:
+        /// the message is never retried, so may be lost if the remote fails
+        /// to handle it, but is never delivered twice.
+        /// Fails if the remote node can't be reached within `timeout_ms`
+        /// (or the default rpc_single timeout, if unset).
+        /// The remote side will receive a "Notify" event.
+        fn notify_single(space: Arc<super::KitsuneSpace>, to_agent: Arc<super::KitsuneAgent>, from_agent: Arc<super::KitsuneAgent>, payload: Vec<u8>, timeout_ms: Option<u64>) -> ();
+
         /// Make a request to multiple destination agents - awaiting/aggregating the responses.
         /// The remote sides will see these messages as "Call" events.
         fn rpc_multi(input: RpcMulti) -> Vec<RpcMultiResponse>;
 
+        /// As `rpc_multi`, but responses are streamed back as they arrive,
+        /// rather than aggregated. The stream ends once `remote_agent_count`
+        /// responses have been delivered, or `timeout_ms` has elapsed.
+        fn rpc_multi_stream(input: RpcMulti) -> RpcMultiStream;
+
         /// Publish data to a "neighborhood" of remote nodes surrounding the "basis" hash.
         /// Returns an approximate number of nodes reached.
         /// The remote sides will see these messages as "Notify" events.
         fn notify_multi(input: NotifyMulti) -> u8;
+
+        /// As `notify_multi`, but returns the remote agents that acknowledged
+        /// the notify before `timeout_ms` elapsed (or the default timeout,
+        /// if unset), rather than a count. Local agents are notified but,
+        /// as with `notify_multi`, not included in the result.
+        fn notify_multi_detailed(input: NotifyMulti) -> Vec<Arc<super::KitsuneAgent>>;
+
+        /// As `rpc_multi`, but fails immediately with `WouldBlock` rather than
+        /// waiting its turn if the outbound queue is full, so the caller can
+        /// shed or batch its own load. The queue holds as many operations as the
+        /// `concurrent_outbound_multi_limit` tuning param (32 by default), shared
+        /// by all rpc_multi and notify_multi calls on this node.
+        fn try_rpc_multi(input: RpcMulti) -> Vec<RpcMultiResponse>;
+
+        /// As `notify_multi`, but fails immediately with `WouldBlock` rather than
+        /// waiting its turn if the outbound queue is full, see `try_rpc_multi`.
+        /// The notify is still held back by any `set_notify_jitter`.
+        fn try_notify_multi(input: NotifyMulti) -> u8;
+
+        /// Publish DHT ops to a "neighborhood" of remote nodes surrounding the "basis" hash.
+        /// Returns how many remote nodes accepted the ops.
+        /// The remote sides will see each op as a "Gossip" event.
+        fn publish_ops(input: PublishOps) -> u32;
+
+        /// Exchange held op hash lists with a remote agent.
+        /// Returns which op hashes each side is missing.
+        fn gossip_hashes(input: Gossip) -> GossipResponse;
+
+        /// Tear down the transport connection to a remote agent, notifying
+        /// the remote node with the given reason code.
+        /// Does not affect DHT membership - see `leave` for that.
+        fn close(input: Close) -> ();
     }
 }
@@ -70,6 +70,21 @@ impl AgentInfoSigned {
     pub fn as_agent_info_ref(&self) -> &[u8] {
         self.agent_info.as_ref()
     }
+
+    /// Check that the signature is the agent's own signature over the agent info.
+    pub async fn verify_signature(&self) -> Result<bool, KitsuneP2pError> {
+        use lair_keystore_api::internal::sign_ed25519::*;
+        let pub_key = self.agent.get_bytes();
+        if pub_key.len() != 32 || self.signature.0.len() != 64 {
+            return Ok(false);
+        }
+        let pub_key: SignEd25519PubKey = pub_key.to_vec().into();
+        let sig: SignEd25519Signature = self.signature.0.clone().into();
+        pub_key
+            .verify(std::sync::Arc::new(self.agent_info.clone()), sig)
+            .await
+            .map_err(KitsuneP2pError::other)
+    }
 }
 
 /// Value that an agent signs to represent themselves on the network.
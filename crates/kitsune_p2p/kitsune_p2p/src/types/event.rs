@@ -72,7 +72,13 @@ pub struct QueryAgentInfoSignedEvt {
 
 ghost_actor::ghost_chan! {
     /// The KitsuneP2pEvent stream allows handling events generated from the
-    /// KitsuneP2p actor.
+    /// KitsuneP2p actor. This is how inbound messages are delivered: `call`
+    /// for `rpc_single` and `rpc_multi` requests from remote nodes, `notify`
+    /// for `notify_single` and `notify_multi`, and `gossip` for dht ops
+    /// received via gossip. The other events ask the implementor for data
+    /// or signatures the actor needs. Every event carries a responder, and
+    /// remote nodes wait on the response to `call`, so the stream must be
+    /// polled continuously for the actor to make progress.
     pub chan KitsuneP2pEvent<super::KitsuneP2pError> {
         /// We need to store signed agent info.
         fn put_agent_info_signed(input: PutAgentInfoSignedEvt) -> ();
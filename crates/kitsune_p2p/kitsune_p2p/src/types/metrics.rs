@@ -14,6 +14,8 @@ observability::metrics!(
     AgentInfoQueryResp,
     Gossip,
     GossipResp,
+    Ping,
+    PingResp,
     Fail
 );
 
@@ -36,7 +38,7 @@ pub fn print_all_metrics() {
         .expect("Failed to print metrics");
         for (metric, count) in KitsuneMetrics::iter() {
             match metric {
-                Call | Notify | FetchOpHashes | FetchOpData | AgentInfoQuery | Gossip => {
+                Call | Notify | FetchOpHashes | FetchOpData | AgentInfoQuery | Gossip | Ping => {
                     let percent = if total_writes > 0.0 {
                         count as f64 / total_writes * 100.0
                     } else {
@@ -53,7 +55,7 @@ pub fn print_all_metrics() {
                     .expect("Failed to print metrics");
                 }
                 CallResp | NotifyResp | FetchOpHashesResp | FetchOpDataResp
-                | AgentInfoQueryResp | GossipResp | Fail => {
+                | AgentInfoQueryResp | GossipResp | PingResp | Fail => {
                     let percent = if total_reads > 0.0 {
                         count as f64 / total_reads * 100.0
                     } else {
@@ -46,6 +46,25 @@ kitsune_p2p_types::write_codec_enum! {
         NotifyResp(0x21) {
         },
 
+        /// "Notify" a single remote agent. The remote acknowledges
+        /// with a NotifyResp on receipt, rather than once handled.
+        NotifySingle(0x22) {
+            space.0: Arc<KitsuneSpace>,
+            from_agent.1: Arc<KitsuneAgent>,
+            to_agent.2: Arc<KitsuneAgent>,
+            data.3: WireData,
+        },
+
+        /// "Notify" the remote, with an id so that a node reached along
+        /// more than one path only handles it once. Answered with a NotifyResp.
+        NotifyWithId(0x23) {
+            space.0: Arc<KitsuneSpace>,
+            from_agent.1: Arc<KitsuneAgent>,
+            to_agent.2: Arc<KitsuneAgent>,
+            data.3: WireData,
+            msg_id.4: u64,
+        },
+
         /// Fetch DhtOp and Agent Hashes with Constraints
         FetchOpHashes(0x31) {
             space.0: Arc<KitsuneSpace>,
@@ -103,5 +122,24 @@ kitsune_p2p_types::write_codec_enum! {
         /// Lists of data in response to FetchOpData
         GossipResp(0x51) {
         },
+
+        /// Announce that an agent has left a space, see `leave`.
+        Leave(0x70) {
+            space.0: Arc<KitsuneSpace>,
+            tombstone.1: AgentInfoSigned,
+        },
+
+        /// Response to a Leave.
+        LeaveResp(0x71) {
+        },
+
+        /// Liveness check, answered by the remote transport
+        /// without involving any application logic.
+        Ping(0x60) {
+        },
+
+        /// Response to a Ping.
+        PingResp(0x61) {
+        },
     }
 }
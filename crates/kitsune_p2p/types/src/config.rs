@@ -86,6 +86,14 @@ pub mod tuning_params_struct {
         /// Default timeout for remote notify. [Default: 30s]
         default_notify_timeout_ms: u32 = 1000 * 30,
 
+        /// Default cap on the nodes sent a remote notify. [Default: 16]
+        default_notify_max_nodes: u32 = 16,
+
+        /// The number of recently received notifies a node remembers,
+        /// so that a notify reaching it along more than one path is
+        /// only handled once. 0 disables deduplication. [Default: 1024]
+        notify_dedup_cache_size: u32 = 1024,
+
         /// Default timeout for rpc single. [Default: 30s]
         default_rpc_single_timeout_ms: u32 = 1000 * 30,
 
@@ -115,10 +123,36 @@ pub mod tuning_params_struct {
         /// on a single thread.
         concurrent_limit_per_thread: usize = 32,
 
+        /// How many rpc_multi / notify_multi operations may be sent at once.
+        /// Further operations wait, and are sent in priority order,
+        /// except try_rpc_multi / try_notify_multi, which fail with
+        /// `WouldBlock` instead. 0 is treated as 1.
+        /// [Default: 32]
+        concurrent_outbound_multi_limit: usize = 32,
+
+        /// How many recent round-trip times to average per remote agent
+        /// when reporting `peer_latencies`.
+        /// [Default: 16]
+        peer_latency_window: usize = 16,
+
+        /// The largest payload, in bytes, that may be sent with `rpc_single`,
+        /// `rpc_multi`, `notify_multi` or `publish_ops` (all op data combined).
+        /// Larger payloads are rejected with a `MessageTooLarge` error
+        /// before anything is sent, so they can be chunked by the caller.
+        /// [Default: 16 MiB]
+        max_message_bytes: usize = 16 * 1024 * 1024,
+
         /// tx2 quic max_idle_timeout
         /// [Default: 30 seconds]
         tx2_quic_max_idle_timeout_ms: u32 = 1000 * 30,
 
+        /// How many transport connections to keep open before closing
+        /// the least recently used. 0 means no limit, short of
+        /// tx2_pool_max_connection_count, at which new connections wait
+        /// for an open one to close rather than evicting it.
+        /// [Default: 0]
+        connection_limit: usize = 0,
+
         /// tx2 pool max connection count
         /// [Default: 4096]
         tx2_pool_max_connection_count: usize = 4096,
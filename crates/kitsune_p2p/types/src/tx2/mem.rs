@@ -26,6 +26,16 @@ pub struct MemConfig {
     /// Tuning Params
     /// Default: None = default.
     pub tuning_params: Option<KitsuneP2pTuningParams>,
+
+    /// Latency added to every message sent, to simulate a real network in tests.
+    /// Default: 0 = none.
+    pub latency: std::time::Duration,
+
+    /// The fraction of messages dropped, from 0.0 to 1.0, to simulate a lossy
+    /// network in tests. Drops are spread evenly rather than at random, so
+    /// tests are deterministic, eg. 0.25 drops every fourth message.
+    /// Default: 0.0 = none.
+    pub drop_rate: f64,
 }
 
 impl Default for MemConfig {
@@ -33,6 +43,8 @@ impl Default for MemConfig {
         Self {
             tls: None,
             tuning_params: None,
+            latency: std::time::Duration::from_millis(0),
+            drop_rate: 0.0,
         }
     }
 }
@@ -40,7 +52,9 @@ impl Default for MemConfig {
 impl MemConfig {
     /// into inner contents with default application
     pub async fn split(self) -> KitsuneResult<(TlsConfig, KitsuneP2pTuningParams)> {
-        let MemConfig { tls, tuning_params } = self;
+        let MemConfig {
+            tls, tuning_params, ..
+        } = self;
 
         let tls = match tls {
             None => TlsConfig::new_ephemeral().await?,
@@ -67,10 +81,68 @@ type ChanRecv = TReceiver<InChan>;
 type ConSend = TSender<Con>;
 type ConRecv = TReceiver<Con>;
 
-type EndpointItem = (ConSend, Active, Tx2Cert);
+type EndpointItem = (ConSend, Active, Tx2Cert, MemSim);
 static MEM_ENDPOINTS: Lazy<Mutex<HashMap<u64, EndpointItem>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// The simulated network conditions of a mem endpoint,
+/// from its MemConfig.
+#[derive(Clone)]
+struct MemSim {
+    latency: std::time::Duration,
+    drop_rate: f64,
+    /// Messages written so far, to spread the drops evenly.
+    sent: Arc<atomic::AtomicU64>,
+}
+
+impl MemSim {
+    fn new(config: &MemConfig) -> Self {
+        Self {
+            latency: config.latency,
+            drop_rate: config.drop_rate.max(0.0).min(1.0),
+            sent: Arc::new(atomic::AtomicU64::new(0)),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.latency > std::time::Duration::from_millis(0) || self.drop_rate > 0.0
+    }
+
+    /// Should the next message be dropped?
+    /// True each time the running total of the drop rate passes a whole number.
+    fn should_drop(&self) -> bool {
+        let n = self.sent.fetch_add(1, atomic::Ordering::Relaxed) as f64;
+        ((n + 1.0) * self.drop_rate).floor() > (n * self.drop_rate).floor()
+    }
+}
+
+/// Applies the simulated network conditions to each message written.
+/// Whole messages are dropped, so the framing of the rest is unaffected.
+struct MemSimWriter {
+    sub: OutChan,
+    sim: MemSim,
+}
+
+impl AsFramedWriter for MemSimWriter {
+    fn write(
+        &mut self,
+        msg_id: MsgId,
+        data: PoolBuf,
+        timeout: KitsuneTimeout,
+    ) -> BoxFuture<'_, KitsuneResult<()>> {
+        if self.sim.should_drop() {
+            tracing::trace!(?msg_id, "dropping message (mem)");
+            return async move { Ok(()) }.boxed();
+        }
+        let latency = self.sim.latency;
+        async move {
+            tokio::time::sleep(latency).await;
+            self.sub.write(msg_id, data, timeout).await
+        }
+        .boxed()
+    }
+}
+
 struct MemInChanRecvAdapt(BoxStream<'static, InChanFut>);
 
 impl MemInChanRecvAdapt {
@@ -117,6 +189,7 @@ struct MemConAdaptInner {
     chan_send: ChanSend,
     con_active: Active,
     mix_active: Active,
+    sim: MemSim,
 }
 
 struct MemConAdapt(MemConAdaptInner);
@@ -129,6 +202,7 @@ impl MemConAdapt {
         chan_send: ChanSend,
         con_active: Active,
         mix_active: Active,
+        sim: MemSim,
     ) -> Self {
         Self(MemConAdaptInner {
             uniq: Uniq::default(),
@@ -138,6 +212,7 @@ impl MemConAdapt {
             chan_send,
             con_active,
             mix_active,
+            sim,
         })
     }
 }
@@ -161,9 +236,13 @@ impl ConAdapt for MemConAdapt {
 
     fn out_chan(&self, _timeout: KitsuneTimeout) -> OutChanFut {
         let sender = self.0.chan_send.clone();
+        let sim = self.0.sim.clone();
         let (send, recv) = bound_async_mem_channel(4096, Some(&self.0.mix_active));
         async move {
-            let send: OutChan = Box::new(FramedWriter::new(send));
+            let mut send: OutChan = Box::new(FramedWriter::new(send));
+            if sim.is_active() {
+                send = Box::new(MemSimWriter { sub: send, sim });
+            }
             let recv: InChan = Box::new(FramedReader::new(recv));
             if sender.send(recv).await.is_err() {
                 return Err("failed to create out channel".into());
@@ -228,6 +307,7 @@ struct MemEndpointAdaptInner {
     url: TxUrl,
     ep_active: Active,
     c_send: ConSend,
+    sim: MemSim,
 }
 
 impl Drop for MemEndpointAdaptInner {
@@ -239,7 +319,7 @@ impl Drop for MemEndpointAdaptInner {
 struct MemEndpointAdapt(Mutex<MemEndpointAdaptInner>, Uniq, Tx2Cert);
 
 impl MemEndpointAdapt {
-    pub fn new(c_send: ConSend, id: u64, local_cert: Tx2Cert) -> (Self, Active) {
+    pub fn new(c_send: ConSend, id: u64, local_cert: Tx2Cert, sim: MemSim) -> (Self, Active) {
         let url = format!("kitsune-mem://{}", id);
         let ep_active = Active::new();
         (
@@ -250,6 +330,7 @@ impl MemEndpointAdapt {
                     url: url.into(),
                     ep_active: ep_active.clone(),
                     c_send,
+                    sim,
                 }),
                 Uniq::default(),
                 local_cert,
@@ -293,7 +374,7 @@ impl EndpointAdapt for MemEndpointAdapt {
     }
 
     fn connect(&self, url: TxUrl, timeout: KitsuneTimeout) -> ConFut {
-        let (this_url, local_cert, this_ep_active) = {
+        let (this_url, local_cert, this_ep_active, this_sim) = {
             let inner = self.0.lock();
             if !inner.ep_active.is_active() {
                 return async move { Err(KitsuneErrorKind::Closed.into()) }.boxed();
@@ -302,6 +383,7 @@ impl EndpointAdapt for MemEndpointAdapt {
                 inner.url.clone(),
                 inner.local_cert.clone(),
                 inner.ep_active.clone(),
+                inner.sim.clone(),
             )
         };
         async move {
@@ -323,9 +405,10 @@ impl EndpointAdapt for MemEndpointAdapt {
                 Ok(id) => id,
             };
 
-            let (c_send, oth_ep_active, remote_cert) = match MEM_ENDPOINTS.lock().get(&id) {
+            let (c_send, oth_ep_active, remote_cert, oth_sim) = match MEM_ENDPOINTS.lock().get(&id)
+            {
                 None => return Err(format!("remote not found: {}", url).into()),
-                Some((s, a, d)) => (s.clone(), a.clone(), d.clone()),
+                Some((s, a, d, sim)) => (s.clone(), a.clone(), d.clone(), sim.clone()),
             };
 
             let con_active = Active::new();
@@ -342,6 +425,7 @@ impl EndpointAdapt for MemEndpointAdapt {
                 oth_send,
                 con_active.clone(),
                 mix_active.clone(),
+                oth_sim,
             );
             let oth_con: Arc<dyn ConAdapt> = Arc::new(oth_con);
 
@@ -352,6 +436,7 @@ impl EndpointAdapt for MemEndpointAdapt {
                 send,
                 con_active,
                 mix_active.clone(),
+                this_sim,
             );
             let con: Arc<dyn ConAdapt> = Arc::new(con);
 
@@ -397,13 +482,14 @@ impl EndpointAdapt for MemEndpointAdapt {
 }
 
 /// Memory-based test endpoint adapter for kitsune tx2.
-struct MemBackendAdapt(Tx2Cert);
+struct MemBackendAdapt(Tx2Cert, MemSim);
 
 impl MemBackendAdapt {
     /// Construct a new memory-based test endpoint adapter for kitsune tx2.
     pub async fn new(config: MemConfig) -> KitsuneResult<AdapterFactory> {
+        let sim = MemSim::new(&config);
         let (tls, _tuning_params) = config.split().await?;
-        let out: AdapterFactory = Arc::new(Self(tls.cert_digest.into()));
+        let out: AdapterFactory = Arc::new(Self(tls.cert_digest.into(), sim));
         Ok(out)
     }
 }
@@ -411,14 +497,16 @@ impl MemBackendAdapt {
 impl BindAdapt for MemBackendAdapt {
     fn bind(&self, _url: TxUrl, timeout: KitsuneTimeout) -> EndpointFut {
         let local_cert = self.0.clone();
+        let sim = self.1.clone();
         timeout
             .mix(async move {
                 let id = NEXT_MEM_ID.fetch_add(1, atomic::Ordering::Relaxed);
                 let (c_send, c_recv) = t_chan(32);
-                let (ep, ep_active) = MemEndpointAdapt::new(c_send.clone(), id, local_cert.clone());
+                let (ep, ep_active) =
+                    MemEndpointAdapt::new(c_send.clone(), id, local_cert.clone(), sim.clone());
                 MEM_ENDPOINTS
                     .lock()
-                    .insert(id, (c_send, ep_active.clone(), local_cert));
+                    .insert(id, (c_send, ep_active.clone(), local_cert, sim));
                 let ep: Arc<dyn EndpointAdapt> = Arc::new(ep);
                 let url = ep.local_addr()?;
                 tracing::info!(%url, "bound local endpoint (mem)");
@@ -500,6 +588,21 @@ mod tests {
         (addr, ep)
     }
 
+    #[test]
+    fn test_tx2_mem_sim_spreads_drops() {
+        let mut config = MemConfig::default();
+        assert!(!MemSim::new(&config).is_active());
+
+        config.drop_rate = 0.25;
+        let sim = MemSim::new(&config);
+        assert!(sim.is_active());
+        let dropped = (0..8).map(|_| sim.should_drop()).collect::<Vec<_>>();
+        assert_eq!(
+            vec![false, false, false, true, false, false, false, true],
+            dropped
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_tx2_mem_stress() {
         let t = KitsuneTimeout::from_millis(5000);
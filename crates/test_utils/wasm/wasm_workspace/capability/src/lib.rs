@@ -6,15 +6,10 @@ pub struct CapFor(CapSecret, AgentPubKey);
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
     // grant unrestricted access to accept_cap_claim so other agents can send us claims
-    let mut functions: GrantedFunctions = BTreeSet::new();
-    functions.insert((zome_info()?.zome_name, "accept_cap_claim".into()));
-    // functions.insert((zome_info()?.zome_name, "needs_cap_claim".into()));
-    create_cap_grant(CapGrantEntry {
-        tag: "".into(),
-        // empty access converts to unrestricted
-        access: ().into(),
-        functions,
-    })?;
+    create_cap_grant(CapGrantEntry::unrestricted(vec![(
+        zome_info()?.zome_name,
+        "accept_cap_claim".into(),
+    )]))?;
 
     Ok(InitCallbackResult::Pass)
 }
@@ -40,6 +35,24 @@ pub fn transferable_cap_grant(secret: CapSecret) -> ExternResult<HeaderHash> {
     create_cap_grant(cap_grant_entry(secret)?)
 }
 
+/// Like `transferable_cap_grant` but tagged, so it can only be created once.
+#[hdk_extern]
+pub fn tagged_cap_grant(secret: CapSecret) -> ExternResult<HeaderHash> {
+    create_cap_grant(cap_grant_entry(secret)?.with_tag("tagged"))
+}
+
+/// Like `transferable_cap_grant` but only the given agent may use the secret.
+#[hdk_extern]
+pub fn assigned_cap_grant(cap_for: CapFor) -> ExternResult<HeaderHash> {
+    let CapFor(secret, agent) = cap_for;
+    create_cap_grant(CapGrantEntry::assigned(
+        "".into(),
+        secret,
+        vec![agent],
+        vec![(zome_info()?.zome_name, "needs_cap_claim".into())],
+    ))
+}
+
 #[hdk_extern]
 pub fn roll_cap_grant(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
     let secret = CapSecret::try_from_random()?;
@@ -51,6 +64,11 @@ pub fn delete_cap_grant(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
     hdk::prelude::delete_cap_grant(header_hash)
 }
 
+#[hdk_extern]
+fn list_cap_grants(_: ()) -> ExternResult<Vec<CapGrantEntry>> {
+    get_cap_grants()
+}
+
 #[hdk_extern]
 fn get_entry(header_hash: HeaderHash) -> ExternResult<Option<Element>> {
     get(header_hash, GetOptions::latest())
@@ -76,6 +94,7 @@ fn try_cap_claim(cap_for: crate::CapFor) -> ExternResult<ZomeCallResponse> {
         "needs_cap_claim".to_string().into(),
         Some(cap_for.0),
         &(),
+        None,
     )?)
 }
 
@@ -87,14 +106,13 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
     let secret = CapSecret::try_from_random()?;
 
     // grant the secret as assigned (can only be used by the intended agent)
-    let mut functions: GrantedFunctions = BTreeSet::new();
     let this_zome = zome_info()?.zome_name;
-    functions.insert((this_zome.clone(), "needs_cap_claim".into()));
-    create_cap_grant(CapGrantEntry {
-        access: (secret, agent.clone()).into(),
-        functions,
-        tag: tag.clone(),
-    })?;
+    create_cap_grant(CapGrantEntry::assigned(
+        tag.clone(),
+        secret,
+        vec![agent.clone()],
+        vec![(this_zome.clone(), "needs_cap_claim".into())],
+    ))?;
 
     // send the assigned cap token
     call_remote(
@@ -103,6 +121,7 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
         "accept_cap_claim".into(),
         None,
         &CapClaim::new(tag, agent_info()?.agent_latest_pubkey, secret),
+        None,
     )?;
     Ok(())
 }
@@ -100,14 +100,10 @@ fn get_activity(
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
     // grant unrestricted access to accept_cap_claim so other agents can send us claims
-    let mut functions: GrantedFunctions = BTreeSet::new();
-    functions.insert((zome_info()?.zome_name, "create_entry".into()));
-    create_cap_grant(CapGrantEntry {
-        tag: "".into(),
-        // empty access converts to unrestricted
-        access: ().into(),
-        functions,
-    })?;
+    create_cap_grant(CapGrantEntry::unrestricted(vec![(
+        zome_info()?.zome_name,
+        "create_entry".into(),
+    )]))?;
 
     Ok(InitCallbackResult::Pass)
 }
@@ -143,6 +139,7 @@ fn call_create_entry_remotely(agent: AgentPubKey) -> ExternResult<HeaderHash> {
         "create_entry".to_string().into(),
         None,
         &(),
+        None,
     )?;
 
     match zome_call_response {
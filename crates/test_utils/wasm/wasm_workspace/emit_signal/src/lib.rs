@@ -18,14 +18,10 @@ fn recv_remote_signal(signal: ExternIO) -> ExternResult<()> {
 
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
-    let mut functions: GrantedFunctions = BTreeSet::new();
-    functions.insert((zome_info()?.zome_name, "recv_remote_signal".into()));
-    create_cap_grant(CapGrantEntry {
-        tag: "".into(),
-        // empty access converts to unrestricted
-        access: ().into(),
-        functions,
-    })?;
+    create_cap_grant(CapGrantEntry::unrestricted(vec![(
+        zome_info()?.zome_name,
+        "recv_remote_signal".into(),
+    )]))?;
 
     Ok(InitCallbackResult::Pass)
 }
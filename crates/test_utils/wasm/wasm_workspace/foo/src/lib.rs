@@ -3,15 +3,10 @@ use hdk::prelude::*;
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
     // grant unrestricted access to accept_cap_claim so other agents can send us claims
-    let mut functions: GrantedFunctions = BTreeSet::new();
-    functions.insert((zome_info()?.zome_name, "foo".into()));
-    // functions.insert((zome_info()?.zome_name, "needs_cap_claim".into()));
-    create_cap_grant(CapGrantEntry {
-        tag: "".into(),
-        // empty access converts to unrestricted
-        access: ().into(),
-        functions,
-    })?;
+    create_cap_grant(CapGrantEntry::unrestricted(vec![(
+        zome_info()?.zome_name,
+        "foo".into(),
+    )]))?;
 
     Ok(InitCallbackResult::Pass)
 }
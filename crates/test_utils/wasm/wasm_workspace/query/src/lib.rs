@@ -13,6 +13,11 @@ fn query(args: QueryFilter) -> ExternResult<Vec<Element>> {
     hdk::prelude::query(args)
 }
 
+#[hdk_extern]
+fn chain_activity(input: ChainActivityInput) -> ExternResult<Vec<(u32, HeaderHash)>> {
+    hdk::prelude::chain_activity(input.start_index, input.limit)
+}
+
 #[hdk_extern]
 fn add_path(s: String) -> ExternResult<EntryHash> {
     path(&s)
@@ -1,62 +1,170 @@
 use hdk::prelude::*;
 
+zome_fns! {
+    /// The externs of this zome that other cells and agents call.
+    enum WhoamiFn { whoami, whoami_open }
+}
+
+#[hdk_extern]
+fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    // anyone may call whoami_open, without set_access
+    create_cap_grant(CapGrantEntry::unrestricted(vec![(
+        zome_info()?.zome_name,
+        WhoamiFn::WhoamiOpen.into(),
+    )]))?;
+
+    Ok(InitCallbackResult::Pass)
+}
+
+fn whoami_grant() -> ExternResult<CapGrantEntry> {
+    Ok(CapGrantEntry::unrestricted(vec![(
+        zome_info()?.zome_name,
+        WhoamiFn::Whoami.into(),
+    )]))
+}
+
 #[hdk_extern]
 fn set_access(_: ()) -> ExternResult<()> {
-    let mut functions: GrantedFunctions = BTreeSet::new();
-    functions.insert((zome_info()?.zome_name, "whoami".into()));
-    create_cap_grant(CapGrantEntry {
-        tag: "".into(),
-        // empty access converts to unrestricted
-        access: ().into(),
-        functions,
-    })?;
+    let grant = whoami_grant()?;
+    // set_access may be called more than once, don't grant access twice
+    if !get_cap_grants()?.contains(&grant) {
+        create_cap_grant(grant)?;
+    }
 
     Ok(())
 }
 
+// closes the access opened by set_access
+#[hdk_extern]
+fn revoke_access(_: ()) -> ExternResult<()> {
+    let grant = whoami_grant()?;
+    if !get_cap_grants()?.contains(&grant) {
+        return Ok(());
+    }
+    // set_access never opens a second grant while one is active,
+    // so the active grant is the latest one on the chain
+    let latest = query(
+        ChainQueryFilter::new()
+            .entry_type(EntryType::CapGrant)
+            .include_entries(true),
+    )?
+    .into_iter()
+    .filter(|element| element.entry().to_grant_option().as_ref() == Some(&grant))
+    .last();
+    if let Some(element) = latest {
+        delete_cap_grant(element.header_address().clone())?;
+    }
+
+    Ok(())
+}
+
+// like set_access but only the given agent may call whoami,
+// and only with the returned secret
+#[hdk_extern]
+fn set_access_for(agent: AgentPubKey) -> ExternResult<CapSecret> {
+    let secret = CapSecret::try_from_random()?;
+    create_cap_grant(CapGrantEntry::assigned(
+        "".into(),
+        secret,
+        vec![agent],
+        vec![(zome_info()?.zome_name, WhoamiFn::Whoami.into())],
+    ))?;
+
+    Ok(secret)
+}
+
 // returns the current agent info
 #[hdk_extern]
 fn whoami(_: ()) -> ExternResult<AgentInfo> {
     agent_info()
 }
 
+// same as whoami but open to everyone from init
+#[hdk_extern]
+fn whoami_open(_: ()) -> ExternResult<AgentInfo> {
+    agent_info()
+}
+
 // returns the agent info reported by the given pub key
 // in theory the output is the same as the input
 // it's just that the output comes _from the opinion of the remote agent_
+// so its queried_at is when the remote agent's host gathered it
 #[hdk_extern]
 fn whoarethey(agent_pubkey: AgentPubKey) -> ExternResult<AgentInfo> {
-    let zome_call_response: ZomeCallResponse = call_remote(
+    // The output type needs to match the return type of "whoami"
+    call_remote_typed::<_, AgentInfo>(
         agent_pubkey,
         zome_info()?.zome_name,
-        "whoami".to_string().into(),
+        WhoamiFn::Whoami.into(),
         None,
         &(),
+        // give up on an unresponsive remote agent after 30s
+        Some(30_000),
+    )
+}
+
+// like whoarethey but presents a secret from set_access_for,
+// so it works without the remote agent opening access to everyone
+#[hdk_extern]
+fn whoarethey_with_secret(input: (AgentPubKey, CapSecret)) -> ExternResult<AgentInfo> {
+    let (agent_pubkey, secret) = input;
+    call_remote_with_secret::<_, AgentInfo>(
+        agent_pubkey,
+        zome_info()?.zome_name,
+        WhoamiFn::Whoami.into(),
+        secret,
+        &(),
+        Some(30_000),
+    )
+}
+
+// asks the agents near the basis who they are
+// agents that didn't answer or refused the call are skipped
+#[hdk_extern]
+fn who_are_they_near(basis: AnyDhtHash) -> ExternResult<Vec<AgentInfo>> {
+    let responses = call_remote_multi(
+        basis,
+        zome_info()?.zome_name,
+        WhoamiFn::Whoami.into(),
+        None,
+        &(),
+        None,
+        None,
     )?;
-    match zome_call_response {
-        // The decode() type needs to match the return type of "whoami"
-        ZomeCallResponse::Ok(v) => Ok(v.decode()?),
-        // This should be handled in real code.
-        _ => unreachable!(),
+    let mut infos = Vec::new();
+    for (_agent, response) in responses {
+        if let ZomeCallResponse::Ok(v) = response {
+            infos.push(v.decode()?);
+        }
     }
+    Ok(infos)
 }
 
 // returns the agent info reported by the given pub key
 // in theory the output is the same as the input
 // it's just that the output comes _from the opinion of the remote agent_
+// fails with ZomeCallFailure::Unauthorized unless the other cell called set_access
 #[hdk_extern]
-fn who_are_they_local(cell_id: CellId) -> ExternResult<AgentInfo> {
-    let zome_call_response: ZomeCallResponse = call(
+fn who_are_they_local(cell_id: CellId) -> ExternResult<Result<AgentInfo, ZomeCallFailure>> {
+    try_call::<_, AgentInfo>(
         Some(cell_id),
         zome_info()?.zome_name,
-        "whoami".to_string().into(),
+        WhoamiFn::Whoami.into(),
         None,
         &(),
-    )?;
-    match zome_call_response {
-        ZomeCallResponse::Ok(v) => Ok(v.decode()?),
-        // This should be handled in real code.
-        _ => unreachable!(),
-    }
+    )
+}
+
+// like who_are_they_local but calls whoami_open, so it doesn't need set_access
+#[hdk_extern]
+fn who_are_they_local_open(cell_id: CellId) -> ExternResult<Result<AgentInfo, ZomeCallFailure>> {
+    try_call::<_, AgentInfo>(
+        Some(cell_id),
+        zome_info()?.zome_name,
+        WhoamiFn::WhoamiOpen.into(),
+        None,
+        &(),
+    )
 }
 
 /// Call the create entry zome from this zome.
@@ -66,14 +174,25 @@ fn who_are_they_local(cell_id: CellId) -> ExternResult<AgentInfo> {
 fn call_create_entry(cell_id: CellId) -> ExternResult<HeaderHash> {
     let zome_call_response: ZomeCallResponse = call(
         Some(cell_id),
-        "create_entry".to_string().into(),
-        "create_entry".to_string().into(),
+        "create_entry".into(),
+        "create_entry".into(),
         None,
         &(),
     )?;
     match zome_call_response {
         ZomeCallResponse::Ok(v) => Ok(v.decode()?),
+        ZomeCallResponse::ZomeNotFound(_, _) => Err(WasmError::Guest(
+            "the target cell has no create_entry zome".into(),
+        )),
         // This should be handled in real code.
         _ => unreachable!(),
     }
 }
+
+/// Call any zome fn in the given cell and return the response as-is
+/// so the caller can inspect why a call failed.
+#[hdk_extern]
+fn call_zome_fn(input: (CellId, ZomeName, FunctionName)) -> ExternResult<ZomeCallResponse> {
+    let (cell_id, zome_name, fn_name) = input;
+    call(Some(cell_id), zome_name, fn_name, None, &())
+}
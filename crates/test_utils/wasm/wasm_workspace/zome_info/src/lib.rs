@@ -28,8 +28,39 @@ pub mod tests {
         assert_eq!(
             result,
             Ok(
-                output
+                output.clone()
             )
         );
+
+        // served from the cache, the mock only expects one host call
+        assert_eq!(super::zome_info(()), Ok(output));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn zome_info_host_calls() {
+        let host_calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counting_hdk = |output: ZomeInfo| {
+            let mut mock_hdk = hdk::prelude::MockHdkT::new();
+            let host_calls = host_calls.clone();
+            mock_hdk.expect_zome_info().returning(move |_| {
+                host_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Ok(output.clone())
+            });
+            mock_hdk
+        };
+
+        // an extern that needs the zome info twice only reaches the host once
+        let output = fixt!(ZomeInfo);
+        hdk::prelude::set_hdk(counting_hdk(output.clone()));
+        assert_eq!(super::zome_info(()), Ok(output.clone()));
+        assert_eq!(super::zome_info(()), Ok(output));
+        assert_eq!(host_calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // a new hdk, as for a new instance, reaches the host again
+        let output = fixt!(ZomeInfo);
+        hdk::prelude::set_hdk(counting_hdk(output.clone()));
+        assert_eq!(super::zome_info(()), Ok(output.clone()));
+        assert_eq!(super::zome_info(()), Ok(output));
+        assert_eq!(host_calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}